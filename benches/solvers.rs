@@ -0,0 +1,179 @@
+use std::collections::HashMap;
+use std::convert::TryInto;
+
+use crane::algorithms::{ExpectedSize, Minimax, Naive, TwoPly};
+use crane::{bench, Correctness, Guess, Wordle};
+use criterion::{criterion_group, criterion_main, Criterion};
+
+/// Number of answers replayed in every iteration.
+const GAMES: usize = 20;
+
+/// Against the full dictionary a single game takes seconds, so the benchmarks only allow guessing
+/// the official answers.
+fn dictionary() -> Vec<(&'static str, usize)> {
+    let counts: HashMap<_, _> = crane::builtin_dictionary().collect();
+    crane::builtin_answers()
+        .map(|answer| (answer, counts[answer]))
+        .collect()
+}
+
+fn solvers(c: &mut Criterion) {
+    let dictionary = dictionary();
+    let wordle = Wordle::with_dictionary(dictionary.iter().copied());
+    let answers: Vec<_> = crane::builtin_answers().take(GAMES).collect();
+
+    let mut group = c.benchmark_group("solvers");
+    group.sample_size(10);
+
+    let naive = Naive::with_dictionary(dictionary.iter().copied());
+    group.bench_function("naive", |b| {
+        b.iter(|| bench::run(&wordle, answers.iter().copied(), || naive.clone()).unwrap())
+    });
+
+    let minimax = Minimax::with_dictionary(dictionary.iter().copied());
+    group.bench_function("minimax", |b| {
+        b.iter(|| bench::run(&wordle, answers.iter().copied(), || minimax.clone()).unwrap())
+    });
+
+    let expected_size = ExpectedSize::with_dictionary(dictionary.iter().copied());
+    group.bench_function("expected-size", |b| {
+        b.iter(|| bench::run(&wordle, answers.iter().copied(), || expected_size.clone()).unwrap())
+    });
+
+    let two_ply = TwoPly::with_dictionary(dictionary.iter().copied());
+    group.bench_function("two-ply", |b| {
+        b.iter(|| bench::run(&wordle, answers.iter().copied(), || two_ply.clone()).unwrap())
+    });
+
+    group.finish();
+}
+
+/// The matcher [`Guess::matches`] replaced, as kept in the tests of `src/rules.rs`, to compare
+/// their speed.
+fn legacy_matches<const N: usize>(guess: &Guess<N>, word: &str) -> bool {
+    assert_eq!(guess.word.len(), N);
+    assert_eq!(word.len(), N);
+
+    let mut used = [false; N];
+    for (i, ((g, &m), w)) in guess
+        .word
+        .chars()
+        .zip(&guess.mask)
+        .zip(word.chars())
+        .enumerate()
+    {
+        if m == Correctness::Correct {
+            if g != w {
+                return false;
+            } else {
+                used[i] = true;
+            }
+        }
+    }
+
+    for (i, (w, &m)) in word.chars().zip(&guess.mask).enumerate() {
+        if m == Correctness::Correct {
+            // Must be correct, or we would have returned in the earlier loop.
+            continue;
+        }
+
+        let mut plausible = true;
+        if guess
+            .word
+            .chars()
+            .zip(&guess.mask)
+            .enumerate()
+            .any(|(j, (g, m))| {
+                if g != w {
+                    return false;
+                }
+                if used[j] {
+                    return false;
+                }
+
+                match m {
+                    Correctness::Correct => unreachable!("we shouldn't reach this point"),
+                    Correctness::Misplaced if j == i => {
+                        // `w` was misplaced the last time around, which
+                        // means that the word cannot be the answer.
+                        plausible = false;
+                        false
+                    }
+                    Correctness::Misplaced => {
+                        used[j] = true;
+                        true
+                    }
+                    Correctness::Wrong => {
+                        // TODO: early return
+                        plausible = false;
+                        false
+                    }
+                }
+            })
+            && plausible
+        {
+            // The character `w` was either guessed correctly, or was previously misplaced.
+        } else if !plausible {
+            return false;
+        } else {
+            // We have no information about the character `w`, so the word might still match.
+        }
+    }
+
+    true
+}
+
+fn matches(c: &mut Criterion) {
+    let words: Vec<_> = crane::builtin_dictionary().map(|(word, _)| word).collect();
+    let guess: Guess = Guess::new("trace", Correctness::parse_mask("XYYYX").unwrap());
+
+    let mut group = c.benchmark_group("matches");
+    group.bench_function("compute", |b| {
+        b.iter(|| words.iter().filter(|word| guess.matches(word)).count())
+    });
+    group.bench_function("legacy", |b| {
+        b.iter(|| {
+            words
+                .iter()
+                .filter(|word| legacy_matches(&guess, word))
+                .count()
+        })
+    });
+    group.finish();
+}
+
+fn compute_bytes(c: &mut Criterion) {
+    let words: Vec<[u8; 5]> = crane::builtin_dictionary()
+        .map(|(word, _)| word.as_bytes().try_into().unwrap())
+        .collect();
+    c.bench_function("compute_bytes", |b| {
+        b.iter(|| {
+            words
+                .iter()
+                .map(|answer| Correctness::pack(Correctness::compute_bytes(answer, b"trace")))
+                .fold(0usize, |sum, packed| sum + packed as usize)
+        })
+    });
+}
+
+#[cfg(feature = "simd")]
+fn packed_patterns(c: &mut Criterion) {
+    let words: Vec<[u8; 5]> = crane::builtin_dictionary()
+        .map(|(word, _)| word.as_bytes().try_into().unwrap())
+        .collect();
+    let mut patterns = vec![0; words.len()];
+    c.bench_function("packed_patterns", |b| {
+        b.iter(|| {
+            crane::simd::packed_patterns(b"trace", &words, &mut patterns);
+            patterns
+                .iter()
+                .fold(0usize, |sum, &packed| sum + packed as usize)
+        })
+    });
+}
+
+#[cfg(feature = "simd")]
+criterion_group!(benches, solvers, matches, compute_bytes, packed_patterns);
+#[cfg(not(feature = "simd"))]
+criterion_group!(benches, solvers, matches, compute_bytes);
+criterion_main!(benches);
@@ -0,0 +1,83 @@
+//! Checks that a guess matches exactly the candidates that would reveal the same mask as the
+//! answer, over words with many repeated letters, with masks computed the way the rules spell them
+//! out rather than by the crate.
+//!
+//! Run with `cargo +nightly fuzz run matches` from the root of the repository.
+
+#![no_main]
+
+use crane::{Correctness, Guess};
+use libfuzzer_sys::fuzz_target;
+
+/// A small alphabet, so that repeated letters are common, and the same letters with accents, so
+/// that masks are also computed for words outside of `a` to `z`.
+const LETTERS: [[char; 4]; 2] = [['a', 'b', 'c', 'd'], ['á', 'b', 'ç', 'd']];
+
+/// Computes the mask of `guess` against `answer` one letter at a time: letters in the right place
+/// are green, and the others yellow from left to right as long as the answer has more copies of
+/// them than there are green and earlier yellow copies in the guess.
+fn reference(answer: &str, guess: &str) -> [Correctness; 5] {
+    let answer: Vec<char> = answer.chars().collect();
+    let guess: Vec<char> = guess.chars().collect();
+
+    let mut mask = [Correctness::Wrong; 5];
+    for i in 0..5 {
+        if answer[i] == guess[i] {
+            mask[i] = Correctness::Correct;
+        }
+    }
+    for i in 0..5 {
+        if mask[i] == Correctness::Correct {
+            continue;
+        }
+        let in_answer = answer.iter().filter(|&&a| a == guess[i]).count();
+        let colored = (0..5)
+            .filter(|&j| guess[j] == guess[i])
+            .filter(|&j| {
+                mask[j] == Correctness::Correct || (j < i && mask[j] == Correctness::Misplaced)
+            })
+            .count();
+        if colored < in_answer {
+            mask[i] = Correctness::Misplaced;
+        }
+    }
+    mask
+}
+
+fuzz_target!(|data: &[u8]| {
+    let (&alphabet, data) = match data.split_first() {
+        Some(split) => split,
+        None => return,
+    };
+    if data.len() < 15 {
+        return;
+    }
+
+    let letters = &LETTERS[(alphabet % 2) as usize];
+    let word = |bytes: &[u8]| -> String {
+        bytes
+            .iter()
+            .map(|&b| letters[(b % 4) as usize])
+            .collect()
+    };
+    let (answer, guess, candidate) = (word(&data[..5]), word(&data[5..10]), word(&data[10..15]));
+
+    let mask = reference(&answer, &guess);
+    assert_eq!(
+        Correctness::compute::<5>(&answer, &guess),
+        mask,
+        "{} against {}",
+        guess,
+        answer
+    );
+    let g = Guess::new(guess.clone(), mask);
+    assert!(g.matches(&answer), "{} does not match {:?}", answer, g);
+    assert_eq!(
+        g.matches(&candidate),
+        reference(&candidate, &guess) == mask,
+        "{} and {} disagree about {:?}",
+        answer,
+        candidate,
+        g
+    );
+});
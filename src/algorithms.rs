@@ -1,3 +1,443 @@
+use std::borrow::Cow;
+use std::cmp::Reverse;
+use std::collections::BTreeMap;
+
+use crate::explanation::{Explanation, ALTERNATIVES};
+use crate::{CancelToken, Correctness, History};
+
+pub mod anytime;
+pub mod book;
+pub mod buckets;
+pub mod cache;
+pub mod candidates;
+pub mod endgame;
+pub mod ensemble;
+mod exact;
+pub mod expected_size;
+pub mod minimax;
 pub mod naive;
+pub mod opener_rules;
+pub mod opening;
+pub mod pool;
+pub mod positional;
+pub mod prior;
+pub mod random;
+pub mod reduced_pool;
+pub mod registry;
+pub mod tie_break;
+mod tiles;
+pub mod tree;
+pub mod two_ply;
+pub mod weighting;
 
+pub use anytime::Anytime;
+pub use book::{Booked, OpeningBook};
+pub use buckets::{Pattern, PatternBuckets};
+pub use cache::{Pack, PatternCache};
+pub use candidates::Candidates;
+pub use endgame::Endgame;
+pub use ensemble::{Crossover, Ensemble};
+pub use expected_size::ExpectedSize;
+pub use minimax::Minimax;
 pub use naive::Naive;
+pub use opener_rules::{OpenerRule, OpenerRules};
+#[cfg(feature = "std")]
+pub use opening::OpeningCache;
+pub use opening::{FixedOpening, Opening};
+pub use pool::GuessPool;
+pub use positional::Positional;
+pub use prior::Prior;
+pub use random::Random;
+pub use reduced_pool::ReducedPool;
+pub use registry::{lookup, registry, Algorithm, Factory, Settings};
+pub use tie_break::TieBreak;
+use tiles::Tiles;
+pub use tree::{DecisionTree, TreeGuesser};
+pub use two_ply::TwoPly;
+pub use weighting::Weighting;
+
+/// Returns the item with the highest score, along with that score.
+///
+/// Ties are broken in favor of the item that comes first. With the `parallel` feature enabled,
+/// items are scored on all cores. Once `cancel` is cancelled, the items left are not scored, but
+/// the first item always is so that there is a best one.
+pub(crate) fn best_by<T, S, F>(items: &[T], cancel: &CancelToken, score: F) -> Option<(T, S)>
+where
+    T: Copy + Send + Sync,
+    S: PartialOrd + Send,
+    F: Fn(T) -> S + Send + Sync,
+{
+    #[cfg(feature = "parallel")]
+    {
+        use rayon::prelude::*;
+
+        items
+            .par_iter()
+            .enumerate()
+            .filter(|&(i, _)| i == 0 || !cancel.is_cancelled())
+            .map(|(_, &item)| (item, score(item)))
+            .reduce_with(|best, next| if next.1 > best.1 { next } else { best })
+    }
+
+    #[cfg(not(feature = "parallel"))]
+    {
+        items
+            .iter()
+            .enumerate()
+            .take_while(|&(i, _)| i == 0 || !cancel.is_cancelled())
+            .map(|(_, &item)| (item, score(item)))
+            .fold(None, |best, next| match best {
+                Some(best) if next.1 <= best.1 => Some(best),
+                _ => Some(next),
+            })
+    }
+}
+
+/// Like [`best_by`], but skips the items that cannot beat the best one found so far.
+///
+/// `bound` returns a score at least as high as the one of the item, and should be much cheaper to
+/// compute: items whose bound is no better than the best score so far are not scored at all. The
+/// result is the same as with [`best_by`], as long as the bounds hold. With the `parallel`
+/// feature enabled, every thread only prunes against the best item it found itself.
+pub(crate) fn best_by_bounded<T, S, B, F>(
+    items: &[T],
+    cancel: &CancelToken,
+    bound: B,
+    score: F,
+) -> Option<(T, S)>
+where
+    T: Copy + Send + Sync,
+    S: PartialOrd + Send,
+    B: Fn(T) -> S + Send + Sync,
+    F: Fn(T) -> S + Send + Sync,
+{
+    let step = |best: Option<(T, S)>, item: T| match best {
+        Some(best) if bound(item) <= best.1 => Some(best),
+        Some(best) => {
+            let next = (item, score(item));
+            Some(if next.1 <= best.1 { best } else { next })
+        }
+        None => Some((item, score(item))),
+    };
+
+    #[cfg(feature = "parallel")]
+    {
+        use rayon::prelude::*;
+
+        items
+            .par_iter()
+            .enumerate()
+            .filter(|&(i, _)| i == 0 || !cancel.is_cancelled())
+            .fold(|| None, |best, (_, &item)| step(best, item))
+            .flatten()
+            .reduce_with(|best, next| if next.1 > best.1 { next } else { best })
+    }
+
+    #[cfg(not(feature = "parallel"))]
+    {
+        items
+            .iter()
+            .enumerate()
+            .take_while(|&(i, _)| i == 0 || !cancel.is_cancelled())
+            .fold(None, |best, (_, &item)| step(best, item))
+    }
+}
+
+/// Returns the `k` words of `guesses` with the highest scores, best first, each with its score.
+///
+/// Ties are broken in favor of the guess that comes first.
+pub(crate) fn top<const N: usize>(
+    cache: &PatternCache<N>,
+    guesses: &[usize],
+    k: usize,
+    score: impl Fn(usize) -> f64,
+) -> Vec<(&'static str, f64)>
+where
+    Correctness: Pack<N>,
+{
+    let mut ranked: Vec<(usize, f64)> =
+        guesses.iter().map(|&guess| (guess, score(guess))).collect();
+    ranked.sort_by(|a, b| b.1.partial_cmp(&a.1).expect("scores are never NaN"));
+    ranked
+        .into_iter()
+        .take(k)
+        .map(|(guess, score)| (cache.word(guess), score))
+        .collect()
+}
+
+/// Returns what guessers play once no candidate is left, because the masks they were given
+/// contradict each other or the answer is not among theirs: the first word of the dictionary of
+/// `cache` that was not played yet, so that the game goes on, and eventually ends, rather than
+/// the guesser panicking.
+pub(crate) fn no_candidate_left<const N: usize>(
+    cache: &PatternCache<N>,
+    history: &History<N>,
+) -> Cow<'static, str>
+where
+    Correctness: Pack<N>,
+{
+    tracing::warn!(
+        turn = history.len() + 1,
+        "no candidate agrees with every mask so far"
+    );
+    let played = |word: &str| history.iter().any(|guess| guess.word == word);
+    // An empty dictionary has nothing to play, and the game rejects the empty word.
+    let word = (0..cache.len())
+        .map(|index| cache.word(index))
+        .find(|word| !played(word))
+        .unwrap_or("");
+    Cow::Borrowed(word)
+}
+
+/// Returns the expected amount of information (in bits) revealed by a guess, i.e. the Shannon
+/// entropy of the distribution of the patterns it produces.
+///
+/// `patterns` is the row of the [`PatternCache`] for the guess, and `candidates` holds the index
+/// of every remaining candidate along with its likelihood of being the answer.
+pub(crate) fn entropy<P: Pattern>(patterns: &[P], candidates: &[(usize, f64)]) -> f64 {
+    // Bucket every candidate by the pattern we would see if it were the answer.
+    P::entropy(patterns, candidates)
+}
+
+/// Returns the amount of information (in bits) left to find out about the answer, i.e. the
+/// Shannon entropy of the distribution of the `candidates`.
+///
+/// No guess reveals more than that, which only a guess that tells every candidate apart does, so
+/// this bounds the [`entropy`] of every guess.
+pub(crate) fn information(candidates: &[(usize, f64)]) -> f64 {
+    let total: f64 = candidates.iter().map(|&(_, weight)| weight).sum();
+    -candidates
+        .iter()
+        .map(|&(_, weight)| weight / total)
+        .filter(|&p| p > 0.0)
+        .map(|p| p * p.log2())
+        .sum::<f64>()
+}
+
+/// Explains why `guess` was picked among the words of `cache` when the `remaining` candidates
+/// could still be the answer, each as likely as its weight.
+///
+/// # Panics
+///
+/// Panics if `guess` is not a word of the cache.
+pub(crate) fn explain<const N: usize>(
+    cache: &PatternCache<N>,
+    remaining: &Candidates,
+    weight: impl Fn(usize) -> f64,
+    guess: &str,
+) -> Explanation<N>
+where
+    Correctness: Pack<N>,
+{
+    let guess = cache
+        .index(guess)
+        .expect("guessers pick the words of their cache");
+    let candidates: Vec<(usize, f64)> = remaining
+        .iter()
+        .map(|index| (index, weight(index)))
+        .collect();
+
+    let mut alternatives: Vec<(&'static str, f64)> = (0..cache.len())
+        .filter(|&index| index != guess)
+        .map(|index| (cache.word(index), entropy(cache.row(index), &candidates)))
+        .collect();
+    alternatives.sort_by(|a, b| b.1.partial_cmp(&a.1).expect("entropy is never NaN"));
+    alternatives.truncate(ALTERNATIVES);
+
+    let patterns = cache.row(guess);
+    let mut counts = BTreeMap::new();
+    for &(index, _) in &candidates {
+        *counts.entry(patterns[index]).or_insert(0) += 1;
+    }
+    let mut distribution: Vec<_> = counts
+        .into_iter()
+        .map(|(pattern, count)| (<Correctness as Pack<N>>::unpack(pattern), count))
+        .collect();
+    distribution.sort_by_key(|&(_, count)| Reverse(count));
+
+    Explanation {
+        candidates: candidates.len(),
+        entropy: entropy(patterns, &candidates),
+        alternatives,
+        distribution,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Instant;
+
+    use super::{
+        best_by, best_by_bounded, ExpectedSize, GuessPool, Minimax, Naive, TwoPly, ALTERNATIVES,
+    };
+    use crate::{CancelToken, Correctness, Guess, Guesser, History, Wordle, WORDS};
+
+    #[test]
+    fn solves_every_answer() {
+        let wordle = Wordle::with_dictionary(WORDS.iter().copied());
+        let naive = Naive::with_dictionary(WORDS.iter().copied());
+        let minimax = Minimax::with_dictionary(WORDS.iter().copied());
+        let expected_size = ExpectedSize::with_dictionary(WORDS.iter().copied());
+        let two_ply = TwoPly::with_dictionary(WORDS.iter().copied()).with_shortlist(3);
+        for &(answer, _) in WORDS {
+            assert!(matches!(wordle.play(answer, naive.clone()), Ok(Some(_))));
+            assert!(matches!(wordle.play(answer, minimax.clone()), Ok(Some(_))));
+            assert!(matches!(
+                wordle.play(answer, expected_size.clone()),
+                Ok(Some(_))
+            ));
+            assert!(matches!(wordle.play(answer, two_ply.clone()), Ok(Some(_))));
+        }
+    }
+
+    #[test]
+    fn guesses_eliminated_words() {
+        for pool in [GuessPool::Dictionary, GuessPool::hybrid()] {
+            let naive: Naive = Naive::with_dictionary(WORDS.iter().copied()).with_pool(pool);
+            for hard_mode in [false, true] {
+                let wordle =
+                    Wordle::with_dictionary(WORDS.iter().copied()).with_hard_mode(hard_mode);
+                for &(answer, _) in WORDS {
+                    assert!(matches!(wordle.play(answer, naive.clone()), Ok(Some(_))));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn best_by_prefers_first_on_ties() {
+        let items = [3, 1, 4, 1, 5, 9, 2, 6];
+        let cancel = CancelToken::new();
+        assert_eq!(best_by(&items, &cancel, |x| x % 5), Some((4, 4)));
+        assert_eq!(best_by(&items, &cancel, |x| x), Some((9, 9)));
+        assert_eq!(best_by(&[] as &[usize], &cancel, |x| x), None);
+    }
+
+    #[test]
+    fn best_by_stops_once_cancelled() {
+        let items = [3, 1, 4, 1, 5, 9, 2, 6];
+        let cancel = CancelToken::new();
+        cancel.cancel();
+        assert_eq!(best_by(&items, &cancel, |x| x), Some((3, 3)));
+    }
+
+    #[test]
+    fn best_by_bounded_agrees_with_best_by() {
+        let items = [3, 1, 4, 1, 5, 9, 2, 6];
+        let cancel = CancelToken::new();
+        // Bounds of the score modulo 5, loose for some items and exact for others.
+        let bound = |x: usize| if x < 5 { x } else { 4 };
+        assert_eq!(
+            best_by_bounded(&items, &cancel, bound, |x| x % 5),
+            best_by(&items, &cancel, |x| x % 5)
+        );
+        assert_eq!(
+            best_by_bounded(&[] as &[usize], &cancel, bound, |x| x),
+            None
+        );
+    }
+
+    #[cfg(not(feature = "parallel"))]
+    #[test]
+    fn best_by_bounded_skips_dominated_items() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let items = [4, 1, 3, 9, 2];
+        let cancel = CancelToken::new();
+        let scored = AtomicUsize::new(0);
+        let best = best_by_bounded(
+            &items,
+            &cancel,
+            |x: usize| x.min(4),
+            |x| {
+                scored.fetch_add(1, Ordering::Relaxed);
+                x.min(4)
+            },
+        );
+        // Nothing beats the first item once it scores the most any item may.
+        assert_eq!(best, Some((4, 4)));
+        assert_eq!(scored.into_inner(), 1);
+    }
+
+    #[test]
+    fn plays_past_the_deadline() {
+        let wordle = Wordle::with_dictionary(WORDS.iter().copied());
+        let naive = Naive::with_dictionary(WORDS.iter().copied());
+        let two_ply = TwoPly::with_dictionary(WORDS.iter().copied());
+        let deadline = Instant::now();
+        for &(answer, _) in WORDS {
+            let transcript = wordle
+                .play_with_deadline(answer, naive.clone(), deadline)
+                .unwrap();
+            assert!(transcript.score.is_some());
+            let transcript = wordle
+                .play_with_deadline(answer, two_ply.clone(), deadline)
+                .unwrap();
+            assert!(transcript.score.is_some());
+        }
+    }
+
+    #[test]
+    fn explains_guesses() {
+        let guessers: [Box<dyn Guesser>; 4] = [
+            Box::new(Naive::with_dictionary(WORDS.iter().copied())),
+            Box::new(Minimax::with_dictionary(WORDS.iter().copied())),
+            Box::new(ExpectedSize::with_dictionary(WORDS.iter().copied())),
+            Box::new(TwoPly::with_dictionary(WORDS.iter().copied())),
+        ];
+        for mut guesser in guessers {
+            let (guess, explanation) = guesser.guess_with_explanation(&History::new());
+            let explanation = explanation.expect("scoring guessers explain their guesses");
+            assert_eq!(explanation.candidates, WORDS.len());
+            assert!(explanation.entropy > 0.0);
+            assert_eq!(explanation.alternatives.len(), ALTERNATIVES);
+            assert!(explanation
+                .alternatives
+                .iter()
+                .all(|&(word, _)| word != guess));
+            assert!(explanation
+                .alternatives
+                .windows(2)
+                .all(|pair| pair[0].1 >= pair[1].1));
+            let counts: Vec<usize> = explanation.distribution.iter().map(|&(_, n)| n).collect();
+            assert_eq!(counts.iter().sum::<usize>(), WORDS.len());
+            assert!(counts.windows(2).all(|pair| pair[0] >= pair[1]));
+        }
+    }
+
+    #[test]
+    fn ranks_the_guess_first() {
+        let guessers: [Box<dyn Guesser>; 3] = [
+            Box::new(Naive::with_dictionary(WORDS.iter().copied())),
+            Box::new(Minimax::with_dictionary(WORDS.iter().copied())),
+            Box::new(ExpectedSize::with_dictionary(WORDS.iter().copied())),
+        ];
+        let history = History::from(vec![Guess::new(
+            "cigar",
+            Correctness::compute("focal", "cigar"),
+        )]);
+        for mut guesser in guessers {
+            let ranking = guesser.rank(&history, usize::MAX);
+            assert!(ranking.windows(2).all(|pair| pair[0].1 >= pair[1].1));
+            assert_eq!(guesser.rank(&history, 1), ranking[..1]);
+
+            // Guessers may break ties differently, but never pick a worse guess.
+            let guess = guesser.guess(&history);
+            let score = ranking.iter().find(|&&(word, _)| word == guess).unwrap().1;
+            assert_eq!(score, ranking[0].1);
+        }
+    }
+
+    #[test]
+    fn ranks_the_remaining_candidates_by_default() {
+        let two_ply = TwoPly::with_dictionary(WORDS.iter().copied());
+        let history = History::from(vec![Guess::new(
+            "cigar",
+            Correctness::compute("focal", "cigar"),
+        )]);
+        let ranking = two_ply.rank(&history, usize::MAX);
+        assert!(!ranking.is_empty());
+        assert!(ranking.iter().all(|&(word, _)| history.matches(word)));
+        assert!(ranking.windows(2).all(|pair| pair[0].1 >= pair[1].1));
+    }
+}
@@ -0,0 +1,101 @@
+use std::borrow::Cow;
+use std::time::{Duration, Instant};
+
+use crate::{CancelToken, Estimate, Explanation, Guesser, History};
+
+/// Gives the wrapped guesser a time budget for every turn, after which it settles for the best
+/// guess it found so far.
+///
+/// The algorithms score their most promising guesses first wherever they can, e.g. the two-ply
+/// one looks two guesses ahead in the order of the information revealed by the first guess alone,
+/// so the guess they settle for is usually a good one. They still finish scoring the guess they
+/// are at, so a turn may take a little longer than the budget.
+#[derive(Clone)]
+pub struct Anytime<G> {
+    inner: G,
+    budget: Duration,
+    /// Cancels every turn on top of the budget, see [`Guesser::set_cancel_token`].
+    cancel: CancelToken,
+}
+
+impl<G> Anytime<G> {
+    pub fn new(inner: G, budget: Duration) -> Self {
+        Self {
+            inner,
+            budget,
+            cancel: CancelToken::new(),
+        }
+    }
+
+    /// Hands the wrapped guesser a token cancelled when the budget of this turn runs out.
+    fn start_turn<const N: usize>(&mut self)
+    where
+        G: Guesser<N>,
+    {
+        let deadline = Instant::now() + self.budget;
+        self.inner
+            .set_cancel_token(self.cancel.clone().with_deadline(deadline));
+    }
+}
+
+impl<G: Guesser<N>, const N: usize> Guesser<N> for Anytime<G> {
+    fn guess(&mut self, history: &History<N>) -> Cow<'static, str> {
+        self.start_turn();
+        self.inner.guess(history)
+    }
+
+    fn guess_with_explanation(
+        &mut self,
+        history: &History<N>,
+    ) -> (Cow<'static, str>, Option<Explanation<N>>) {
+        self.start_turn();
+        self.inner.guess_with_explanation(history)
+    }
+
+    fn set_hard_mode(&mut self, hard_mode: bool) {
+        self.inner.set_hard_mode(hard_mode)
+    }
+
+    fn set_cancel_token(&mut self, token: CancelToken) {
+        self.cancel = token;
+    }
+
+    fn remaining(&self) -> Option<Vec<&'static str>> {
+        self.inner.remaining()
+    }
+
+    fn rank(&self, history: &History<N>, k: usize) -> Vec<(&'static str, f64)> {
+        self.inner.rank(history, k)
+    }
+
+    fn estimate(&self, history: &History<N>) -> Option<Estimate> {
+        self.inner.estimate(history)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::Anytime;
+    use crate::algorithms::Minimax;
+    use crate::{Guesser, History, Wordle, WORDS};
+
+    #[test]
+    fn settles_once_the_budget_runs_out() {
+        let wordle = Wordle::with_dictionary(WORDS.iter().copied());
+        let inner = Minimax::with_dictionary(WORDS.iter().copied());
+
+        // Without any time, the guesser still plays, out of the first guess it scored every turn.
+        for &(answer, _) in WORDS {
+            let guesser = Anytime::new(inner.clone(), Duration::ZERO);
+            assert!(matches!(wordle.play(answer, guesser), Ok(Some(_))));
+        }
+
+        let mut plenty = Anytime::new(inner.clone(), Duration::from_secs(3600));
+        assert_eq!(
+            plenty.guess(&History::new()),
+            inner.clone().guess(&History::new())
+        );
+    }
+}
@@ -0,0 +1,231 @@
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+#[cfg(feature = "std")]
+use std::path::Path;
+use std::sync::Arc;
+#[cfg(feature = "std")]
+use std::{fs, io};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{CancelToken, Correctness, Estimate, Explanation, Guess, Guesser, History};
+
+/// The best second guess for every mask a given first guess may reveal, computed once so that
+/// games can skip scoring their first two guesses, which are the most expensive ones.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OpeningBook {
+    opener: String,
+    /// Whether the responses were computed for hard mode.
+    hard_mode: bool,
+    /// Keyed by the mask revealed by the opener, in the format of [`Correctness::parse_mask`].
+    responses: BTreeMap<String, String>,
+}
+
+impl OpeningBook {
+    /// Computes the book for `opener` by asking a fresh guesser from `mk` for its second guess
+    /// after every mask that one of the `answers` would reveal.
+    ///
+    /// This asks for as many second guesses as there are distinct masks, up to
+    /// [`Correctness::PATTERNS`], so it takes a while with the slower algorithms.
+    pub fn build<'a, G: Guesser>(
+        opener: &str,
+        answers: impl IntoIterator<Item = &'a str>,
+        hard_mode: bool,
+        mut mk: impl FnMut() -> G,
+    ) -> Self {
+        let mut masks: Vec<[Correctness; 5]> = answers
+            .into_iter()
+            .map(|answer| Correctness::compute(answer, opener))
+            .filter(|&mask| mask != [Correctness::Correct; 5])
+            .collect();
+        masks.sort_unstable_by_key(|&mask| Correctness::pack(mask));
+        masks.dedup();
+
+        let responses = masks
+            .into_iter()
+            .map(|mask| {
+                let mut guesser = (mk)();
+                guesser.set_hard_mode(hard_mode);
+                let response =
+                    guesser.guess(&History::from(vec![Guess::new(opener.to_string(), mask)]));
+                (mask_key(mask), response.into_owned())
+            })
+            .collect();
+
+        Self {
+            opener: opener.to_string(),
+            hard_mode,
+            responses,
+        }
+    }
+
+    /// Reads a book saved with [`OpeningBook::save`].
+    #[cfg(feature = "std")]
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        let contents = fs::read_to_string(path)?;
+        serde_json::from_str(&contents).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    #[cfg(feature = "std")]
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let contents = serde_json::to_string_pretty(self).expect("books are serializable");
+        fs::write(path, contents)
+    }
+
+    pub fn opener(&self) -> &str {
+        &self.opener
+    }
+
+    pub fn hard_mode(&self) -> bool {
+        self.hard_mode
+    }
+
+    /// Returns the second guess to play after the opener revealed `mask`, if the book has one.
+    pub fn response(&self, mask: [Correctness; 5]) -> Option<&str> {
+        self.responses.get(&mask_key(mask)).map(String::as_str)
+    }
+
+    /// Returns the number of masks the book has a response for.
+    pub fn len(&self) -> usize {
+        self.responses.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.responses.is_empty()
+    }
+}
+
+fn mask_key(mask: [Correctness; 5]) -> String {
+    mask.iter().map(|c| c.to_char()).collect()
+}
+
+/// Plays the first two guesses out of an [`OpeningBook`], and only asks the wrapped guesser from
+/// the third guess on, or as soon as the game strays from the book.
+///
+/// The book is ignored when the game is not played in the mode it was computed for, since its
+/// responses might not be allowed in hard mode.
+#[derive(Clone)]
+pub struct Booked<G> {
+    inner: G,
+    book: Arc<OpeningBook>,
+    hard_mode: bool,
+}
+
+impl<G> Booked<G> {
+    pub fn new(inner: G, book: Arc<OpeningBook>) -> Self {
+        Self {
+            inner,
+            book,
+            hard_mode: false,
+        }
+    }
+}
+
+impl<G> Booked<G> {
+    /// Returns the guess the book has for `history`, if any.
+    fn booked(&self, history: &[Guess]) -> Option<Cow<'static, str>> {
+        if self.hard_mode != self.book.hard_mode {
+            return None;
+        }
+        match history {
+            [] => Some(Cow::Owned(self.book.opener.clone())),
+            [first] if first.word == self.book.opener => self
+                .book
+                .response(first.mask)
+                .map(|response| Cow::Owned(response.to_string())),
+            _ => None,
+        }
+    }
+}
+
+impl<G: Guesser> Guesser for Booked<G> {
+    fn guess(&mut self, history: &History) -> Cow<'static, str> {
+        match self.booked(history) {
+            Some(guess) => guess,
+            None => self.inner.guess(history),
+        }
+    }
+
+    fn guess_with_explanation(
+        &mut self,
+        history: &History,
+    ) -> (Cow<'static, str>, Option<Explanation>) {
+        match self.booked(history) {
+            Some(guess) => (guess, None),
+            None => self.inner.guess_with_explanation(history),
+        }
+    }
+
+    fn set_hard_mode(&mut self, hard_mode: bool) {
+        self.hard_mode = hard_mode;
+        self.inner.set_hard_mode(hard_mode)
+    }
+
+    fn set_cancel_token(&mut self, token: CancelToken) {
+        self.inner.set_cancel_token(token)
+    }
+
+    fn remaining(&self) -> Option<Vec<&'static str>> {
+        self.inner.remaining()
+    }
+
+    fn rank(&self, history: &History, k: usize) -> Vec<(&'static str, f64)> {
+        self.inner.rank(history, k)
+    }
+
+    fn estimate(&self, history: &History) -> Option<Estimate> {
+        self.inner.estimate(history)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::{Booked, OpeningBook};
+    use crate::algorithms::Naive;
+    use crate::{Wordle, WORDS};
+
+    #[test]
+    fn build() {
+        let answers = WORDS.iter().map(|&(word, _)| word);
+        let naive = Naive::with_dictionary(WORDS.iter().copied());
+        let book = OpeningBook::build("blush", answers, false, || naive.clone());
+
+        // "cigar", "awake", "evade" and "dwarf" share no letter with "blush", "focal" and "naval"
+        // reveal the same mask, every other answer reveals a distinct one, and "blush" itself
+        // needs no response.
+        assert_eq!(book.opener(), "blush");
+        assert_eq!(book.len(), 7);
+        assert_eq!(book.response(mask!(W W W C W)), Some("sissy"));
+        assert_eq!(book.response(mask!(C C C C C)), None);
+    }
+
+    #[test]
+    fn plays_from_the_book() {
+        let wordle = Wordle::with_dictionary(WORDS.iter().copied());
+        let answers = WORDS.iter().map(|&(word, _)| word);
+        let naive = Naive::with_dictionary(WORDS.iter().copied());
+        let book = Arc::new(OpeningBook::build("awake", answers, false, || {
+            naive.clone()
+        }));
+
+        for &(answer, _) in WORDS {
+            let guesser = Booked::new(naive.clone(), book.clone());
+            let transcript = wordle.play_transcript(answer, guesser).unwrap();
+            assert!(transcript.score.is_some(), "did not find {}", answer);
+            assert_eq!(transcript.guesses[0].word, "awake");
+        }
+    }
+
+    #[test]
+    fn round_trip() {
+        let path = std::env::temp_dir().join("crane-opening-book-test.json");
+        let answers = WORDS.iter().map(|&(word, _)| word);
+        let naive = Naive::with_dictionary(WORDS.iter().copied());
+        let book = OpeningBook::build("cigar", answers, true, || naive.clone());
+
+        book.save(&path).unwrap();
+        assert_eq!(OpeningBook::load(&path).unwrap(), book);
+    }
+}
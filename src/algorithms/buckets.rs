@@ -0,0 +1,153 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::ops::AddAssign;
+
+use crate::Correctness;
+
+/// An integer masks are packed into, see [`Pack`](super::Pack).
+pub trait Pattern: Copy + Ord + Default + Into<usize> + Send + Sync + fmt::Debug + 'static {
+    /// Returns the Shannon entropy (in bits) of the distribution of the patterns the `candidates`
+    /// reveal, given the row of the [`PatternCache`](super::PatternCache) for a guess, and each
+    /// candidate along with its likelihood of being the answer.
+    fn entropy(row: &[Self], candidates: &[(usize, f64)]) -> f64;
+}
+
+impl Pattern for u8 {
+    fn entropy(row: &[u8], candidates: &[(usize, f64)]) -> f64 {
+        PatternBuckets::weigh(row, candidates).entropy()
+    }
+}
+
+impl Pattern for u16 {
+    fn entropy(row: &[u16], candidates: &[(usize, f64)]) -> f64 {
+        // Longer words have too many masks for buckets in a fixed-size array, most of which no
+        // candidate reveals.
+        let mut totals: HashMap<u16, f64> = HashMap::new();
+        for &(candidate, weight) in candidates {
+            *totals.entry(row[candidate]).or_default() += weight;
+        }
+        let total: f64 = totals.values().sum();
+        -totals
+            .values()
+            .map(|&in_pattern| in_pattern / total)
+            .filter(|&p| p > 0.0)
+            .map(|p| p * p.log2())
+            .sum::<f64>()
+    }
+}
+
+/// Totals of candidates per packed mask, e.g. how many candidates would reveal each pattern if
+/// they were the answer to a guess.
+///
+/// The totals live in a fixed-size array indexed by the packed mask, so bucketing a candidate
+/// costs an addition rather than hashing the mask, and the buckets of every guess fit in cache.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PatternBuckets<W = usize> {
+    totals: [W; Correctness::PATTERNS],
+}
+
+impl<W: Copy + Default + AddAssign> PatternBuckets<W> {
+    /// Creates empty buckets.
+    pub fn new() -> Self {
+        Self {
+            totals: [W::default(); Correctness::PATTERNS],
+        }
+    }
+
+    /// Adds `weight` to the bucket of the given packed mask.
+    pub fn add(&mut self, pattern: u8, weight: W) {
+        self.totals[pattern as usize] += weight;
+    }
+
+    /// Returns the total of the bucket of the given packed mask.
+    pub fn get(&self, pattern: u8) -> W {
+        self.totals[pattern as usize]
+    }
+
+    /// Returns the total of every bucket, indexed by packed mask.
+    pub fn totals(&self) -> &[W; Correctness::PATTERNS] {
+        &self.totals
+    }
+}
+
+impl PatternBuckets<usize> {
+    /// Counts the `candidates` by the pattern they reveal, given the row of the
+    /// [`PatternCache`](super::PatternCache) for a guess.
+    pub fn count(patterns: &[u8], candidates: &[usize]) -> Self {
+        let mut buckets = Self::new();
+        for &candidate in candidates {
+            buckets.add(patterns[candidate], 1);
+        }
+        buckets
+    }
+
+    /// Returns the size of the largest bucket.
+    pub fn largest(&self) -> usize {
+        self.totals.iter().copied().max().unwrap_or(0)
+    }
+
+    /// Returns the sum of the squares of the bucket sizes, which is proportional to the number of
+    /// candidates left on average.
+    pub fn sum_of_squares(&self) -> usize {
+        self.totals.iter().map(|&size| size * size).sum()
+    }
+}
+
+impl PatternBuckets<f64> {
+    /// Weighs the `candidates`, each given along with its likelihood of being the answer, by the
+    /// pattern they reveal, given the row of the [`PatternCache`](super::PatternCache) for a guess.
+    pub fn weigh(patterns: &[u8], candidates: &[(usize, f64)]) -> Self {
+        let mut buckets = Self::new();
+        for &(candidate, weight) in candidates {
+            buckets.add(patterns[candidate], weight);
+        }
+        buckets
+    }
+
+    /// Returns the Shannon entropy (in bits) of the distribution of the patterns.
+    pub fn entropy(&self) -> f64 {
+        let total: f64 = self.totals.iter().sum();
+        -self
+            .totals
+            .iter()
+            .map(|&in_pattern| in_pattern / total)
+            .filter(|&p| p > 0.0)
+            .map(|p| p * p.log2())
+            .sum::<f64>()
+    }
+}
+
+impl<W: Copy + Default + AddAssign> Default for PatternBuckets<W> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Pattern, PatternBuckets};
+
+    #[test]
+    fn buckets_candidates_by_pattern() {
+        let patterns = [0, 242, 0, 7];
+        let counts = PatternBuckets::count(&patterns, &[0, 1, 2, 3]);
+        assert_eq!(counts.get(0), 2);
+        assert_eq!(counts.get(242), 1);
+        assert_eq!(counts.largest(), 2);
+        assert_eq!(counts.sum_of_squares(), 6);
+
+        let weights = PatternBuckets::weigh(&patterns, &[(0, 1.0), (1, 2.0), (3, 1.0)]);
+        assert_eq!(weights.get(242), 2.0);
+        assert!((weights.entropy() - 1.5).abs() < 1e-12);
+        assert_eq!(PatternBuckets::weigh(&patterns, &[(1, 3.0)]).entropy(), 0.0);
+    }
+
+    #[test]
+    fn wide_patterns_have_the_same_entropy() {
+        let candidates = [(0, 1.0), (1, 2.0), (2, 0.5), (3, 1.0)];
+        let patterns: [u8; 4] = [0, 242, 0, 7];
+        let wide = patterns.map(u16::from);
+        let entropy = Pattern::entropy(&patterns[..], &candidates);
+        assert!((Pattern::entropy(&wide[..], &candidates) - entropy).abs() < 1e-12);
+    }
+}
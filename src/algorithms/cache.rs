@@ -0,0 +1,440 @@
+#[cfg(feature = "std")]
+use std::convert::TryInto;
+use std::fmt;
+#[cfg(feature = "std")]
+use std::fs::File;
+#[cfg(feature = "std")]
+use std::io::{self, BufWriter, Write};
+#[cfg(feature = "std")]
+use std::path::Path;
+use std::sync::{Arc, OnceLock};
+
+#[cfg(feature = "std")]
+use memmap2::Mmap;
+
+use super::Pattern;
+use crate::dictionary::Dictionary;
+use crate::Correctness;
+
+/// Identifies the files written by [`PatternCache::save`], and their version.
+#[cfg(feature = "std")]
+const MAGIC: &[u8; 8] = b"CRMTRX01";
+/// The magic, followed by the number of words and the fingerprint of the word list.
+#[cfg(feature = "std")]
+const HEADER_LEN: usize = MAGIC.len() + 2 * 8;
+
+/// How the masks of words of `N` letters are packed in a [`PatternCache`], for words of 1 to 10
+/// letters.
+///
+/// Masks are read as base-3 numbers, like [`Correctness::pack`] does for five letters, into a `u8`
+/// for words of up to five letters and into a `u16` for longer ones.
+pub trait Pack<const N: usize> {
+    /// The integer masks are packed into.
+    type Packed: Pattern;
+    /// Number of distinct masks of `N` letters, and therefore of packed masks.
+    const PATTERNS: usize;
+
+    /// Packs a mask into an integer smaller than [`Pack::PATTERNS`].
+    fn pack(mask: [Correctness; N]) -> Self::Packed;
+
+    /// Reverses [`Pack::pack`].
+    fn unpack(packed: Self::Packed) -> [Correctness; N];
+
+    /// Writes the packed mask revealed by `guess` against every one of `answers` to `row`.
+    fn pack_row(guess: &[u8; N], answers: &[[u8; N]], row: &mut [Self::Packed]) {
+        for (pattern, answer) in row.iter_mut().zip(answers) {
+            *pattern = Self::pack(Correctness::compute_bytes(answer, guess));
+        }
+    }
+}
+
+impl Pack<5> for Correctness {
+    type Packed = u8;
+    const PATTERNS: usize = Correctness::PATTERNS;
+
+    fn pack(mask: [Correctness; 5]) -> u8 {
+        Correctness::pack(mask)
+    }
+
+    fn unpack(packed: u8) -> [Correctness; 5] {
+        Correctness::unpack(packed)
+    }
+
+    /// Computes the row many answers at once, see [`crate::simd::packed_patterns`].
+    #[cfg(feature = "simd")]
+    fn pack_row(guess: &[u8; 5], answers: &[[u8; 5]], row: &mut [u8]) {
+        crate::simd::packed_patterns(guess, answers, row);
+    }
+}
+
+macro_rules! impl_pack {
+    ($packed:ty: $($n:literal)*) => {$(
+        impl Pack<$n> for Correctness {
+            type Packed = $packed;
+            const PATTERNS: usize = 3usize.pow($n);
+
+            fn pack(mask: [Correctness; $n]) -> $packed {
+                mask.iter().fold(0, |packed: $packed, &c| packed * 3 + digit::<$packed>(c))
+            }
+
+            fn unpack(mut packed: $packed) -> [Correctness; $n] {
+                assert!((packed as usize) < <Self as Pack<$n>>::PATTERNS, "not a packed mask");
+                let mut mask = [Correctness::Wrong; $n];
+                for c in mask.iter_mut().rev() {
+                    *c = match packed % 3 {
+                        0 => Correctness::Correct,
+                        1 => Correctness::Misplaced,
+                        _ => Correctness::Wrong,
+                    };
+                    packed /= 3;
+                }
+                mask
+            }
+        }
+    )*};
+}
+
+impl_pack!(u8: 1 2 3 4);
+impl_pack!(u16: 6 7 8 9 10);
+
+/// Returns the base-3 digit of a tile in a packed mask, as in [`Correctness::pack`].
+fn digit<T: From<u8>>(c: Correctness) -> T {
+    T::from(match c {
+        Correctness::Correct => 0,
+        Correctness::Misplaced => 1,
+        Correctness::Wrong => 2,
+    })
+}
+
+/// The packed pattern of words of `N` letters, see [`Pack`].
+type Packed<const N: usize> = <Correctness as Pack<N>>::Packed;
+
+/// Lookup table of the packed [`Correctness`] pattern between every pair of words in a word list
+/// of `N` letters, see [`Pack`].
+///
+/// Words are identified by their index in the list. Rows are computed lazily the first time a
+/// guess is looked up, so repeated computations across turns and games are a plain table lookup.
+/// The whole table of five-letter words can also be computed once and saved, to be mapped into
+/// memory by later runs.
+///
+/// Building the cache for a new dictionary is expensive, so guessers created out of a dictionary,
+/// e.g. with [`Naive::with_dictionary`](super::Naive::with_dictionary), are best constructed once
+/// and cloned for every game, which shares their cache.
+pub struct PatternCache<const N: usize = 5>
+where
+    Correctness: Pack<N>,
+{
+    dictionary: Arc<Dictionary<N>>,
+    /// The letters of every word, or placeholders for the words that [`Correctness::compute_bytes`]
+    /// does not accept, to compute whole rows at once.
+    dense: Vec<[u8; N]>,
+    rows: Rows<Packed<N>>,
+}
+
+enum Rows<P> {
+    /// Computed the first time they are looked up.
+    Lazy(Vec<OnceLock<Box<[P]>>>),
+    /// All of them, out of a file written by [`PatternCache::save`], which is read as patterns by
+    /// the function alongside.
+    #[cfg(feature = "std")]
+    Mapped(Mmap, fn(&[u8]) -> &[P]),
+}
+
+impl<const N: usize> PatternCache<N>
+where
+    Correctness: Pack<N>,
+{
+    /// Creates the cache for `words`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a word is not `N` letters long, see [`PatternCache::try_new`].
+    pub fn new(words: impl IntoIterator<Item = &'static str>) -> Self {
+        Self::for_dictionary(Arc::new(Dictionary::new(words)))
+    }
+
+    /// Like [`PatternCache::new`], but fails with [`Error::WordLength`](crate::Error::WordLength)
+    /// rather than panicking when a word is not `N` letters long.
+    pub fn try_new(words: impl IntoIterator<Item = &'static str>) -> crate::Result<Self> {
+        Ok(Self::for_dictionary(Arc::new(Dictionary::try_new(words)?)))
+    }
+
+    /// Creates the cache for the words of `dictionary`, which guessers built out of the cache take
+    /// their frequency counts from, see e.g. [`Naive::from_cache`](super::Naive::from_cache).
+    pub fn for_dictionary(dictionary: Arc<Dictionary<N>>) -> Self {
+        Self {
+            rows: Rows::Lazy((0..dictionary.len()).map(|_| OnceLock::new()).collect()),
+            dense: (0..dictionary.len())
+                .map(|i| {
+                    dictionary
+                        .letters(i)
+                        .map_or([b'a'; N], |word| *word.as_bytes())
+                })
+                .collect(),
+            dictionary,
+        }
+    }
+}
+
+impl PatternCache {
+    /// The cache for the built-in dictionary, shared by every guesser in the process.
+    pub fn global() -> Arc<Self> {
+        static GLOBAL: OnceLock<Arc<PatternCache>> = OnceLock::new();
+        GLOBAL
+            .get_or_init(|| Arc::new(Self::for_dictionary(Dictionary::builtin())))
+            .clone()
+    }
+
+    /// Maps the table saved by [`PatternCache::save`] for `words` into memory, so that every row
+    /// is a lookup away without computing anything.
+    ///
+    /// Fails if the file was saved for another word list.
+    #[cfg(feature = "std")]
+    pub fn load(
+        words: impl IntoIterator<Item = &'static str>,
+        path: impl AsRef<Path>,
+    ) -> io::Result<Self> {
+        let mut cache = Self::new(words);
+        let file = File::open(path)?;
+        // SAFETY: the table is only ever read, and files written by `save` are not modified
+        // afterwards. A file truncated behind our back would crash the process, as with any
+        // memory-mapped file.
+        let map = unsafe { Mmap::map(&file)? };
+
+        let invalid = |message| io::Error::new(io::ErrorKind::InvalidData, message);
+        let (header, table) = map
+            .split_at_checked(HEADER_LEN)
+            .ok_or_else(|| invalid("not a pattern matrix"))?;
+        if &header[..MAGIC.len()] != MAGIC {
+            return Err(invalid("not a pattern matrix"));
+        }
+        let len = u64::from_le_bytes(header[8..16].try_into().expect("8 bytes"));
+        let fingerprint = u64::from_le_bytes(header[16..24].try_into().expect("8 bytes"));
+        if len != cache.len() as u64 || fingerprint != cache.fingerprint() {
+            return Err(invalid(
+                "the pattern matrix was saved for another word list",
+            ));
+        }
+        if table.len() != cache.len() * cache.len() {
+            return Err(invalid("the pattern matrix is truncated"));
+        }
+
+        cache.rows = Rows::Mapped(map, |row| row);
+        Ok(cache)
+    }
+
+    /// Computes every row of the table and writes them to `path`, to be loaded with
+    /// [`PatternCache::load`]. The file takes a byte per pair of words.
+    #[cfg(feature = "std")]
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        writer.write_all(MAGIC)?;
+        writer.write_all(&(self.len() as u64).to_le_bytes())?;
+        writer.write_all(&self.fingerprint().to_le_bytes())?;
+        for guess in 0..self.len() {
+            writer.write_all(self.row(guess))?;
+        }
+        writer.flush()
+    }
+
+    /// Computes every row that was not looked up yet on `gpu`, a chunk of guesses at a time,
+    /// rather than the first time each of them is looked up.
+    ///
+    /// Rows of guesses that [`Correctness::compute_bytes`] does not accept are still computed on
+    /// the CPU when they are looked up. A cache loaded from a file has nothing left to compute.
+    #[cfg(feature = "gpu")]
+    pub fn compute_on_gpu(&self, gpu: &crate::gpu::Gpu) -> Result<(), crate::gpu::GpuError> {
+        /// Number of rows computed, and held twice in memory, at once.
+        const CHUNK: usize = 1024;
+
+        let rows = match &self.rows {
+            Rows::Lazy(rows) => rows,
+            Rows::Mapped(..) => return Ok(()),
+        };
+        let missing: Vec<usize> = (0..self.len())
+            .filter(|&guess| rows[guess].get().is_none())
+            .filter(|&guess| self.dictionary.letters(guess).is_some())
+            .collect();
+        for chunk in missing.chunks(CHUNK) {
+            let guesses: Vec<[u8; 5]> = chunk.iter().map(|&guess| self.dense[guess]).collect();
+            let patterns = gpu.packed_patterns(&guesses, &self.dense)?;
+            for (&guess, row) in chunk.iter().zip(patterns.chunks(self.len())) {
+                let mut row: Box<[u8]> = row.into();
+                self.patch_row(guess, &mut row);
+                // Another thread may have looked the row up meanwhile, with the same result.
+                let _ = rows[guess].set(row);
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<const N: usize> PatternCache<N>
+where
+    Correctness: Pack<N>,
+{
+    /// Returns a hash of the word list that is stable across runs and platforms.
+    #[cfg(feature = "std")]
+    fn fingerprint(&self) -> u64 {
+        self.dictionary.fingerprint()
+    }
+
+    /// Returns the number of words in the word list.
+    pub fn len(&self) -> usize {
+        self.dictionary.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.dictionary.is_empty()
+    }
+
+    /// Returns the frequency counts of `words`, the words of the cache in the same order, for the
+    /// guessers built with their own counts rather than those the cache was built with.
+    pub(crate) fn counts_of(
+        &self,
+        words: impl IntoIterator<Item = (&'static str, usize)>,
+    ) -> crate::Result<Arc<[usize]>> {
+        let counts: Arc<[usize]> = words.into_iter().map(|(_, count)| count).collect();
+        if counts.len() != self.len() {
+            return Err(crate::Error::CacheMismatch {
+                words: counts.len(),
+                cache: self.len(),
+            });
+        }
+        Ok(counts)
+    }
+
+    /// Returns the words the patterns are between.
+    pub fn dictionary(&self) -> &Arc<Dictionary<N>> {
+        &self.dictionary
+    }
+
+    /// Returns the index of `word`, if it is part of the word list.
+    pub fn index(&self, word: &str) -> Option<usize> {
+        self.dictionary.index(word)
+    }
+
+    /// Returns the word at `index`.
+    pub fn word(&self, index: usize) -> &'static str {
+        self.dictionary.word(index)
+    }
+
+    /// Returns the packed patterns revealed by the guess at index `guess`, indexed by answer.
+    pub fn row(&self, guess: usize) -> &[Packed<N>] {
+        let rows = match &self.rows {
+            Rows::Lazy(rows) => rows,
+            #[cfg(feature = "std")]
+            Rows::Mapped(map, patterns) => {
+                let start = HEADER_LEN + guess * self.len();
+                return patterns(&map[start..start + self.len()]);
+            }
+        };
+
+        rows[guess].get_or_init(|| match self.dictionary.letters(guess) {
+            Some(letters) => {
+                let mut row = vec![Default::default(); self.len()].into_boxed_slice();
+                Correctness::pack_row(letters.as_bytes(), &self.dense, &mut row);
+                self.patch_row(guess, &mut row);
+                row
+            }
+            None => (0..self.len())
+                .map(|answer| self.compute(guess, answer))
+                .collect(),
+        })
+    }
+
+    /// Computes the patterns of the answers that only have placeholders in `dense`, in the row of
+    /// the guess at index `guess` computed out of it.
+    fn patch_row(&self, guess: usize, row: &mut [Packed<N>]) {
+        for answer in (0..self.len()).filter(|&i| self.dictionary.letters(i).is_none()) {
+            row[answer] = self.compute(guess, answer);
+        }
+    }
+
+    /// Computes the packed pattern revealed by the guess at index `guess` against the answer at
+    /// index `answer` out of their strings.
+    fn compute(&self, guess: usize, answer: usize) -> Packed<N> {
+        <Correctness as Pack<N>>::pack(Correctness::compute(self.word(answer), self.word(guess)))
+    }
+
+    /// Returns the packed pattern revealed by the guess at index `guess` if the answer is the
+    /// word at index `answer`.
+    pub fn pattern(&self, guess: usize, answer: usize) -> Packed<N> {
+        self.row(guess)[answer]
+    }
+}
+
+impl<const N: usize> fmt::Debug for PatternCache<N>
+where
+    Correctness: Pack<N>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut debug = f.debug_struct("PatternCache");
+        debug.field("words", &self.len());
+        #[cfg(feature = "std")]
+        debug.field("mapped", &matches!(self.rows, Rows::Mapped(..)));
+        debug.finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Pack, PatternCache};
+    use crate::Correctness;
+
+    #[test]
+    fn matches_compute() {
+        let words = ["abcde", "baecd", "aabbb", "fghij"];
+        let cache: PatternCache = PatternCache::new(words.iter().copied());
+        for (g, guess) in words.iter().enumerate() {
+            for (a, answer) in words.iter().enumerate() {
+                assert_eq!(
+                    cache.pattern(g, a),
+                    Correctness::pack(Correctness::compute(answer, guess))
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn packs_other_lengths() {
+        let words = ["bright", "fright", "ñandus", "tights"];
+        let cache = PatternCache::<6>::new(words.iter().copied());
+        for (g, guess) in words.iter().enumerate() {
+            for (a, answer) in words.iter().enumerate() {
+                let mask: [Correctness; 6] = Correctness::compute(answer, guess);
+                assert_eq!(cache.pattern(g, a), <Correctness as Pack<6>>::pack(mask));
+                assert_eq!(<Correctness as Pack<6>>::unpack(cache.pattern(g, a)), mask);
+            }
+        }
+        assert!(PatternCache::<6>::try_new(["bright", "crane"]).is_err());
+
+        let mask = [Correctness::Wrong; 4];
+        assert_eq!(<Correctness as Pack<4>>::pack(mask), 80);
+        assert_eq!(<Correctness as Pack<4>>::unpack(80), mask);
+    }
+
+    #[test]
+    fn index() {
+        let cache: PatternCache = PatternCache::new(vec!["abcde", "fghij"]);
+        assert_eq!(cache.index("fghij"), Some(1));
+        assert_eq!(cache.index("klmno"), None);
+        assert_eq!(cache.word(0), "abcde");
+    }
+
+    #[test]
+    fn round_trip() {
+        let path = std::env::temp_dir().join("crane-pattern-matrix-test.bin");
+        let words = ["abcde", "baecd", "aabbb", "fghij", "ñandu"];
+        let cache = PatternCache::new(words.iter().copied());
+        cache.save(&path).unwrap();
+
+        let loaded = PatternCache::load(words.iter().copied(), &path).unwrap();
+        for guess in 0..words.len() {
+            assert_eq!(loaded.row(guess), cache.row(guess));
+        }
+
+        let other = PatternCache::load(words[1..].iter().copied(), &path);
+        assert_eq!(other.unwrap_err().kind(), std::io::ErrorKind::InvalidData);
+    }
+}
@@ -0,0 +1,217 @@
+use std::collections::HashSet;
+
+use super::{Pack, PatternCache};
+use crate::{Correctness, Guess};
+
+const BITS: usize = u64::BITS as usize;
+
+/// The words of a [`PatternCache`] that could still be the answer, as a bitset over their indices.
+///
+/// Narrowing it down after a guess only looks up the patterns of the guess in the cache instead
+/// of comparing strings, and cloning it for every game is a small copy.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Candidates {
+    bits: Vec<u64>,
+}
+
+impl Candidates {
+    /// Creates a set holding every word of a cache of `len` words.
+    pub fn all(len: usize) -> Self {
+        let mut bits = vec![u64::MAX; len.div_ceil(BITS)];
+        if !len.is_multiple_of(BITS) {
+            *bits.last_mut().expect("there is a partial block") = (1 << (len % BITS)) - 1;
+        }
+        Self { bits }
+    }
+
+    /// Creates a set holding the given words of a cache of `len` words.
+    pub fn from_indices(len: usize, indices: impl IntoIterator<Item = usize>) -> Self {
+        let mut bits = vec![0; len.div_ceil(BITS)];
+        for index in indices {
+            assert!(index < len, "index {} is out of bounds", index);
+            bits[index / BITS] |= 1 << (index % BITS);
+        }
+        Self { bits }
+    }
+
+    /// Returns the number of candidates left.
+    pub fn len(&self) -> usize {
+        self.bits
+            .iter()
+            .map(|block| block.count_ones() as usize)
+            .sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bits.iter().all(|&block| block == 0)
+    }
+
+    pub fn contains(&self, index: usize) -> bool {
+        self.bits
+            .get(index / BITS)
+            .is_some_and(|block| block & (1 << (index % BITS)) != 0)
+    }
+
+    /// Iterates over the indices of the candidates left, in increasing order.
+    pub fn iter(&self) -> impl Iterator<Item = usize> + '_ {
+        self.bits.iter().enumerate().flat_map(|(i, &block)| {
+            let mut block = block;
+            std::iter::from_fn(move || {
+                if block == 0 {
+                    return None;
+                }
+                let bit = block.trailing_zeros() as usize;
+                block &= block - 1;
+                Some(i * BITS + bit)
+            })
+        })
+    }
+
+    /// Returns the words of the candidates left, in the order of the cache.
+    pub fn words<const N: usize>(&self, cache: &PatternCache<N>) -> Vec<&'static str>
+    where
+        Correctness: Pack<N>,
+    {
+        self.iter().map(|index| cache.word(index)).collect()
+    }
+
+    /// Keeps only the candidates for which `f` returns true.
+    pub fn retain(&mut self, mut f: impl FnMut(usize) -> bool) {
+        for (i, block) in self.bits.iter_mut().enumerate() {
+            let mut remaining = *block;
+            while remaining != 0 {
+                let bit = remaining.trailing_zeros() as usize;
+                remaining &= remaining - 1;
+                if !f(i * BITS + bit) {
+                    *block &= !(1 << bit);
+                }
+            }
+        }
+    }
+
+    /// Keeps only the candidates that are among `words`, e.g. the official answer list rather than
+    /// every word of the dictionary. Words that are not part of the cache are ignored.
+    pub fn retain_words<'a, const N: usize>(
+        &mut self,
+        cache: &PatternCache<N>,
+        words: impl IntoIterator<Item = &'a str>,
+    ) where
+        Correctness: Pack<N>,
+    {
+        let words: HashSet<&str> = words.into_iter().collect();
+        self.retain(|candidate| words.contains(cache.word(candidate)));
+    }
+
+    /// Keeps only the candidates that could still be the answer after `guess`.
+    ///
+    /// If the guessed word is part of the cache, this is a lookup in its row of patterns,
+    /// otherwise the pattern is computed for every candidate.
+    pub fn retain_matching<const N: usize>(&mut self, cache: &PatternCache<N>, guess: &Guess<N>)
+    where
+        Correctness: Pack<N>,
+    {
+        match cache.index(&guess.word) {
+            Some(index) => {
+                let patterns = cache.row(index);
+                let pattern = <Correctness as Pack<N>>::pack(guess.mask);
+                self.retain(|candidate| patterns[candidate] == pattern);
+            }
+            None => self.retain(|candidate| guess.matches(cache.word(candidate))),
+        }
+    }
+
+    /// Keeps only the candidates that could still be the answer after the guesses of `history`
+    /// that come after the first `seen` ones, then counts them all as seen.
+    ///
+    /// Guessers are usually asked for every guess, and then only the last one is new, but a
+    /// wrapper may play some turns on their behalf, e.g. from an opening book.
+    pub fn catch_up<const N: usize>(
+        &mut self,
+        cache: &PatternCache<N>,
+        history: &[Guess<N>],
+        seen: &mut usize,
+    ) where
+        Correctness: Pack<N>,
+    {
+        for guess in &history[*seen..] {
+            self.retain_matching(cache, guess);
+        }
+        *seen = history.len();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Candidates;
+    use crate::algorithms::PatternCache;
+    use crate::Guess;
+
+    #[test]
+    fn all() {
+        for len in [0, 1, 63, 64, 65, 130] {
+            let candidates = Candidates::all(len);
+            assert_eq!(candidates.len(), len);
+            assert_eq!(candidates.is_empty(), len == 0);
+            assert!(candidates.iter().eq(0..len));
+            assert!(!candidates.contains(len));
+        }
+    }
+
+    #[test]
+    fn from_indices() {
+        let candidates = Candidates::from_indices(130, vec![129, 3, 64]);
+        assert_eq!(candidates.len(), 3);
+        assert!(candidates.iter().eq(vec![3, 64, 129]));
+    }
+
+    #[test]
+    fn retain() {
+        let mut candidates = Candidates::all(100);
+        candidates.retain(|i| i % 3 == 0);
+        assert_eq!(candidates.len(), 34);
+        assert!(candidates.contains(99));
+        assert!(!candidates.contains(98));
+        assert!(candidates.iter().eq((0..100).step_by(3)));
+    }
+
+    #[test]
+    fn retain_matching() {
+        let words = ["fight", "light", "right", "wrong"];
+        let cache = PatternCache::new(words.iter().copied());
+
+        let mut candidates = Candidates::all(words.len());
+        candidates.retain_matching(&cache, &Guess::new("right", mask!(W C C C C)));
+        assert!(candidates.iter().eq(vec![0, 1]));
+
+        // Guesses outside of the cache work too.
+        let mut candidates = Candidates::all(words.len());
+        candidates.retain_matching(&cache, &Guess::new("night", mask!(W C C C C)));
+        assert!(candidates.iter().eq(vec![0, 1, 2]));
+    }
+
+    #[test]
+    fn catch_up() {
+        let words = ["fight", "light", "night", "right", "wrong"];
+        let cache = PatternCache::new(words.iter().copied());
+        let history = [
+            Guess::new("wrong", mask!(W W W W M)),
+            Guess::new("light", mask!(W C C C C)),
+        ];
+
+        let mut candidates = Candidates::all(words.len());
+        let mut seen = 0;
+        candidates.catch_up(&cache, &history[..1], &mut seen);
+        assert!(candidates.iter().eq(vec![0, 1]));
+        candidates.catch_up(&cache, &history, &mut seen);
+        assert_eq!(seen, 2);
+        assert!(candidates.iter().eq(vec![0]));
+    }
+
+    #[test]
+    fn retain_words() {
+        let cache: PatternCache = PatternCache::new(vec!["fight", "light", "right", "wrong"]);
+        let mut candidates = Candidates::all(cache.len());
+        candidates.retain_words(&cache, vec!["wrong", "light", "night"]);
+        assert!(candidates.iter().eq(vec![1, 3]));
+    }
+}
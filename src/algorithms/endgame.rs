@@ -0,0 +1,214 @@
+use std::borrow::Cow;
+use std::mem;
+use std::sync::Arc;
+
+use super::exact::{self, Search, Solved};
+use super::{explain, Candidates, PatternCache};
+use crate::constraints::Constraints;
+use crate::{CancelToken, Estimate, Explanation, Guesser, History};
+
+/// Solves the endgame exactly: once few enough candidates remain, plays the guess that finds them
+/// in the fewest guesses on average, each being as likely to be the answer, and asks the wrapped
+/// guesser for the other guesses.
+///
+/// The search goes through every way of splitting the candidates that some word of the dictionary
+/// reveals, or some word the hints allow in hard mode, and remembers the best guess for every set
+/// of candidates it meets, so that the rest of the game is a lookup away. It is the one that
+/// builds optimal [`DecisionTree`](super::DecisionTree)s. If it is cancelled, the wrapped guesser
+/// picks the guess instead.
+#[derive(Clone)]
+pub struct Endgame<G> {
+    inner: G,
+    cache: Arc<PatternCache>,
+    remaining: Candidates,
+    /// Number of guesses of the history `remaining` was narrowed down with.
+    seen: usize,
+    threshold: usize,
+    hard_mode: bool,
+    cancel: CancelToken,
+    /// The best guess for every set of candidates searched so far.
+    solved: Solved,
+}
+
+impl<G> Endgame<G> {
+    /// Wraps `inner`, which picks its words out of `cache`.
+    pub fn new(inner: G, cache: Arc<PatternCache>) -> Self {
+        Self {
+            inner,
+            remaining: Candidates::all(cache.len()),
+            cache,
+            seen: 0,
+            threshold: 20,
+            hard_mode: false,
+            cancel: CancelToken::new(),
+            solved: Solved::new(),
+        }
+    }
+
+    /// Sets how few candidates must remain for the search to take over. Defaults to 20.
+    pub fn with_threshold(mut self, threshold: usize) -> Self {
+        self.threshold = threshold;
+        self
+    }
+
+    /// Only considers the given words as possible answers, like the wrapped guesser should.
+    /// Words that are not in the dictionary are ignored.
+    pub fn with_answers<'a>(mut self, answers: impl IntoIterator<Item = &'a str>) -> Self {
+        self.remaining.retain_words(&self.cache, answers);
+        self
+    }
+
+    /// Returns the best guess once few enough candidates remain after `history`, or `None` if the
+    /// wrapped guesser should pick it.
+    fn endgame_guess(&mut self, history: &History) -> Option<&'static str> {
+        self.remaining
+            .catch_up(&self.cache, history, &mut self.seen);
+        if self.remaining.is_empty() || self.remaining.len() > self.threshold {
+            return None;
+        }
+
+        let candidates: Vec<usize> = self.remaining.iter().collect();
+        let search = Search::new(&self.cache, self.hard_mode, &self.cancel)
+            .with_solved(mem::take(&mut self.solved));
+        let solved = search.solve(&candidates, &self.constraints(history));
+        self.solved = search.into_solved();
+        let (total, guess) = solved.ok()?;
+        tracing::debug!(
+            guess = self.cache.word(guess),
+            candidates = candidates.len(),
+            average = total as f64 / candidates.len() as f64,
+            "solved the endgame"
+        );
+        Some(self.cache.word(guess))
+    }
+
+    /// Returns the hints the guesses must follow after `history`, which the search only tracks in
+    /// hard mode.
+    fn constraints(&self, history: &History) -> Constraints {
+        if self.hard_mode {
+            history.constraints().clone()
+        } else {
+            Constraints::new()
+        }
+    }
+}
+
+impl<G: Guesser> Guesser for Endgame<G> {
+    fn guess(&mut self, history: &History) -> Cow<'static, str> {
+        match self.endgame_guess(history) {
+            Some(guess) => Cow::Borrowed(guess),
+            None => self.inner.guess(history),
+        }
+    }
+
+    fn guess_with_explanation(
+        &mut self,
+        history: &History,
+    ) -> (Cow<'static, str>, Option<Explanation>) {
+        match self.endgame_guess(history) {
+            Some(guess) => {
+                let explanation = explain(&self.cache, &self.remaining, |_| 1.0, guess);
+                (Cow::Borrowed(guess), Some(explanation))
+            }
+            None => self.inner.guess_with_explanation(history),
+        }
+    }
+
+    fn set_hard_mode(&mut self, hard_mode: bool) {
+        self.hard_mode = hard_mode;
+        self.inner.set_hard_mode(hard_mode)
+    }
+
+    fn set_cancel_token(&mut self, token: CancelToken) {
+        self.cancel = token.clone();
+        self.inner.set_cancel_token(token)
+    }
+
+    fn remaining(&self) -> Option<Vec<&'static str>> {
+        Some(self.remaining.words(&self.cache))
+    }
+
+    fn rank(&self, history: &History, k: usize) -> Vec<(&'static str, f64)> {
+        self.inner.rank(history, k)
+    }
+
+    /// Estimates the game exactly once the search took over, out of the best guess it found for
+    /// the candidates left.
+    fn estimate(&self, history: &History) -> Option<Estimate> {
+        let mut remaining = self.remaining.clone();
+        let mut seen = self.seen;
+        remaining.catch_up(&self.cache, history, &mut seen);
+        let candidates: Vec<usize> = remaining.iter().collect();
+        if candidates.is_empty() {
+            return None;
+        }
+        let constraints = self.hard_mode.then(|| self.constraints(history));
+        let (total, guess) = match exact::known(&self.solved, &candidates, constraints.as_ref()) {
+            Some(known) => known,
+            None => return self.inner.estimate(history),
+        };
+        let n = candidates.len() as f64;
+        Some(Estimate {
+            expected_turns: total as f64 / n,
+            win_prob: if remaining.contains(guess) {
+                1.0 / n
+            } else {
+                0.0
+            },
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::Endgame;
+    use crate::algorithms::{DecisionTree, PatternCache, Positional};
+    use crate::{Guesser, History, Wordle, WORDS};
+
+    fn endgame() -> Endgame<Positional> {
+        let cache = Arc::new(PatternCache::new(WORDS.iter().map(|&(word, _)| word)));
+        let inner = Positional::with_cache(WORDS.iter().copied(), cache.clone()).unwrap();
+        Endgame::new(inner, cache)
+    }
+
+    #[test]
+    fn plays_as_well_as_the_optimal_tree() {
+        let wordle = Wordle::with_dictionary(WORDS.iter().copied());
+        let cache = PatternCache::new(WORDS.iter().map(|&(word, _)| word));
+        let tree =
+            DecisionTree::build(&cache, WORDS.iter().map(|&(word, _)| word), false, None).unwrap();
+
+        let prototype = endgame();
+        let total: usize = WORDS
+            .iter()
+            .map(|&(answer, _)| wordle.play(answer, prototype.clone()).unwrap().unwrap())
+            .sum();
+        assert_eq!(total, tree.total_guesses());
+    }
+
+    #[test]
+    fn estimates_the_searched_endgame_exactly() {
+        let cache = PatternCache::new(WORDS.iter().map(|&(word, _)| word));
+        let tree =
+            DecisionTree::build(&cache, WORDS.iter().map(|&(word, _)| word), false, None).unwrap();
+
+        let mut guesser = endgame().with_threshold(WORDS.len());
+        let history = History::new();
+        guesser.guess(&history);
+        let estimate = guesser.estimate(&history).unwrap();
+        assert_eq!(
+            estimate.expected_turns,
+            tree.total_guesses() as f64 / WORDS.len() as f64
+        );
+    }
+
+    #[test]
+    fn leaves_the_opening_to_the_wrapped_guesser() {
+        let mut guesser = endgame().with_threshold(WORDS.len() - 1);
+        let inner = endgame().inner.guess(&History::new());
+        assert_eq!(guesser.guess(&History::new()), inner);
+        assert!(guesser.solved.is_empty());
+    }
+}
@@ -0,0 +1,188 @@
+use std::borrow::Cow;
+
+use crate::{CancelToken, Estimate, Explanation, Guesser, History};
+
+/// When an [`Ensemble`] hands over from its early guesser to its late one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Crossover {
+    /// The early guesser picks at least this many guesses.
+    pub turns: usize,
+    /// After that, the late guesser picks the guess as soon as at most this many candidates
+    /// remain.
+    pub candidates: usize,
+}
+
+impl Default for Crossover {
+    fn default() -> Self {
+        Self {
+            turns: 2,
+            candidates: 3,
+        }
+    }
+}
+
+/// Plays the guesses of an early guesser while many candidates remain, and those of a late
+/// guesser once few do, e.g. the one revealing the most information to narrow the candidates
+/// down, then the one picking the most common of the last few.
+///
+/// Once the early guesser has picked the first [`Crossover::turns`] guesses, the late guesser
+/// suggests a guess at every turn, and it is played if the late guesser has at most
+/// [`Crossover::candidates`] candidates left. The late guesser must keep track of its candidates,
+/// see [`Guesser::remaining`], and is best kept cheap since it is asked even on the turns it
+/// loses.
+#[derive(Clone)]
+pub struct Ensemble<E, L> {
+    early: E,
+    late: L,
+    crossover: Crossover,
+    /// Whether the late guesser was asked for the last guess, in which case its candidates are
+    /// the latest ones.
+    asked_late: bool,
+}
+
+impl<E, L> Ensemble<E, L> {
+    pub fn new(early: E, late: L) -> Self {
+        Self {
+            early,
+            late,
+            crossover: Crossover::default(),
+            asked_late: false,
+        }
+    }
+
+    /// Sets when the late guesser takes over. Defaults to after two guesses, with at most three
+    /// candidates left.
+    pub fn with_crossover(mut self, crossover: Crossover) -> Self {
+        self.crossover = crossover;
+        self
+    }
+
+    /// Returns what the late guesser suggests with `late` if it takes over at this turn, or what
+    /// the early one suggests with `early` otherwise.
+    fn pick<T, const N: usize>(
+        &mut self,
+        history: &History<N>,
+        late: impl FnOnce(&mut L, &History<N>) -> T,
+        early: impl FnOnce(&mut E, &History<N>) -> T,
+    ) -> T
+    where
+        E: Guesser<N>,
+        L: Guesser<N>,
+    {
+        self.asked_late = history.len() >= self.crossover.turns;
+        if self.asked_late {
+            let suggestion = late(&mut self.late, history);
+            let left = self.late.remaining().map(|remaining| remaining.len());
+            if left.is_some_and(|left| left <= self.crossover.candidates) {
+                tracing::debug!(candidates = left, "the late guesser takes over");
+                return suggestion;
+            }
+        }
+        early(&mut self.early, history)
+    }
+}
+
+impl<E: Guesser<N>, L: Guesser<N>, const N: usize> Guesser<N> for Ensemble<E, L> {
+    fn guess(&mut self, history: &History<N>) -> Cow<'static, str> {
+        self.pick(history, L::guess, E::guess)
+    }
+
+    fn guess_with_explanation(
+        &mut self,
+        history: &History<N>,
+    ) -> (Cow<'static, str>, Option<Explanation<N>>) {
+        self.pick(
+            history,
+            L::guess_with_explanation,
+            E::guess_with_explanation,
+        )
+    }
+
+    fn set_hard_mode(&mut self, hard_mode: bool) {
+        self.early.set_hard_mode(hard_mode);
+        self.late.set_hard_mode(hard_mode);
+    }
+
+    fn set_cancel_token(&mut self, token: CancelToken) {
+        self.early.set_cancel_token(token.clone());
+        self.late.set_cancel_token(token);
+    }
+
+    fn remaining(&self) -> Option<Vec<&'static str>> {
+        if self.asked_late {
+            self.late.remaining()
+        } else {
+            self.early.remaining()
+        }
+    }
+
+    /// Ranks the guesses like the early guesser, which looks further ahead than the late one.
+    fn rank(&self, history: &History<N>, k: usize) -> Vec<(&'static str, f64)> {
+        self.early.rank(history, k)
+    }
+
+    fn estimate(&self, history: &History<N>) -> Option<Estimate> {
+        if self.asked_late {
+            self.late.estimate(history)
+        } else {
+            self.early.estimate(history)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Crossover, Ensemble};
+    use crate::algorithms::{Naive, Positional, TieBreak};
+    use crate::{Correctness, Guess, Guesser, History, Wordle, WORDS};
+
+    /// Every candidate left tells the others apart, so the naive guesser picks the first one.
+    fn naive() -> Naive {
+        Naive::with_dictionary(WORDS.iter().copied()).with_tie_break(TieBreak::Alphabetical)
+    }
+
+    fn ensemble(crossover: Crossover) -> Ensemble<Naive, Positional> {
+        Ensemble::new(naive(), Positional::with_dictionary(WORDS.iter().copied()))
+            .with_crossover(crossover)
+    }
+
+    #[test]
+    fn hands_over_past_the_crossover() {
+        let history = History::from(vec![Guess::new(
+            "sissy",
+            Correctness::compute("humph", "sissy"),
+        )]);
+        let late = Positional::with_dictionary(WORDS.iter().copied()).guess(&history);
+        let early = naive().guess(&history);
+        assert_ne!(late, early);
+
+        // Eight candidates are left after "sissy", which is few enough once a guess was played.
+        let crossover = Crossover {
+            turns: 1,
+            candidates: 8,
+        };
+        assert_eq!(ensemble(crossover).guess(&history), late);
+        let crossover = Crossover {
+            turns: 2,
+            ..crossover
+        };
+        assert_eq!(ensemble(crossover).guess(&history), early);
+        let crossover = Crossover {
+            turns: 1,
+            candidates: 7,
+        };
+        assert_eq!(ensemble(crossover).guess(&history), early);
+    }
+
+    #[test]
+    fn plays_every_answer() {
+        let wordle = Wordle::with_dictionary(WORDS.iter().copied());
+        let prototype = ensemble(Crossover::default());
+        for &(answer, _) in WORDS {
+            assert!(matches!(
+                wordle.play(answer, prototype.clone()),
+                Ok(Some(_))
+            ));
+        }
+    }
+}
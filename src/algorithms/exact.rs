@@ -0,0 +1,365 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::{Mutex, MutexGuard};
+
+use super::{PatternBuckets, PatternCache};
+use crate::constraints::Constraints;
+use crate::{CancelToken, Correctness, Guess};
+
+/// The best guess for every set of candidates searched so far, along with the number of guesses
+/// it takes to find every one of them.
+pub(crate) type Solved = HashMap<Key, (usize, usize)>;
+
+/// The sorted indices of a set of candidates, along with the constraints on the guesses in hard
+/// mode, since they decide which guesses are allowed.
+pub(crate) type Key = (Box<[u16]>, Option<Constraints>);
+
+/// The error of a search that gives up once cancelled.
+#[derive(Debug)]
+pub(crate) struct Cancelled;
+
+/// Finds the guess that finds every one of a set of candidates in the fewest guesses, each being
+/// as likely to be the answer, for [`Endgame`](super::Endgame) and
+/// [`DecisionTree`](super::DecisionTree).
+///
+/// Every guess allowed in the mode of the game is ranked by the number of candidates it leaves on
+/// average, and the most promising ones are searched, skipping those that cannot beat the best
+/// one so far. The best guess for every set of candidates met is remembered, so that the rest of
+/// the game is a lookup away.
+pub(crate) struct Search<'a> {
+    cache: &'a PatternCache,
+    hard_mode: bool,
+    /// How many of the most promising guesses are searched for every set of candidates.
+    beam: usize,
+    cancel: &'a CancelToken,
+    /// Whether to fail once cancelled, rather than only search the most promising guess from then
+    /// on.
+    give_up: bool,
+    /// The packed mask of a win.
+    win: u8,
+    solved: Mutex<Solved>,
+}
+
+impl<'a> Search<'a> {
+    /// Creates a search through every guess of `cache` allowed in `hard_mode`, which gives up once
+    /// `cancel` is cancelled.
+    pub(crate) fn new(cache: &'a PatternCache, hard_mode: bool, cancel: &'a CancelToken) -> Self {
+        Self {
+            cache,
+            hard_mode,
+            beam: usize::MAX,
+            cancel,
+            give_up: true,
+            win: Correctness::pack([Correctness::Correct; 5]),
+            solved: Mutex::new(Solved::new()),
+        }
+    }
+
+    /// Only searches the `beam` most promising guesses for every set of candidates, which is much
+    /// faster but may miss the best one.
+    pub(crate) fn with_beam(mut self, beam: usize) -> Self {
+        self.beam = beam.max(1);
+        self
+    }
+
+    /// Once cancelled, only searches the most promising guess for every set of candidates left,
+    /// rather than giving up.
+    pub(crate) fn hurry_once_cancelled(mut self) -> Self {
+        self.give_up = false;
+        self
+    }
+
+    /// Starts from the sets of candidates solved by an earlier search with the same settings.
+    pub(crate) fn with_solved(mut self, solved: Solved) -> Self {
+        self.solved = Mutex::new(solved);
+        self
+    }
+
+    /// Returns the sets of candidates solved so far, e.g. for a later search to start from.
+    pub(crate) fn into_solved(self) -> Solved {
+        self.solved
+            .into_inner()
+            .expect("the solved sets are never poisoned")
+    }
+
+    pub(crate) fn word(&self, guess: usize) -> &'static str {
+        self.cache.word(guess)
+    }
+
+    /// Returns the number of guesses it takes to find every one of the sorted `candidates` when
+    /// playing the best guess, along with that guess. `constraints` are the hints revealed so
+    /// far, which the guesses must follow in hard mode.
+    pub(crate) fn solve(
+        &self,
+        candidates: &[usize],
+        constraints: &Constraints,
+    ) -> Result<(usize, usize), Cancelled> {
+        let constraints_key = self.hard_mode.then_some(constraints);
+        if let Some(known) = known(&self.solved(), candidates, constraints_key) {
+            return Ok(known);
+        }
+        if self.give_up && self.cancel.is_cancelled() {
+            return Err(Cancelled);
+        }
+
+        let shortlist = self.shortlist(candidates, constraints);
+        let best = self
+            .best(&shortlist, candidates, constraints)?
+            .expect("every candidate is a guess that makes progress");
+        // Guesses were skipped once cancelled, so the best one so far may not be the best one.
+        if self.give_up && self.cancel.is_cancelled() {
+            return Err(Cancelled);
+        }
+        self.solved().insert(key(candidates, constraints_key), best);
+        Ok(best)
+    }
+
+    /// Searches every guess of the `shortlist` in turn, and returns the one that takes the fewest
+    /// guesses along with their number, the first one on ties.
+    #[cfg(not(feature = "parallel"))]
+    fn best(
+        &self,
+        shortlist: &[(usize, usize)],
+        candidates: &[usize],
+        constraints: &Constraints,
+    ) -> Result<Option<(usize, usize)>, Cancelled> {
+        let mut best: Option<(usize, usize)> = None;
+        for &(bound, guess) in shortlist {
+            if best.is_some() && self.cancel.is_cancelled() {
+                break;
+            }
+            // Only a guess taking fewer guesses than the best one so far replaces it.
+            let limit = best.map_or(usize::MAX, |(best, _)| best - 1);
+            if bound > limit {
+                continue;
+            }
+            if let Some(total) = self.expand(guess, candidates, constraints, || limit)? {
+                best = Some((total, guess));
+            }
+        }
+        Ok(best)
+    }
+
+    /// Searches the guesses of the `shortlist` on all cores, and returns the one that takes the
+    /// fewest guesses along with their number, the first one on ties.
+    ///
+    /// Guesses are searched as tasks of their own, which idle threads steal from busy ones. They
+    /// are pruned against the best total found by any thread, but never when they tie with it, so
+    /// that the first of the best guesses is kept whichever thread searches it first, and the
+    /// result is the same with any number of threads. A single guess has its buckets solved in
+    /// parallel instead.
+    #[cfg(feature = "parallel")]
+    fn best(
+        &self,
+        shortlist: &[(usize, usize)],
+        candidates: &[usize],
+        constraints: &Constraints,
+    ) -> Result<Option<(usize, usize)>, Cancelled> {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        use rayon::prelude::*;
+
+        if let [(_, guess)] = *shortlist {
+            let (_, buckets) = self.split(guess, candidates);
+            let totals = buckets
+                .into_par_iter()
+                .map(|(mask, bucket)| {
+                    let constraints = self.narrow(guess, mask, constraints);
+                    Ok(self.solve(&bucket, &constraints)?.0)
+                })
+                .collect::<Result<Vec<usize>, Cancelled>>()?;
+            return Ok(Some((
+                candidates.len() + totals.iter().sum::<usize>(),
+                guess,
+            )));
+        }
+
+        let best = AtomicUsize::new(usize::MAX);
+        let searched = shortlist
+            .par_iter()
+            .enumerate()
+            .filter(|&(i, _)| i == 0 || !self.cancel.is_cancelled())
+            .map(|(i, &(bound, guess))| {
+                if bound > best.load(Ordering::Relaxed) {
+                    return Ok(None);
+                }
+                let total = self.expand(guess, candidates, constraints, || {
+                    best.load(Ordering::Relaxed)
+                })?;
+                Ok(total.map(|total| {
+                    best.fetch_min(total, Ordering::Relaxed);
+                    (i, total, guess)
+                }))
+            })
+            .collect::<Result<Vec<_>, Cancelled>>()?;
+        Ok(searched
+            .into_iter()
+            .flatten()
+            .min_by_key(|&(i, total, _)| (total, i))
+            .map(|(_, total, guess)| (total, guess)))
+    }
+
+    /// Returns the number of guesses it takes to find every one of `candidates` playing `guess`
+    /// first, or `None` as soon as it takes more than `limit` returns.
+    fn expand(
+        &self,
+        guess: usize,
+        candidates: &[usize],
+        constraints: &Constraints,
+        limit: impl Fn() -> usize,
+    ) -> Result<Option<usize>, Cancelled> {
+        let (_, buckets) = self.split(guess, candidates);
+
+        // Every candidate takes this guess, plus those it takes to find it afterwards.
+        let mut total = candidates.len();
+        for (mask, bucket) in buckets {
+            if total > limit() {
+                return Ok(None);
+            }
+            total += self
+                .solve(&bucket, &self.narrow(guess, mask, constraints))?
+                .0;
+        }
+
+        Ok((total <= limit()).then_some(total))
+    }
+
+    /// Returns whether `guess` is one of `candidates`, along with the other candidates keyed by
+    /// the mask the guess reveals for them.
+    pub(crate) fn split(
+        &self,
+        guess: usize,
+        candidates: &[usize],
+    ) -> (bool, BTreeMap<u8, Vec<usize>>) {
+        let patterns = self.cache.row(guess);
+        let mut buckets: BTreeMap<u8, Vec<usize>> = BTreeMap::new();
+        for &candidate in candidates {
+            buckets
+                .entry(patterns[candidate])
+                .or_default()
+                .push(candidate);
+        }
+        let answer = buckets.remove(&self.win).is_some();
+        (answer, buckets)
+    }
+
+    /// Returns the constraints on the next guesses once `guess` revealed `mask`.
+    pub(crate) fn narrow(&self, guess: usize, mask: u8, constraints: &Constraints) -> Constraints {
+        let mut constraints = constraints.clone();
+        if self.hard_mode {
+            let word = self.cache.word(guess);
+            constraints.add(&Guess::new(word, Correctness::unpack(mask)));
+        }
+        constraints
+    }
+
+    /// Returns the guesses worth searching, i.e. the `beam` ones that leave the fewest candidates
+    /// on average, preferring the candidates themselves on ties, each along with at least the
+    /// number of guesses it takes to find every candidate after it.
+    fn shortlist(&self, candidates: &[usize], constraints: &Constraints) -> Vec<(usize, usize)> {
+        let mut ranked: Vec<(usize, bool, usize, usize)> = (0..self.cache.len())
+            .filter(|&guess| {
+                !self.hard_mode || constraints.hard_mode_allows(self.cache.word(guess))
+            })
+            .filter_map(|guess| {
+                let buckets = PatternBuckets::count(self.cache.row(guess), candidates);
+
+                let answer = buckets.get(self.win) > 0;
+                // A guess that cannot win and reveals the same mask for every candidate teaches
+                // nothing.
+                if !answer && buckets.largest() == candidates.len() {
+                    return None;
+                }
+                // After this guess, the candidate it wins with is found, and every other one
+                // takes at least one more guess, and another one unless it is guessed next.
+                let others = buckets
+                    .totals()
+                    .iter()
+                    .enumerate()
+                    .filter(|&(pattern, &size)| pattern != self.win as usize && size > 0)
+                    .count();
+                let bound = candidates.len() + 2 * (candidates.len() - answer as usize) - others;
+                Some((buckets.sum_of_squares(), !answer, bound, guess))
+            })
+            .collect();
+        ranked.sort_unstable();
+
+        // Outside hard mode, guesses splitting the candidates the same way take as many guesses,
+        // so only the first one is searched. In hard mode, they may allow different guesses next.
+        let mut seen = HashSet::new();
+        ranked
+            .into_iter()
+            .filter(|&(_, _, _, guess)| {
+                let patterns = self.cache.row(guess);
+                self.hard_mode
+                    || seen.insert(
+                        candidates
+                            .iter()
+                            .map(|&candidate| patterns[candidate])
+                            .collect::<Box<[u8]>>(),
+                    )
+            })
+            .take(self.beam)
+            .map(|(_, _, bound, guess)| (bound, guess))
+            .collect()
+    }
+
+    fn solved(&self) -> MutexGuard<'_, Solved> {
+        self.solved
+            .lock()
+            .expect("the solved sets are never poisoned")
+    }
+}
+
+/// Returns the best guess for the sorted `candidates` along with the number of guesses it takes
+/// to find them all, if it is obvious or was already searched. The `constraints` are those of the
+/// search in hard mode.
+pub(crate) fn known(
+    solved: &Solved,
+    candidates: &[usize],
+    constraints: Option<&Constraints>,
+) -> Option<(usize, usize)> {
+    match *candidates {
+        [answer] => Some((1, answer)),
+        // Guessing either candidate is optimal: it takes one guess to find it, and two for the
+        // other one.
+        [first, _] => Some((3, first)),
+        _ => solved.get(&key(candidates, constraints)).copied(),
+    }
+}
+
+fn key(candidates: &[usize], constraints: Option<&Constraints>) -> Key {
+    // The cache holds at most `MAX_WORDS` words, so indices fit.
+    let candidates = candidates
+        .iter()
+        .map(|&candidate| candidate as u16)
+        .collect();
+    (candidates, constraints.cloned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Search;
+    use crate::algorithms::PatternCache;
+    use crate::constraints::Constraints;
+    use crate::{CancelToken, WORDS};
+
+    #[test]
+    fn cancelled() {
+        let cache = PatternCache::new(WORDS.iter().map(|&(word, _)| word));
+        let candidates: Vec<usize> = (0..WORDS.len()).collect();
+        let (exact, _) = Search::new(&cache, false, &CancelToken::new())
+            .solve(&candidates, &Constraints::new())
+            .unwrap();
+
+        let cancel = CancelToken::new();
+        cancel.cancel();
+        let search = Search::new(&cache, false, &cancel);
+        assert!(search.solve(&candidates, &Constraints::new()).is_err());
+        assert!(search.into_solved().is_empty());
+
+        // Only the most promising guess is searched, which does no better.
+        let search = Search::new(&cache, false, &cancel).hurry_once_cancelled();
+        let (hurried, _) = search.solve(&candidates, &Constraints::new()).unwrap();
+        assert!(hurried >= exact);
+    }
+}
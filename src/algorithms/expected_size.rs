@@ -0,0 +1,160 @@
+use std::borrow::Cow;
+use std::cmp::Reverse;
+use std::sync::Arc;
+
+use super::{
+    best_by_bounded, explain, no_candidate_left, top, Candidates, PatternBuckets, PatternCache,
+    TieBreak, Tiles,
+};
+use crate::{CancelToken, Dictionary, Explanation, Guesser, History};
+
+/// Picks the guess that leaves the fewest candidates on average.
+///
+/// If a guess splits the `n` remaining candidates into buckets of sizes `b`, the answer falls in
+/// each bucket with probability `b / n` and leaves `b` candidates, so the expected number of
+/// candidates left is the sum of `b² / n`. Unlike entropy, this needs no logarithm.
+#[derive(Clone)]
+pub struct ExpectedSize {
+    remaining: Candidates,
+    /// Number of guesses of the history `remaining` was narrowed down with.
+    seen: usize,
+    /// Frequency count of every word, indexed like the pattern cache.
+    counts: Arc<[usize]>,
+    tie_break: TieBreak,
+    cancel: CancelToken,
+    cache: Arc<PatternCache>,
+}
+
+impl ExpectedSize {
+    /// Creates a guesser that picks its words from the built-in dictionary.
+    pub fn new() -> Self {
+        Self::from_cache(PatternCache::global())
+    }
+
+    /// Creates a guesser that picks its words from the given dictionary, with a new
+    /// [`PatternCache`] for it.
+    pub fn with_dictionary(words: impl IntoIterator<Item = (&'static str, usize)>) -> Self {
+        let dictionary = Arc::new(Dictionary::with_counts(words));
+        Self::from_cache(Arc::new(PatternCache::for_dictionary(dictionary)))
+    }
+
+    /// Creates a guesser that picks its words from the dictionary of `cache`, with the frequency
+    /// counts it was built with, rather than reading the word list again.
+    pub fn from_cache(cache: Arc<PatternCache>) -> Self {
+        Self::with_counts(cache.dictionary().counts(), cache)
+    }
+
+    /// Creates a guesser that picks its words from the given dictionary, looking their patterns
+    /// up in `cache`, e.g. one loaded with [`PatternCache::load`].
+    ///
+    /// `words` must be in the same order as in `cache`, or this fails with
+    /// [`Error::CacheMismatch`](crate::Error::CacheMismatch) if there are not as many.
+    pub fn with_cache(
+        words: impl IntoIterator<Item = (&'static str, usize)>,
+        cache: Arc<PatternCache>,
+    ) -> crate::Result<Self> {
+        let counts = cache.counts_of(words)?;
+        Ok(Self::with_counts(counts, cache))
+    }
+
+    fn with_counts(counts: Arc<[usize]>, cache: Arc<PatternCache>) -> Self {
+        ExpectedSize {
+            remaining: Candidates::all(counts.len()),
+            seen: 0,
+            counts,
+            tie_break: TieBreak::default(),
+            cancel: CancelToken::new(),
+            cache,
+        }
+    }
+
+    /// Sets how to pick among guesses that score the same. Defaults to
+    /// [`TieBreak::PossibleAnswer`].
+    pub fn with_tie_break(mut self, tie_break: TieBreak) -> Self {
+        self.tie_break = tie_break;
+        self
+    }
+
+    /// Only considers the given words as possible answers, see [`Candidates::retain_words`].
+    pub fn with_answers<'a>(mut self, answers: impl IntoIterator<Item = &'a str>) -> Self {
+        self.remaining.retain_words(&self.cache, answers);
+        self
+    }
+}
+
+impl Default for ExpectedSize {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Guesser for ExpectedSize {
+    fn guess(&mut self, history: &History) -> Cow<'static, str> {
+        self.remaining
+            .catch_up(&self.cache, history, &mut self.seen);
+        if self.remaining.is_empty() {
+            return no_candidate_left(&self.cache, history);
+        }
+
+        let cache = &self.cache;
+        let candidates: Vec<usize> = self.remaining.iter().collect();
+
+        // Every candidate counts the same, so comparing the sums of squares is enough.
+        let preference = |guess| {
+            self.tie_break
+                .preference(cache.word(guess), self.counts[guess], true)
+        };
+        // The sum of squares is smallest when the candidates are spread evenly over as many
+        // buckets as the guess may reveal patterns, which is at most as many as its tiles do.
+        let n = candidates.len();
+        let tiles = Tiles::new(cache, candidates.iter().map(|&index| (index, 1.0)));
+        let bound = |guess| {
+            let buckets = n.min(tiles.patterns(guess));
+            (Reverse((n * n).div_ceil(buckets)), preference(guess))
+        };
+        let (best, (Reverse(squares), _)) =
+            best_by_bounded(&candidates, &self.cancel, bound, |guess| {
+                let squares = PatternBuckets::count(cache.row(guess), &candidates).sum_of_squares();
+                (Reverse(squares), preference(guess))
+            })
+            .expect("there is always at least one remaining candidate");
+        tracing::debug!(
+            guess = cache.word(best),
+            expected_size = squares as f64 / candidates.len() as f64,
+            candidates = candidates.len(),
+            "picked the guess leaving the fewest candidates on average"
+        );
+
+        Cow::Borrowed(cache.word(best))
+    }
+
+    fn guess_with_explanation(
+        &mut self,
+        history: &History,
+    ) -> (Cow<'static, str>, Option<Explanation>) {
+        let guess = self.guess(history);
+        let explanation = explain(&self.cache, &self.remaining, |_| 1.0, &guess);
+        (guess, Some(explanation))
+    }
+
+    fn set_cancel_token(&mut self, token: CancelToken) {
+        self.cancel = token;
+    }
+
+    fn remaining(&self) -> Option<Vec<&'static str>> {
+        Some(self.remaining.words(&self.cache))
+    }
+
+    /// Ranks the candidates by the number of candidates they are expected to leave, negated so
+    /// that higher is better.
+    fn rank(&self, history: &History, k: usize) -> Vec<(&'static str, f64)> {
+        let (mut remaining, mut seen) = (self.remaining.clone(), self.seen);
+        remaining.catch_up(&self.cache, history, &mut seen);
+        let candidates: Vec<usize> = remaining.iter().collect();
+        top(&self.cache, &candidates, k, |guess| {
+            let squares =
+                PatternBuckets::count(self.cache.row(guess), &candidates).sum_of_squares();
+            -(squares as f64) / candidates.len() as f64
+        })
+    }
+}
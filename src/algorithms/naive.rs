@@ -1,56 +1,419 @@
-use std::collections::HashMap;
-use std::iter::FromIterator;
+use std::borrow::Cow;
+use std::sync::Arc;
 
-use crate::{Guess, Guesser, DICTIONARY};
+use super::{
+    best_by_bounded, entropy, explain, information, no_candidate_left, top, Candidates, GuessPool,
+    OpenerRules, Pack, PatternCache, Prior, ReducedPool, TieBreak, Tiles, Weighting,
+};
+use crate::{CancelToken, Correctness, Dictionary, Explanation, Guesser, History};
 
-pub struct Naive {
-    remaining: HashMap<&'static str, usize>,
+/// Relative error of an entropy computed in floating point, well above the few bits lost when
+/// summing the same terms in different orders.
+pub(super) const ROUNDING: f64 = 1e-9;
+
+/// Picks the guess expected to reveal the most information about the answer, for words of `N`
+/// letters.
+#[derive(Clone)]
+pub struct Naive<const N: usize = 5>
+where
+    Correctness: Pack<N>,
+{
+    remaining: Candidates,
+    /// Number of guesses of the history `remaining` was narrowed down with.
+    seen: usize,
+    /// Frequency count of every word, indexed like the pattern cache.
+    counts: Arc<[usize]>,
+    /// Likelihood of every word being the answer, according to the configured [`Prior`] and
+    /// [`Weighting`].
+    weights: Arc<[f64]>,
+    prior: Prior,
+    weighting: Option<Arc<Weighting>>,
+    pool: GuessPool,
+    /// The only words considered among those that were ruled out, sorted, if any.
+    reduced: Option<Arc<[usize]>>,
+    opener: OpenerRules,
+    tie_break: TieBreak,
+    cancel: CancelToken,
+    hard_mode: bool,
+    cache: Arc<PatternCache<N>>,
+    /// The last guess picked, along with the candidates it would leave for every packed pattern it
+    /// may reveal, so that the candidates left once it is played are a lookup away.
+    partition: Option<(usize, Vec<Vec<u16>>)>,
 }
 
 impl Naive {
+    /// Creates a guesser that picks its words from the built-in dictionary.
     pub fn new() -> Self {
+        Self::from_cache(PatternCache::global())
+    }
+}
+
+impl<const N: usize> Naive<N>
+where
+    Correctness: Pack<N>,
+{
+    /// Creates a guesser that picks its words from the given dictionary, with a new
+    /// [`PatternCache`] for it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a word is not `N` letters long, see [`Naive::try_with_dictionary`].
+    pub fn with_dictionary(words: impl IntoIterator<Item = (&'static str, usize)>) -> Self {
+        let dictionary = Arc::new(Dictionary::with_counts(words));
+        Self::from_cache(Arc::new(PatternCache::for_dictionary(dictionary)))
+    }
+
+    /// Like [`Naive::with_dictionary`], but fails with
+    /// [`Error::WordLength`](crate::Error::WordLength) rather than panicking when a word is not `N`
+    /// letters long.
+    pub fn try_with_dictionary(
+        words: impl IntoIterator<Item = (&'static str, usize)>,
+    ) -> crate::Result<Self> {
+        let dictionary = Arc::new(Dictionary::try_with_counts(words)?);
+        Ok(Self::from_cache(Arc::new(PatternCache::for_dictionary(
+            dictionary,
+        ))))
+    }
+
+    /// Creates a guesser that picks its words from the dictionary of `cache`, with the frequency
+    /// counts it was built with, rather than reading the word list again.
+    pub fn from_cache(cache: Arc<PatternCache<N>>) -> Self {
+        Self::with_counts(cache.dictionary().counts(), cache)
+    }
+
+    /// Creates a guesser that picks its words from the given dictionary, looking their patterns
+    /// up in `cache`, e.g. one loaded with [`PatternCache::load`].
+    ///
+    /// `words` must be in the same order as in `cache`, or this fails with
+    /// [`Error::CacheMismatch`](crate::Error::CacheMismatch) if there are not as many.
+    pub fn with_cache(
+        words: impl IntoIterator<Item = (&'static str, usize)>,
+        cache: Arc<PatternCache<N>>,
+    ) -> crate::Result<Self> {
+        let counts = cache.counts_of(words)?;
+        Ok(Self::with_counts(counts, cache))
+    }
+
+    fn with_counts(counts: Arc<[usize]>, cache: Arc<PatternCache<N>>) -> Self {
         Naive {
-            remaining: HashMap::from_iter(DICTIONARY.lines().map(|line| {
-                let (word, count) = line
-                    .split_once(' ')
-                    .expect("every line must have the answer and its frequency count");
+            remaining: Candidates::all(counts.len()),
+            seen: 0,
+            weights: counts.iter().map(|&count| count as f64).collect(),
+            prior: Prior::default(),
+            weighting: None,
+            counts,
+            pool: GuessPool::default(),
+            reduced: None,
+            opener: OpenerRules::default(),
+            tie_break: TieBreak::default(),
+            cancel: CancelToken::new(),
+            hard_mode: false,
+            cache,
+            partition: None,
+        }
+    }
+
+    /// Sets how likely each word is considered to be the answer. Defaults to [`Prior::Counts`].
+    pub fn with_prior(mut self, prior: Prior) -> Self {
+        self.prior = prior;
+        self.reweigh();
+        self
+    }
+
+    /// Also multiplies the prior likelihood of every word by its factor in `weighting`, e.g. to
+    /// favor the words the official game picks as answers.
+    pub fn with_weighting(mut self, weighting: Arc<Weighting>) -> Self {
+        self.weighting = Some(weighting);
+        self.reweigh();
+        self
+    }
 
-                let count: usize = count.parse().expect("every count is a number");
-                (word, count)
-            })),
+    fn reweigh(&mut self) {
+        let mut weights = self.prior.weights(&self.counts);
+        if let Some(weighting) = &self.weighting {
+            for (index, weight) in weights.iter_mut().enumerate() {
+                *weight *= weighting.factor(self.cache.word(index));
+            }
         }
+        self.weights = weights.into();
+    }
+
+    /// Sets which words are considered for guessing. Defaults to [`GuessPool::Remaining`].
+    pub fn with_pool(mut self, pool: GuessPool) -> Self {
+        self.pool = pool;
+        self
+    }
+
+    /// Only considers the words of `pool` among those that were ruled out, when the
+    /// [`GuessPool`] includes them at all, which makes scoring guesses much cheaper. Words that are
+    /// not in the dictionary are ignored.
+    pub fn with_reduced_pool(mut self, pool: &ReducedPool) -> Self {
+        let mut reduced: Vec<usize> = pool
+            .words()
+            .filter_map(|word| self.cache.index(word))
+            .collect();
+        reduced.sort_unstable();
+        self.reduced = Some(reduced.into());
+        self
+    }
+
+    /// Only considers the guesses that follow `rules` for the first guess.
+    pub fn with_opener_rules(mut self, rules: OpenerRules) -> Self {
+        self.opener = rules;
+        self
+    }
+
+    /// Sets how to pick among guesses that score the same. Defaults to
+    /// [`TieBreak::PossibleAnswer`].
+    pub fn with_tie_break(mut self, tie_break: TieBreak) -> Self {
+        self.tie_break = tie_break;
+        self
+    }
+
+    /// Only considers the given words as possible answers, see [`Candidates::retain_words`].
+    pub fn with_answers<'a>(mut self, answers: impl IntoIterator<Item = &'a str>) -> Self {
+        self.remaining.retain_words(&self.cache, answers);
+        self
     }
 }
 
-#[derive(Debug, Copy, Clone)]
-struct Candidate {
-    word: &'static str,
-    count: usize,
-    goodness: f64,
+impl<const N: usize> Naive<N>
+where
+    Correctness: Pack<N>,
+{
+    /// Returns the `remaining` candidates along with their weights, and the words worth guessing
+    /// among them.
+    fn considered(
+        &self,
+        remaining: &Candidates,
+        history: &History<N>,
+    ) -> (Vec<(usize, f64)>, Vec<usize>) {
+        let cache = &self.cache;
+        let candidates: Vec<(usize, f64)> = remaining
+            .iter()
+            .map(|index| (index, self.weights[index]))
+            .collect();
+
+        let mut guesses: Vec<usize> = candidates.iter().map(|&(index, _)| index).collect();
+        if self.pool.includes_eliminated(candidates.len()) {
+            let constraints = self.hard_mode.then(|| history.constraints());
+            let allowed = |&index: &usize| {
+                !remaining.contains(index)
+                    && constraints.is_none_or(|c| c.hard_mode_allows(cache.word(index)))
+            };
+            match &self.reduced {
+                Some(reduced) => guesses.extend(reduced.iter().copied().filter(allowed)),
+                None => guesses.extend((0..cache.len()).filter(allowed)),
+            }
+        }
+        if history.is_empty() {
+            self.opener.restrict(cache, &mut guesses);
+        }
+        (candidates, guesses)
+    }
 }
 
-impl Guesser for Naive {
-    fn guess(&mut self, history: &[Guess]) -> String {
-        if let Some(last) = history.last() {
-            self.remaining.retain(|word, _| last.matches(word));
-        }
-
-        let mut best: Option<Candidate> = None;
-        for (&word, &count) in &self.remaining {
-            let goodness = 0.0;
-            if let Some(c) = best {
-                if goodness > c.goodness {
-                    best = Some(c);
-                }
-            } else {
-                best = Some(Candidate {
-                    word,
-                    count,
-                    goodness,
-                });
+impl Default for Naive {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> Guesser<N> for Naive<N>
+where
+    Correctness: Pack<N>,
+{
+    fn guess(&mut self, history: &History<N>) -> Cow<'static, str> {
+        match (self.partition.take(), &history[self.seen..]) {
+            (Some((guess, mut buckets)), [played])
+                if self.cache.index(&played.word) == Some(guess) =>
+            {
+                let pattern = <Correctness as Pack<N>>::pack(played.mask);
+                let bucket = std::mem::take(&mut buckets[pattern.into()]);
+                self.remaining =
+                    Candidates::from_indices(self.cache.len(), bucket.into_iter().map(usize::from));
+                self.seen = history.len();
             }
+            _ => self
+                .remaining
+                .catch_up(&self.cache, history, &mut self.seen),
+        }
+        if self.remaining.is_empty() {
+            return no_candidate_left(&self.cache, history);
+        }
+
+        let cache = &self.cache;
+        let remaining = &self.remaining;
+        let (candidates, guesses) = self.considered(remaining, history);
+
+        // The goodness of a guess is the expected amount of information we gain from the pattern
+        // it reveals, weighting each candidate by how likely it is to be the answer.
+        let preference = |guess| {
+            self.tie_break.preference(
+                cache.word(guess),
+                self.counts[guess],
+                remaining.contains(guess),
+            )
+        };
+        // A guess reveals neither more than the information left, nor more than its tiles do on
+        // their own. Entropies summed in other orders may differ in the last bits, so leave some
+        // room for rounding.
+        let left = information(&candidates);
+        let tiles = Tiles::new(cache, candidates.iter().copied());
+        let bound = |guess| {
+            let most = left.min(tiles.information(guess));
+            (most * (1.0 + ROUNDING), preference(guess))
+        };
+        let (best, (goodness, _)) = best_by_bounded(&guesses, &self.cancel, bound, |guess| {
+            (entropy(cache.row(guess), &candidates), preference(guess))
+        })
+        .expect("there is always at least one remaining candidate");
+        tracing::debug!(
+            guess = cache.word(best),
+            entropy = goodness,
+            candidates = candidates.len(),
+            considered = guesses.len(),
+            "picked the guess revealing the most information"
+        );
+
+        let patterns = cache.row(best);
+        let mut buckets = vec![Vec::new(); <Correctness as Pack<N>>::PATTERNS];
+        for &(candidate, _) in &candidates {
+            // The cache holds at most `MAX_WORDS` words, so indices fit.
+            buckets[patterns[candidate].into()].push(candidate as u16);
+        }
+        self.partition = Some((best, buckets));
+
+        Cow::Borrowed(cache.word(best))
+    }
+
+    fn guess_with_explanation(
+        &mut self,
+        history: &History<N>,
+    ) -> (Cow<'static, str>, Option<Explanation<N>>) {
+        let guess = self.guess(history);
+        let weights = &self.weights;
+        let explanation = explain(&self.cache, &self.remaining, |index| weights[index], &guess);
+        (guess, Some(explanation))
+    }
+
+    fn set_hard_mode(&mut self, hard_mode: bool) {
+        // Remaining candidates always use every hint, but eliminated words might not.
+        self.hard_mode = hard_mode;
+    }
+
+    fn set_cancel_token(&mut self, token: CancelToken) {
+        self.cancel = token;
+    }
+
+    fn remaining(&self) -> Option<Vec<&'static str>> {
+        Some(self.remaining.words(&self.cache))
+    }
+
+    fn rank(&self, history: &History<N>, k: usize) -> Vec<(&'static str, f64)> {
+        let (mut remaining, mut seen) = (self.remaining.clone(), self.seen);
+        remaining.catch_up(&self.cache, history, &mut seen);
+        let (candidates, guesses) = self.considered(&remaining, history);
+        top(&self.cache, &guesses, k, |guess| {
+            entropy(self.cache.row(guess), &candidates)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::Naive;
+    use crate::algorithms::{OpenerRules, Prior, Weighting};
+    use crate::{Correctness, Error, Guess, Guesser, History, Wordle, WORDS};
+
+    #[test]
+    fn weighs_the_prior() {
+        let weighting = Weighting::parse("word,picks\nhumph,3\n", "picks")
+            .unwrap()
+            .with_unlisted(0.5);
+        let naive: Naive = Naive::with_dictionary(WORDS.iter().copied())
+            .with_weighting(Arc::new(weighting))
+            .with_prior(Prior::Uniform);
+        // The weighting is kept whatever the prior.
+        let mut expected = [0.5; 12];
+        expected[3] = 3.0;
+        assert_eq!(&naive.weights[..], expected);
+    }
+
+    #[test]
+    fn follows_the_opener_rules() {
+        let rules = OpenerRules::new(["+h".parse().unwrap(), "-s".parse().unwrap()]);
+        let mut naive: Naive =
+            Naive::with_dictionary(WORDS.iter().copied()).with_opener_rules(rules);
+        assert_eq!(naive.guess(&History::new()), "heath");
+
+        // Later guesses are free again.
+        let history = History::from(vec![Guess::new(
+            "heath",
+            Correctness::compute("blush", "heath"),
+        )]);
+        assert_eq!(naive.guess(&history), "blush");
+    }
+
+    #[test]
+    fn shares_the_counts_of_the_dictionary() {
+        let (first, second) = (Naive::new(), Naive::new());
+        assert!(Arc::ptr_eq(&first.counts, &second.counts));
+
+        let naive: Naive = Naive::with_dictionary(WORDS.iter().copied());
+        let counts: Vec<usize> = WORDS.iter().map(|&(_, count)| count).collect();
+        assert_eq!(&naive.counts[..], counts);
+        assert!(Arc::ptr_eq(
+            &naive.counts,
+            &naive.cache.dictionary().counts()
+        ));
+    }
+
+    #[test]
+    fn reuses_the_partition() {
+        let prototype: Naive = Naive::with_dictionary(WORDS.iter().copied());
+        for &(answer, _) in WORDS {
+            let mut naive = prototype.clone();
+            let first = naive.guess(&History::new());
+            let history = History::from(vec![Guess::new(
+                first.clone(),
+                Correctness::compute(answer, &first),
+            )]);
+            naive.guess(&history);
+
+            // A guesser that did not pick the first guess itself has to filter the candidates.
+            let mut fresh = prototype.clone();
+            fresh.guess(&history);
+            assert_eq!(naive.remaining, fresh.remaining);
+            assert!(naive
+                .remaining
+                .iter()
+                .any(|i| naive.cache.word(i) == answer));
+        }
+    }
+
+    #[test]
+    fn solves_six_letter_words() {
+        let words = [
+            ("bright", 3),
+            ("fright", 2),
+            ("wright", 1),
+            ("planet", 2),
+            ("sprint", 1),
+            ("cobalt", 1),
+            ("ñandus", 1),
+        ];
+        let wordle = Wordle::<6>::with_dictionary(words.iter().copied());
+        let naive = Naive::<6>::with_dictionary(words.iter().copied());
+        for &(answer, _) in &words {
+            assert!(matches!(wordle.play(answer, naive.clone()), Ok(Some(_))));
         }
 
-        best.unwrap().word.to_string()
+        assert!(matches!(
+            Naive::<5>::try_with_dictionary(words.iter().copied()),
+            Err(Error::WordLength { len: 5, .. })
+        ));
     }
 }
@@ -0,0 +1,182 @@
+use std::fmt;
+use std::str::FromStr;
+
+use super::{Pack, PatternCache};
+use crate::Correctness;
+
+/// A rule the first guess must follow, e.g. in the challenges of the community where everyone
+/// opens with a word containing some letter.
+///
+/// Rules are written `+letters` for a guess with every one of the letters, as many times as they
+/// are listed, `-letters` for a guess with none of them, e.g. those of yesterday's answer, and
+/// `letter@position` for a guess with the letter at the position, counting from 1.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OpenerRule {
+    Contains(String),
+    Excludes(String),
+    At(char, usize),
+}
+
+impl OpenerRule {
+    pub fn allows(&self, word: &str) -> bool {
+        match self {
+            OpenerRule::Contains(letters) => letters.chars().all(|letter| {
+                let wanted = letters.chars().filter(|&c| c == letter).count();
+                word.chars().filter(|&c| c == letter).count() >= wanted
+            }),
+            OpenerRule::Excludes(letters) => !word.chars().any(|letter| letters.contains(letter)),
+            OpenerRule::At(letter, position) => word.chars().nth(position - 1) == Some(*letter),
+        }
+    }
+}
+
+/// A rule that is not written like an [`OpenerRule`].
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("`{0}` is not a rule like `+q`, `-crane` or `s@1`")]
+pub struct InvalidOpenerRule(String);
+
+impl FromStr for OpenerRule {
+    type Err = InvalidOpenerRule;
+
+    fn from_str(rule: &str) -> Result<Self, Self::Err> {
+        let invalid = || InvalidOpenerRule(rule.to_string());
+        let letters = |letters: &str| {
+            let valid = !letters.is_empty() && letters.chars().all(char::is_lowercase);
+            valid.then(|| letters.to_string()).ok_or_else(invalid)
+        };
+
+        if let Some(rest) = rule.strip_prefix('+') {
+            return letters(rest).map(OpenerRule::Contains);
+        }
+        if let Some(rest) = rule.strip_prefix('-') {
+            return letters(rest).map(OpenerRule::Excludes);
+        }
+        let (letter, position) = rule.split_once('@').ok_or_else(invalid)?;
+        let mut chars = letter.chars();
+        match (chars.next(), chars.next(), position.parse()) {
+            (Some(letter), None, Ok(position)) if letter.is_lowercase() && position > 0 => {
+                Ok(OpenerRule::At(letter, position))
+            }
+            _ => Err(invalid()),
+        }
+    }
+}
+
+impl fmt::Display for OpenerRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OpenerRule::Contains(letters) => write!(f, "+{}", letters),
+            OpenerRule::Excludes(letters) => write!(f, "-{}", letters),
+            OpenerRule::At(letter, position) => write!(f, "{}@{}", letter, position),
+        }
+    }
+}
+
+/// Every rule the first guess must follow, see [`OpenerRule`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct OpenerRules(Vec<OpenerRule>);
+
+impl OpenerRules {
+    pub fn new(rules: impl IntoIterator<Item = OpenerRule>) -> Self {
+        Self(rules.into_iter().collect())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn allows(&self, word: &str) -> bool {
+        self.0.iter().all(|rule| rule.allows(word))
+    }
+
+    /// Keeps the `guesses` every rule allows, or the words of `cache` they allow if they allow
+    /// none of the guesses, e.g. because none of the candidates has a `q`.
+    ///
+    /// If no word follows the rules at all, every guess is kept.
+    pub(crate) fn restrict<const N: usize>(&self, cache: &PatternCache<N>, guesses: &mut Vec<usize>)
+    where
+        Correctness: Pack<N>,
+    {
+        if self.is_empty() {
+            return;
+        }
+
+        let allowed: Vec<usize> = guesses
+            .iter()
+            .copied()
+            .filter(|&guess| self.allows(cache.word(guess)))
+            .collect();
+        if !allowed.is_empty() {
+            *guesses = allowed;
+            return;
+        }
+
+        let allowed: Vec<usize> = (0..cache.len())
+            .filter(|&guess| self.allows(cache.word(guess)))
+            .collect();
+        if allowed.is_empty() {
+            tracing::warn!(rules = %self, "no word follows the rules of the first guess");
+        } else {
+            *guesses = allowed;
+        }
+    }
+}
+
+impl fmt::Display for OpenerRules {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, rule) in self.0.iter().enumerate() {
+            if i > 0 {
+                f.write_str(",")?;
+            }
+            write!(f, "{}", rule)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{OpenerRule, OpenerRules};
+    use crate::algorithms::PatternCache;
+
+    #[test]
+    fn parse() {
+        assert_eq!("+q".parse(), Ok(OpenerRule::Contains("q".to_string())));
+        assert_eq!(
+            "-crane".parse(),
+            Ok(OpenerRule::Excludes("crane".to_string()))
+        );
+        assert_eq!("s@1".parse(), Ok(OpenerRule::At('s', 1)));
+        for invalid in ["", "+", "-Q", "q", "s@0", "s@", "st@1", "@1"] {
+            assert!(invalid.parse::<OpenerRule>().is_err(), "{}", invalid);
+        }
+        for rule in ["+ee", "-crane", "s@1"] {
+            assert_eq!(rule.parse::<OpenerRule>().unwrap().to_string(), rule);
+        }
+    }
+
+    #[test]
+    fn allows() {
+        let rules = OpenerRules::new(["+ee".parse().unwrap(), "-r".parse().unwrap()]);
+        assert!(rules.allows("evade"));
+        assert!(!rules.allows("serve"));
+        assert!(!rules.allows("awake"));
+        assert!(OpenerRule::At('h', 5).allows("heath"));
+        assert!(!OpenerRule::At('h', 6).allows("heath"));
+    }
+
+    #[test]
+    fn restrict() {
+        let cache: PatternCache = PatternCache::new(["cigar", "quiet", "rebut", "heath"]);
+        let rules = OpenerRules::new(["+q".parse().unwrap()]);
+
+        // None of the guesses has a `q`, so another word is.
+        let mut guesses = vec![0, 2];
+        rules.restrict(&cache, &mut guesses);
+        assert_eq!(guesses, [1]);
+
+        let impossible = OpenerRules::new(["+z".parse().unwrap()]);
+        impossible.restrict(&cache, &mut guesses);
+        assert_eq!(guesses, [1]);
+    }
+}
@@ -0,0 +1,266 @@
+use std::borrow::Cow;
+#[cfg(feature = "std")]
+use std::collections::BTreeMap;
+#[cfg(feature = "std")]
+use std::path::{Path, PathBuf};
+#[cfg(feature = "std")]
+use std::{fs, io};
+
+use crate::{CancelToken, Estimate, Explanation, Guesser, History};
+
+/// Plays a known first guess, and only asks the wrapped guesser from the second guess on.
+///
+/// The best opening never changes for a given dictionary and algorithm, yet it is by far the most
+/// expensive guess to compute, so it is worth computing once and reusing across games.
+#[derive(Clone)]
+pub struct Opening<G> {
+    inner: G,
+    first: Cow<'static, str>,
+}
+
+impl<G> Opening<G> {
+    pub fn new(inner: G, first: impl Into<Cow<'static, str>>) -> Self {
+        Self {
+            inner,
+            first: first.into(),
+        }
+    }
+}
+
+impl<G: Guesser<N>, const N: usize> Guesser<N> for Opening<G> {
+    fn guess(&mut self, history: &History<N>) -> Cow<'static, str> {
+        if history.is_empty() {
+            return self.first.clone();
+        }
+
+        self.inner.guess(history)
+    }
+
+    fn guess_with_explanation(
+        &mut self,
+        history: &History<N>,
+    ) -> (Cow<'static, str>, Option<Explanation<N>>) {
+        if history.is_empty() {
+            return (self.first.clone(), None);
+        }
+
+        self.inner.guess_with_explanation(history)
+    }
+
+    fn set_hard_mode(&mut self, hard_mode: bool) {
+        self.inner.set_hard_mode(hard_mode)
+    }
+
+    fn set_cancel_token(&mut self, token: CancelToken) {
+        self.inner.set_cancel_token(token)
+    }
+
+    fn remaining(&self) -> Option<Vec<&'static str>> {
+        self.inner.remaining()
+    }
+
+    fn rank(&self, history: &History<N>, k: usize) -> Vec<(&'static str, f64)> {
+        self.inner.rank(history, k)
+    }
+
+    fn estimate(&self, history: &History<N>) -> Option<Estimate> {
+        self.inner.estimate(history)
+    }
+}
+
+/// Always opens with the same guesses, in order, and only asks the wrapped guesser once they were
+/// all played, e.g. to evaluate a favorite combination of openers.
+///
+/// The openers are played even if they cannot be the answer, or are not allowed in hard mode.
+#[derive(Clone)]
+pub struct FixedOpening<G> {
+    inner: G,
+    openers: Vec<Cow<'static, str>>,
+}
+
+impl<G> FixedOpening<G> {
+    pub fn new<W: Into<Cow<'static, str>>>(inner: G, openers: impl IntoIterator<Item = W>) -> Self {
+        Self {
+            inner,
+            openers: openers.into_iter().map(Into::into).collect(),
+        }
+    }
+
+    /// Returns the guesses the game opens with, in order.
+    pub fn openers(&self) -> &[Cow<'static, str>] {
+        &self.openers
+    }
+}
+
+impl<G: Guesser<N>, const N: usize> Guesser<N> for FixedOpening<G> {
+    fn guess(&mut self, history: &History<N>) -> Cow<'static, str> {
+        match self.openers.get(history.len()) {
+            Some(opener) => opener.clone(),
+            None => self.inner.guess(history),
+        }
+    }
+
+    fn guess_with_explanation(
+        &mut self,
+        history: &History<N>,
+    ) -> (Cow<'static, str>, Option<Explanation<N>>) {
+        match self.openers.get(history.len()) {
+            Some(opener) => (opener.clone(), None),
+            None => self.inner.guess_with_explanation(history),
+        }
+    }
+
+    fn set_hard_mode(&mut self, hard_mode: bool) {
+        self.inner.set_hard_mode(hard_mode)
+    }
+
+    fn set_cancel_token(&mut self, token: CancelToken) {
+        self.inner.set_cancel_token(token)
+    }
+
+    fn remaining(&self) -> Option<Vec<&'static str>> {
+        self.inner.remaining()
+    }
+
+    fn rank(&self, history: &History<N>, k: usize) -> Vec<(&'static str, f64)> {
+        self.inner.rank(history, k)
+    }
+
+    fn estimate(&self, history: &History<N>) -> Option<Estimate> {
+        self.inner.estimate(history)
+    }
+}
+
+/// First guesses persisted to disk, keyed by a description of the algorithm and dictionary they
+/// were computed for (see [`fingerprint`]).
+#[cfg(feature = "std")]
+#[derive(Debug, Clone)]
+pub struct OpeningCache {
+    path: PathBuf,
+    openings: BTreeMap<String, String>,
+}
+
+#[cfg(feature = "std")]
+impl OpeningCache {
+    /// Reads the cache stored at `path`, or starts an empty one if there is no such file.
+    pub fn load(path: impl Into<PathBuf>) -> io::Result<Self> {
+        let path = path.into();
+        let openings = match fs::read_to_string(&path) {
+            Ok(contents) => serde_json::from_str(&contents)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => BTreeMap::new(),
+            Err(e) => return Err(e),
+        };
+
+        Ok(Self { path, openings })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.openings.get(key).map(String::as_str)
+    }
+
+    pub fn insert(&mut self, key: impl Into<String>, first: impl Into<String>) {
+        self.openings.insert(key.into(), first.into());
+    }
+
+    /// Returns the first guess cached for `key`, computing it by asking `guesser` and storing it
+    /// in the cache if there is none.
+    pub fn get_or_compute<G: Guesser<N>, const N: usize>(
+        &mut self,
+        key: &str,
+        mut guesser: G,
+    ) -> Cow<'static, str> {
+        if let Some(first) = self.get(key) {
+            return Cow::Owned(first.to_string());
+        }
+
+        let first = guesser.guess(&History::new());
+        self.insert(key, first.clone());
+        first
+    }
+
+    /// Writes the cache back to the file it was loaded from.
+    pub fn save(&self) -> io::Result<()> {
+        let contents =
+            serde_json::to_string_pretty(&self.openings).expect("maps of strings are serializable");
+        fs::write(&self.path, contents)
+    }
+}
+
+/// Returns a hash of a dictionary that is stable across runs and platforms, to tell apart first
+/// guesses computed for different dictionaries.
+pub fn fingerprint<'a>(words: impl IntoIterator<Item = (&'a str, usize)>) -> u64 {
+    crate::fnv1a(words.into_iter().flat_map(|(word, count)| {
+        word.bytes()
+            .chain(std::iter::once(b' '))
+            .chain(count.to_le_bytes())
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{fingerprint, FixedOpening, Opening, OpeningCache};
+    use crate::algorithms::Naive;
+    use crate::Wordle;
+
+    #[test]
+    fn plays_the_opening() {
+        let w = Wordle::new();
+        let guesser = guesser!(|history| {
+            assert!(!history.is_empty(), "the opening is never asked for");
+            "right".into()
+        });
+        assert_eq!(w.play("right", Opening::new(guesser, "wrong")), Ok(Some(2)));
+    }
+
+    #[test]
+    fn plays_every_opener_first() {
+        let words = [
+            ("crane", 1),
+            ("moist", 1),
+            ("fight", 1),
+            ("light", 1),
+            ("night", 1),
+        ];
+        let w: Wordle = Wordle::with_dictionary(words.iter().copied());
+        let naive = Naive::with_dictionary(words.iter().copied());
+        let guesser = FixedOpening::new(naive, vec!["crane", "moist"]);
+        let transcript = w.play_transcript("light", guesser.clone()).unwrap();
+        let words: Vec<&str> = transcript.guesses.iter().map(|g| &*g.word).collect();
+        assert_eq!(words[..2], ["crane", "moist"]);
+        assert_eq!(words.last(), Some(&"light"));
+
+        // The game is over as soon as an opener is the answer.
+        let transcript = w.play_transcript("crane", guesser).unwrap();
+        assert_eq!(transcript.score, Some(1));
+    }
+
+    #[test]
+    fn fingerprint_depends_on_counts() {
+        let a = fingerprint(vec![("right", 1), ("wrong", 2)]);
+        let b = fingerprint(vec![("right", 1), ("wrong", 3)]);
+        assert_ne!(a, b);
+        assert_eq!(a, fingerprint(vec![("right", 1), ("wrong", 2)]));
+    }
+
+    #[test]
+    fn round_trip() {
+        let path = std::env::temp_dir().join("crane-opening-cache-test.json");
+        let _ = std::fs::remove_file(&path);
+
+        let mut cache = OpeningCache::load(&path).unwrap();
+        assert_eq!(cache.get("naive"), None);
+        let first = cache.get_or_compute("naive", guesser!(|_history| { "right".into() }));
+        assert_eq!(first, "right");
+        cache.save().unwrap();
+
+        let mut cache = OpeningCache::load(&path).unwrap();
+        assert_eq!(cache.get("naive"), Some("right"));
+        let first = cache.get_or_compute("naive", guesser!(|_history| { unreachable!() }));
+        assert_eq!(first, "right");
+    }
+}
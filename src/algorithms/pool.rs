@@ -0,0 +1,45 @@
+/// Which words a guesser considers playing.
+///
+/// Only guessing words that could still be the answer gives a chance to win on every turn, but
+/// a word that was already ruled out often splits the remaining candidates much better.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub enum GuessPool {
+    /// Only words that could still be the answer.
+    #[default]
+    Remaining,
+    /// Every word of the dictionary.
+    Dictionary,
+    /// Every word of the dictionary while more than `threshold` candidates remain, and only the
+    /// candidates once few enough are left to go for the win.
+    Hybrid { threshold: usize },
+}
+
+impl GuessPool {
+    /// A hybrid pool that goes for the win once three candidates or fewer remain.
+    pub fn hybrid() -> Self {
+        GuessPool::Hybrid { threshold: 3 }
+    }
+
+    /// Returns whether words that were ruled out are worth guessing when `remaining` candidates
+    /// are left.
+    pub fn includes_eliminated(&self, remaining: usize) -> bool {
+        match *self {
+            GuessPool::Remaining => false,
+            GuessPool::Dictionary => true,
+            GuessPool::Hybrid { threshold } => remaining > threshold,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::GuessPool;
+
+    #[test]
+    fn includes_eliminated() {
+        assert!(!GuessPool::Remaining.includes_eliminated(100));
+        assert!(GuessPool::Dictionary.includes_eliminated(1));
+        assert!(GuessPool::hybrid().includes_eliminated(4));
+        assert!(!GuessPool::hybrid().includes_eliminated(3));
+    }
+}
@@ -0,0 +1,246 @@
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use super::{
+    best_by, explain, no_candidate_left, Candidates, GuessPool, OpenerRules, PatternCache, TieBreak,
+};
+use crate::keyboard::Keyboard;
+use crate::{CancelToken, Dictionary, Explanation, Guesser, History};
+
+/// Picks the candidate whose letters are the most common at their positions among the remaining
+/// candidates.
+///
+/// This is the classic heuristic of scoring words by letter frequencies: it never looks at the
+/// patterns a guess would reveal, so it is much faster than the algorithms that do, at the cost of
+/// a few more guesses.
+///
+/// Words that were ruled out may be considered too, see [`Positional::with_pool`], in which case
+/// only the letters that tell some candidates apart score, and words with a letter the answer is
+/// known not to have are skipped.
+#[derive(Clone)]
+pub struct Positional {
+    remaining: Candidates,
+    /// Number of guesses of the history `remaining` was narrowed down with.
+    seen: usize,
+    /// Frequency count of every word, indexed like the pattern cache.
+    counts: Arc<[usize]>,
+    pool: GuessPool,
+    opener: OpenerRules,
+    tie_break: TieBreak,
+    cancel: CancelToken,
+    hard_mode: bool,
+    cache: Arc<PatternCache>,
+}
+
+impl Positional {
+    /// Creates a guesser that picks its words from the built-in dictionary.
+    pub fn new() -> Self {
+        Self::from_cache(PatternCache::global())
+    }
+
+    /// Creates a guesser that picks its words from the given dictionary, with a new
+    /// [`PatternCache`] for it.
+    pub fn with_dictionary(words: impl IntoIterator<Item = (&'static str, usize)>) -> Self {
+        let dictionary = Arc::new(Dictionary::with_counts(words));
+        Self::from_cache(Arc::new(PatternCache::for_dictionary(dictionary)))
+    }
+
+    /// Creates a guesser that picks its words from the dictionary of `cache`, with the frequency
+    /// counts it was built with, rather than reading the word list again.
+    pub fn from_cache(cache: Arc<PatternCache>) -> Self {
+        Self::with_counts(cache.dictionary().counts(), cache)
+    }
+
+    /// Creates a guesser that picks its words from the given dictionary, looking their patterns
+    /// up in `cache`, e.g. one loaded with [`PatternCache::load`].
+    ///
+    /// `words` must be in the same order as in `cache`, or this fails with
+    /// [`Error::CacheMismatch`](crate::Error::CacheMismatch) if there are not as many.
+    pub fn with_cache(
+        words: impl IntoIterator<Item = (&'static str, usize)>,
+        cache: Arc<PatternCache>,
+    ) -> crate::Result<Self> {
+        let counts = cache.counts_of(words)?;
+        Ok(Self::with_counts(counts, cache))
+    }
+
+    fn with_counts(counts: Arc<[usize]>, cache: Arc<PatternCache>) -> Self {
+        Positional {
+            remaining: Candidates::all(counts.len()),
+            seen: 0,
+            counts,
+            pool: GuessPool::default(),
+            opener: OpenerRules::default(),
+            tie_break: TieBreak::default(),
+            cancel: CancelToken::new(),
+            hard_mode: false,
+            cache,
+        }
+    }
+
+    /// Sets which words are considered for guessing. Defaults to [`GuessPool::Remaining`].
+    pub fn with_pool(mut self, pool: GuessPool) -> Self {
+        self.pool = pool;
+        self
+    }
+
+    /// Only considers the guesses that follow `rules` for the first guess.
+    pub fn with_opener_rules(mut self, rules: OpenerRules) -> Self {
+        self.opener = rules;
+        self
+    }
+
+    /// Sets how to pick among guesses that score the same. Defaults to
+    /// [`TieBreak::PossibleAnswer`].
+    pub fn with_tie_break(mut self, tie_break: TieBreak) -> Self {
+        self.tie_break = tie_break;
+        self
+    }
+
+    /// Only considers the given words as possible answers, see [`Candidates::retain_words`].
+    pub fn with_answers<'a>(mut self, answers: impl IntoIterator<Item = &'a str>) -> Self {
+        self.remaining.retain_words(&self.cache, answers);
+        self
+    }
+}
+
+impl Default for Positional {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Guesser for Positional {
+    fn guess(&mut self, history: &History) -> Cow<'static, str> {
+        self.remaining
+            .catch_up(&self.cache, history, &mut self.seen);
+        if self.remaining.is_empty() {
+            return no_candidate_left(&self.cache, history);
+        }
+
+        let cache = &self.cache;
+        let remaining = &self.remaining;
+        let candidates: Vec<usize> = remaining.iter().collect();
+
+        // How many candidates have every letter at every position. A letter every candidate has
+        // at its position tells none of them apart, which makes no difference between candidates
+        // but keeps the words that were ruled out from scoring with the letters already found.
+        let mut frequencies: HashMap<(usize, char), usize> = HashMap::new();
+        for &candidate in &candidates {
+            for letter in cache.word(candidate).chars().enumerate() {
+                *frequencies.entry(letter).or_default() += 1;
+            }
+        }
+        frequencies.retain(|_, &mut frequency| frequency < candidates.len());
+
+        // Once a single candidate is left, nothing scores, yet only the candidate may win.
+        let mut guesses = candidates.clone();
+        if candidates.len() > 1 && self.pool.includes_eliminated(candidates.len()) {
+            let keyboard = Keyboard::from_history(history);
+            let constraints = self.hard_mode.then(|| history.constraints());
+            guesses.extend((0..cache.len()).filter(|&index| {
+                let word = cache.word(index);
+                !remaining.contains(index)
+                    && !word.chars().any(|letter| keyboard.is_absent(letter))
+                    && constraints.is_none_or(|c| c.hard_mode_allows(word))
+            }));
+        }
+        if history.is_empty() {
+            self.opener.restrict(cache, &mut guesses);
+        }
+
+        let (best, (score, _)) = best_by(&guesses, &self.cancel, |guess| {
+            let score: usize = cache
+                .word(guess)
+                .chars()
+                .enumerate()
+                .map(|letter| frequencies.get(&letter).copied().unwrap_or(0))
+                .sum();
+            let preference = self.tie_break.preference(
+                cache.word(guess),
+                self.counts[guess],
+                remaining.contains(guess),
+            );
+            (score, preference)
+        })
+        .expect("there is always at least one remaining candidate");
+        tracing::debug!(
+            guess = cache.word(best),
+            score,
+            candidates = candidates.len(),
+            considered = guesses.len(),
+            "picked the guess with the most common letters"
+        );
+
+        Cow::Borrowed(cache.word(best))
+    }
+
+    fn guess_with_explanation(
+        &mut self,
+        history: &History,
+    ) -> (Cow<'static, str>, Option<Explanation>) {
+        let guess = self.guess(history);
+        let explanation = explain(&self.cache, &self.remaining, |_| 1.0, &guess);
+        (guess, Some(explanation))
+    }
+
+    fn set_hard_mode(&mut self, hard_mode: bool) {
+        // Remaining candidates always use every hint, but eliminated words might not.
+        self.hard_mode = hard_mode;
+    }
+
+    fn set_cancel_token(&mut self, token: CancelToken) {
+        self.cancel = token;
+    }
+
+    fn remaining(&self) -> Option<Vec<&'static str>> {
+        Some(self.remaining.words(&self.cache))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Positional;
+    use crate::algorithms::{GuessPool, TieBreak};
+    use crate::{Guess, Guesser, History};
+
+    #[test]
+    fn picks_the_most_common_letters() {
+        // "sores" has the most common letter at every position.
+        let words = [
+            ("cares", 1),
+            ("cores", 1),
+            ("bores", 1),
+            ("sores", 1),
+            ("sorts", 1),
+            ("soars", 1),
+        ];
+        let mut guesser = Positional::with_dictionary(words.iter().copied());
+        assert_eq!(guesser.guess(&History::new()), "sores");
+    }
+
+    #[test]
+    fn probes_with_letters_left_to_try() {
+        // No answer has the most common letters at the first three positions together, but
+        // "bakes" and "bakeg" do, and "g" is known not to be in the answer.
+        let words = [
+            ("bares", 1),
+            ("bades", 1),
+            ("bates", 1),
+            ("cokes", 1),
+            ("dokes", 1),
+            ("bakes", 1),
+            ("bakeg", 1),
+        ];
+        let answers = ["bares", "bades", "bates", "cokes", "dokes"];
+        let history = History::from(vec![Guess::new("gumpy", mask!(W W W W W))]);
+        let positional = Positional::with_dictionary(words.iter().copied())
+            .with_answers(answers)
+            .with_tie_break(TieBreak::Alphabetical);
+
+        assert_eq!(positional.clone().guess(&history), "bades");
+        let mut probing = positional.with_pool(GuessPool::Dictionary);
+        assert_eq!(probing.guess(&history), "bakes");
+    }
+}
@@ -0,0 +1,85 @@
+/// How likely each word of the dictionary is considered to be the answer, based on how frequently
+/// it is used.
+///
+/// The raw frequency counts make rare but valid answers almost impossible, whereas a uniform prior
+/// plans for the worst case where every word is as likely as any other.
+#[derive(Debug, Copy, Clone, PartialEq, Default)]
+pub enum Prior {
+    /// Every word is equally likely.
+    Uniform,
+    /// Words are as likely as their frequency count.
+    #[default]
+    Counts,
+    /// Words are ranked by frequency count, and their likelihood falls off along a sigmoid over
+    /// that rank: the `cutoff` most common words are all likely answers, the rest much less so.
+    Sigmoid {
+        /// Rank at which a word is considered half as likely as the most common words.
+        cutoff: usize,
+        /// Number of ranks over which the likelihood drops, larger values giving a softer slope.
+        width: f64,
+    },
+}
+
+impl Prior {
+    /// A sigmoid prior tuned for the built-in dictionary, where the answers are a few thousand
+    /// common words out of more than ten thousand valid guesses.
+    pub fn sigmoid() -> Self {
+        Prior::Sigmoid {
+            cutoff: 3000,
+            width: 300.0,
+        }
+    }
+
+    /// Returns the (unnormalized) likelihood of each word given the frequency counts of every
+    /// word in the dictionary.
+    pub fn weights(&self, counts: &[usize]) -> Vec<f64> {
+        match *self {
+            Prior::Uniform => vec![1.0; counts.len()],
+            Prior::Counts => counts.iter().map(|&count| count as f64).collect(),
+            Prior::Sigmoid { cutoff, width } => {
+                let mut sorted = counts.to_vec();
+                sorted.sort_unstable_by(|a, b| b.cmp(a));
+
+                counts
+                    .iter()
+                    .map(|count| {
+                        // Words with the same count share the same rank.
+                        let rank = sorted.partition_point(|c| c > count);
+                        let x = (rank as f64 - cutoff as f64) / width;
+                        1.0 / (1.0 + x.exp())
+                    })
+                    .collect()
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Prior;
+
+    #[test]
+    fn uniform() {
+        assert_eq!(Prior::Uniform.weights(&[1, 100, 0]), vec![1.0, 1.0, 1.0]);
+    }
+
+    #[test]
+    fn counts() {
+        assert_eq!(Prior::Counts.weights(&[1, 100, 0]), vec![1.0, 100.0, 0.0]);
+    }
+
+    #[test]
+    fn sigmoid() {
+        let prior = Prior::Sigmoid {
+            cutoff: 2,
+            width: 0.5,
+        };
+        let weights = prior.weights(&[50, 1000, 10, 50, 1]);
+        // Ranks are 1, 0, 3, 1 and 4.
+        assert!(weights[1] > 0.95);
+        assert_eq!(weights[0], weights[3]);
+        assert!(weights[0] > 0.8);
+        assert!(weights[2] < 0.2);
+        assert!(weights[4] < 0.05);
+    }
+}
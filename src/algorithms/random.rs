@@ -0,0 +1,116 @@
+use std::borrow::Cow;
+use std::sync::Arc;
+
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+use super::{no_candidate_left, Candidates, PatternCache};
+use crate::{Guesser, History};
+
+/// Picks a remaining candidate uniformly at random, as a baseline for smarter algorithms.
+///
+/// The random number generator is seeded by the caller, so runs are reproducible. Clones share the
+/// state of the generator at the time they were made, so every game played by a clone of the same
+/// prototype draws the same numbers.
+#[derive(Clone)]
+pub struct Random {
+    remaining: Candidates,
+    /// Number of guesses of the history `remaining` was narrowed down with.
+    seen: usize,
+    cache: Arc<PatternCache>,
+    rng: ChaCha8Rng,
+}
+
+impl Random {
+    /// Creates a guesser that picks its words from the built-in dictionary.
+    pub fn new() -> Self {
+        Self::with_cache(PatternCache::global())
+    }
+
+    /// Creates a guesser that picks its words from the given dictionary, with a new
+    /// [`PatternCache`] for it.
+    pub fn with_dictionary(words: impl IntoIterator<Item = (&'static str, usize)>) -> Self {
+        Self::with_cache(Arc::new(PatternCache::new(
+            words.into_iter().map(|(word, _)| word),
+        )))
+    }
+
+    /// Creates a guesser that picks its words from those of `cache`, e.g. one loaded with
+    /// [`PatternCache::load`].
+    pub fn with_cache(cache: Arc<PatternCache>) -> Self {
+        Random {
+            remaining: Candidates::all(cache.len()),
+            seen: 0,
+            cache,
+            rng: ChaCha8Rng::seed_from_u64(0),
+        }
+    }
+
+    /// Seeds the random number generator. Defaults to 0.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.rng = ChaCha8Rng::seed_from_u64(seed);
+        self
+    }
+
+    /// Only considers the given words as possible answers, see [`Candidates::retain_words`].
+    pub fn with_answers<'a>(mut self, answers: impl IntoIterator<Item = &'a str>) -> Self {
+        self.remaining.retain_words(&self.cache, answers);
+        self
+    }
+}
+
+impl Default for Random {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Guesser for Random {
+    fn guess(&mut self, history: &History) -> Cow<'static, str> {
+        self.remaining
+            .catch_up(&self.cache, history, &mut self.seen);
+        if self.remaining.is_empty() {
+            return no_candidate_left(&self.cache, history);
+        }
+
+        let pick = self.rng.random_range(0..self.remaining.len());
+        let index = self
+            .remaining
+            .iter()
+            .nth(pick)
+            .expect("there is always at least one remaining candidate");
+        tracing::debug!(
+            guess = self.cache.word(index),
+            candidates = self.remaining.len(),
+            "picked a random candidate"
+        );
+
+        Cow::Borrowed(self.cache.word(index))
+    }
+
+    fn remaining(&self) -> Option<Vec<&'static str>> {
+        Some(self.remaining.words(&self.cache))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Random;
+    use crate::{Wordle, WORDS};
+
+    #[test]
+    fn reproducible() {
+        let wordle = Wordle::with_dictionary(WORDS.iter().copied());
+        let play = |seed| {
+            let random = Random::with_dictionary(WORDS.iter().copied()).with_seed(seed);
+            WORDS
+                .iter()
+                .map(|&(answer, _)| wordle.play_transcript(answer, random.clone()).unwrap())
+                .collect::<Vec<_>>()
+        };
+
+        let transcripts = play(42);
+        assert!(transcripts.iter().all(|t| t.score.is_some()));
+        assert_eq!(transcripts, play(42));
+    }
+}
@@ -0,0 +1,263 @@
+#[cfg(feature = "std")]
+use std::path::Path;
+#[cfg(feature = "std")]
+use std::{fs, io};
+
+use serde::{Deserialize, Serialize};
+
+use super::naive::ROUNDING;
+use super::{best_by_bounded, entropy, information, PatternCache, Tiles};
+#[cfg(feature = "std")]
+use crate::Dictionary;
+use crate::{CancelToken, Correctness};
+
+/// A few words of the dictionary whose best guess reveals nearly as much information as the best
+/// guess out of the whole dictionary, so that guessers only score those among the words that were
+/// ruled out, see [`Naive::with_reduced_pool`](super::Naive::with_reduced_pool).
+///
+/// The pool is profiled on the turns the guesses revealing the most information lead to: from
+/// every candidate, to the candidates left by every mask of the best guess, and so on while more
+/// than two candidates remain. Words are then picked greedily to come within `epsilon` bits of the
+/// best guess at as many of those turns as possible, the turns more candidates go through first.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReducedPool {
+    /// The fingerprint of the dictionary the pool was built for, see [`Dictionary::fingerprint`].
+    dictionary: u64,
+    epsilon: f64,
+    /// At how many of the profiled turns a candidate or a word of the pool comes within `epsilon`
+    /// bits of the best guess, out of how many.
+    covered: (usize, usize),
+    /// In the order they were picked, the most useful first.
+    words: Vec<String>,
+}
+
+/// A profiled turn that no candidate comes close enough to the best guess at.
+struct Turn {
+    /// The number of candidates left at this turn.
+    weight: usize,
+    /// The guesses that come within `epsilon` bits of the best one.
+    close: Vec<usize>,
+}
+
+impl ReducedPool {
+    /// Profiles the words of `cache` from the `candidates`, each given along with its likelihood
+    /// of being the answer, and picks at most `size` of them that come within `epsilon` bits of
+    /// the best guess at the profiled turns.
+    ///
+    /// This scores every word of the dictionary at every profiled turn, so it takes a while with
+    /// large dictionaries.
+    pub fn build(
+        cache: &PatternCache,
+        candidates: &[(usize, f64)],
+        size: usize,
+        epsilon: f64,
+    ) -> Self {
+        let guesses: Vec<usize> = (0..cache.len()).collect();
+        let win = Correctness::pack([Correctness::Correct; 5]);
+        let cancel = CancelToken::new();
+
+        let mut turns = Vec::new();
+        let mut profiled = 0;
+        let mut pending = vec![candidates.to_vec()];
+        while let Some(candidates) = pending.pop() {
+            if candidates.len() <= 2 {
+                continue;
+            }
+            profiled += 1;
+
+            // The same bound as the naive guesser's: no guess reveals more than the information
+            // left, nor more than its tiles do on their own.
+            let left = information(&candidates);
+            let tiles = Tiles::new(cache, candidates.iter().copied());
+            let bound = |guess| left.min(tiles.information(guess)) * (1.0 + ROUNDING);
+            let score = |guess| entropy(cache.row(guess), &candidates);
+            let (best, most) = best_by_bounded(&guesses, &cancel, bound, score)
+                .expect("the dictionary is not empty");
+
+            let threshold = most * (1.0 - ROUNDING) - epsilon;
+            let close: Vec<usize> = guesses
+                .iter()
+                .copied()
+                .filter(|&guess| bound(guess) >= threshold && score(guess) >= threshold)
+                .collect();
+            let candidate_is_close = candidates
+                .iter()
+                .any(|&(candidate, _)| close.binary_search(&candidate).is_ok());
+            if !candidate_is_close {
+                turns.push(Turn {
+                    weight: candidates.len(),
+                    close,
+                });
+            }
+
+            let patterns = cache.row(best);
+            let mut buckets = vec![Vec::new(); Correctness::PATTERNS];
+            for &(candidate, weight) in &candidates {
+                buckets[patterns[candidate] as usize].push((candidate, weight));
+            }
+            buckets.swap_remove(win as usize);
+            pending.extend(buckets);
+        }
+        tracing::debug!(
+            turns = profiled,
+            uncovered = turns.len(),
+            "profiled the best guesses"
+        );
+
+        // Every word is worth as many candidates as the turns it comes close at and that no word
+        // picked so far does.
+        let mut worth = vec![0; cache.len()];
+        let mut turns_of = vec![Vec::new(); cache.len()];
+        for (i, turn) in turns.iter().enumerate() {
+            for &guess in &turn.close {
+                worth[guess] += turn.weight;
+                turns_of[guess].push(i);
+            }
+        }
+        let mut covered = vec![false; turns.len()];
+        let mut words = Vec::new();
+        while words.len() < size {
+            let Some((pick, _)) = worth
+                .iter()
+                .enumerate()
+                .filter(|&(_, &worth)| worth > 0)
+                .min_by_key(|&(guess, &worth)| (std::cmp::Reverse(worth), guess))
+            else {
+                break;
+            };
+            words.push(cache.word(pick).to_string());
+            for &i in &turns_of[pick] {
+                if !std::mem::replace(&mut covered[i], true) {
+                    for &guess in &turns[i].close {
+                        worth[guess] -= turns[i].weight;
+                    }
+                }
+            }
+        }
+
+        let uncovered = covered.iter().filter(|&&covered| !covered).count();
+        Self {
+            dictionary: cache.dictionary().fingerprint(),
+            epsilon,
+            covered: (profiled - uncovered, profiled),
+            words,
+        }
+    }
+
+    /// Reads a pool saved with [`ReducedPool::save`].
+    ///
+    /// Fails if the pool was built for another dictionary than `dictionary`, in which case it
+    /// should be built again.
+    #[cfg(feature = "std")]
+    pub fn load(path: impl AsRef<Path>, dictionary: &Dictionary) -> io::Result<Self> {
+        let contents = fs::read_to_string(path)?;
+        let pool: Self = serde_json::from_str(&contents)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        if pool.dictionary != dictionary.fingerprint() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "the reduced pool was built for another word list",
+            ));
+        }
+        Ok(pool)
+    }
+
+    #[cfg(feature = "std")]
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let contents = serde_json::to_string_pretty(self).expect("pools are serializable");
+        fs::write(path, contents)
+    }
+
+    /// Returns the words of the pool, the most useful first.
+    pub fn words(&self) -> impl Iterator<Item = &str> {
+        self.words.iter().map(String::as_str)
+    }
+
+    pub fn len(&self) -> usize {
+        self.words.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+
+    /// Returns how many bits of information the words may miss out on at the profiled turns.
+    pub fn epsilon(&self) -> f64 {
+        self.epsilon
+    }
+
+    /// Returns at how many of the profiled turns a candidate or a word of the pool comes within
+    /// [`ReducedPool::epsilon`] bits of the best guess, out of how many turns were profiled.
+    pub fn covered(&self) -> (usize, usize) {
+        self.covered
+    }
+
+    /// Returns a hash of the words that is stable across runs and platforms, to tell apart
+    /// guesses computed with different pools.
+    pub fn fingerprint(&self) -> u64 {
+        crate::fnv1a(
+            self.words
+                .iter()
+                .flat_map(|word| word.bytes().chain(std::iter::once(b'\n'))),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::ReducedPool;
+    use crate::algorithms::{GuessPool, Naive, PatternCache};
+    use crate::{Dictionary, Guesser, History, WORDS};
+
+    const ANSWERS: &[&str] = &["cigar", "sissy", "awake", "focal", "naval", "heath"];
+
+    fn cache() -> Arc<PatternCache> {
+        let dictionary = Dictionary::with_counts(WORDS.iter().copied());
+        Arc::new(PatternCache::for_dictionary(Arc::new(dictionary)))
+    }
+
+    fn build(cache: &PatternCache, size: usize) -> ReducedPool {
+        let candidates: Vec<(usize, f64)> = ANSWERS
+            .iter()
+            .map(|answer| cache.index(answer).unwrap())
+            .map(|index| (index, WORDS[index].1 as f64))
+            .collect();
+        ReducedPool::build(cache, &candidates, size, 0.0)
+    }
+
+    #[test]
+    fn comes_close_to_the_best_guess() {
+        let cache = cache();
+        let pool = build(&cache, usize::MAX);
+        assert!(pool.len() < WORDS.len() - ANSWERS.len());
+        let (covered, turns) = pool.covered();
+        assert!(turns > 0);
+        assert_eq!(covered, turns);
+
+        let naive = || {
+            Naive::from_cache(cache.clone())
+                .with_answers(ANSWERS.iter().copied())
+                .with_pool(GuessPool::Dictionary)
+        };
+        let best = naive().rank(&History::new(), 1)[0].1;
+        let reduced = naive().with_reduced_pool(&pool).rank(&History::new(), 1)[0].1;
+        assert!((best - reduced).abs() < 1e-9);
+
+        assert!(build(&cache, 0).is_empty());
+    }
+
+    #[test]
+    fn round_trips_for_the_same_dictionary() {
+        let cache = cache();
+        let pool = build(&cache, usize::MAX);
+        let path = std::env::temp_dir().join("crane-reduced-pool-test.json");
+        pool.save(&path).unwrap();
+
+        assert_eq!(ReducedPool::load(&path, cache.dictionary()).unwrap(), pool);
+        let other = Dictionary::new(WORDS[1..].iter().map(|&(word, _)| word));
+        assert!(ReducedPool::load(&path, &other).is_err());
+        std::fs::remove_file(path).unwrap();
+    }
+}
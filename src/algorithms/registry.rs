@@ -0,0 +1,363 @@
+use std::sync::Arc;
+
+use super::{
+    Crossover, Ensemble, ExpectedSize, GuessPool, Minimax, Naive, OpenerRules, PatternCache,
+    Positional, Prior, Random, ReducedPool, TieBreak, TwoPly, Weighting,
+};
+use crate::{builtin_dictionary, Guesser};
+
+/// Creates a fresh guesser for every game, from any thread.
+pub type Factory = Box<dyn Fn() -> Box<dyn Guesser> + Send + Sync>;
+
+/// Settings for building any algorithm of the [`registry`]. Algorithms ignore the settings that do
+/// not apply to them.
+#[derive(Debug, Clone, Default)]
+pub struct Settings {
+    /// The dictionary to pick guesses from, or the built-in one if `None`.
+    pub dictionary: Option<Vec<(&'static str, usize)>>,
+    /// The patterns between the words of the dictionary, e.g. loaded with [`PatternCache::load`],
+    /// or computed as they are needed if `None`.
+    pub cache: Option<Arc<PatternCache>>,
+    /// The words that may be the answer, or every word of the dictionary if `None`.
+    pub answers: Option<Vec<&'static str>>,
+    pub prior: Prior,
+    /// Factors adjusting the prior, if any.
+    pub weighting: Option<Arc<Weighting>>,
+    pub pool: GuessPool,
+    /// The only words the naive algorithm considers among those that were ruled out, if any.
+    pub reduced_pool: Option<Arc<ReducedPool>>,
+    /// Rules the first guess of the naive and positional algorithms must follow.
+    pub opener: OpenerRules,
+    pub tie_break: TieBreak,
+    /// Seed of the random number generator, for the algorithms that make random choices.
+    pub seed: u64,
+    /// When the ensemble hands over from the naive algorithm to the positional one.
+    pub crossover: Crossover,
+}
+
+/// A guessing algorithm that can be selected by name.
+pub struct Algorithm {
+    pub name: &'static str,
+    /// Other names the algorithm can be selected by.
+    pub aliases: &'static [&'static str],
+    pub description: &'static str,
+    build: fn(Settings) -> crate::Result<Factory>,
+}
+
+impl Algorithm {
+    /// Builds the guesser for the given settings, and returns a factory that clones it for every
+    /// game so that the pattern cache is only built once.
+    ///
+    /// Fails if the cache of the settings is for another dictionary.
+    pub fn factory(&self, settings: Settings) -> crate::Result<Factory> {
+        (self.build)(settings)
+    }
+}
+
+/// Returns every algorithm that can be selected by name.
+pub fn registry() -> &'static [Algorithm] {
+    &ALGORITHMS
+}
+
+/// Returns the algorithm with the given name or alias.
+pub fn lookup(name: &str) -> Option<&'static Algorithm> {
+    registry()
+        .iter()
+        .find(|algorithm| algorithm.name == name || algorithm.aliases.contains(&name))
+}
+
+static ALGORITHMS: [Algorithm; 7] = [
+    Algorithm {
+        name: "naive",
+        aliases: &["entropy"],
+        description: "Picks the guess revealing the most information",
+        build: naive,
+    },
+    Algorithm {
+        name: "minimax",
+        aliases: &[],
+        description: "Picks the guess whose worst case leaves the fewest candidates",
+        build: minimax,
+    },
+    Algorithm {
+        name: "expected-size",
+        aliases: &[],
+        description: "Picks the guess leaving the fewest candidates on average",
+        build: expected_size,
+    },
+    Algorithm {
+        name: "two-ply",
+        aliases: &[],
+        description: "Picks the guess revealing the most information over the next two guesses",
+        build: two_ply,
+    },
+    Algorithm {
+        name: "positional",
+        aliases: &["frequency"],
+        description: "Picks the candidate with the most common letters at their positions",
+        build: positional,
+    },
+    Algorithm {
+        name: "ensemble",
+        aliases: &[],
+        description: "Picks the naive guess, then the positional one once few candidates remain",
+        build: ensemble,
+    },
+    Algorithm {
+        name: "random",
+        aliases: &[],
+        description: "Picks a random candidate, as a baseline",
+        build: random,
+    },
+];
+
+fn naive(settings: Settings) -> crate::Result<Factory> {
+    Ok(cloning(naive_guesser(settings)?))
+}
+
+fn naive_guesser(settings: Settings) -> crate::Result<Naive> {
+    let mut guesser = match (settings.cache, settings.dictionary) {
+        (Some(cache), dictionary) => Naive::with_cache(words(dictionary), cache)?,
+        (None, Some(dictionary)) => Naive::with_dictionary(dictionary),
+        (None, None) => Naive::new(),
+    };
+    if let Some(answers) = settings.answers {
+        guesser = guesser.with_answers(answers);
+    }
+    if let Some(weighting) = settings.weighting {
+        guesser = guesser.with_weighting(weighting);
+    }
+    if let Some(pool) = &settings.reduced_pool {
+        guesser = guesser.with_reduced_pool(pool);
+    }
+    Ok(guesser
+        .with_prior(settings.prior)
+        .with_pool(settings.pool)
+        .with_opener_rules(settings.opener)
+        .with_tie_break(settings.tie_break))
+}
+
+fn minimax(settings: Settings) -> crate::Result<Factory> {
+    let mut guesser = match (settings.cache, settings.dictionary) {
+        (Some(cache), dictionary) => Minimax::with_cache(words(dictionary), cache)?,
+        (None, Some(dictionary)) => Minimax::with_dictionary(dictionary),
+        (None, None) => Minimax::new(),
+    };
+    if let Some(answers) = settings.answers {
+        guesser = guesser.with_answers(answers);
+    }
+    Ok(cloning(guesser.with_tie_break(settings.tie_break)))
+}
+
+fn expected_size(settings: Settings) -> crate::Result<Factory> {
+    let mut guesser = match (settings.cache, settings.dictionary) {
+        (Some(cache), dictionary) => ExpectedSize::with_cache(words(dictionary), cache)?,
+        (None, Some(dictionary)) => ExpectedSize::with_dictionary(dictionary),
+        (None, None) => ExpectedSize::new(),
+    };
+    if let Some(answers) = settings.answers {
+        guesser = guesser.with_answers(answers);
+    }
+    Ok(cloning(guesser.with_tie_break(settings.tie_break)))
+}
+
+fn two_ply(settings: Settings) -> crate::Result<Factory> {
+    let mut guesser = match (settings.cache, settings.dictionary) {
+        (Some(cache), dictionary) => TwoPly::with_cache(words(dictionary), cache)?,
+        (None, Some(dictionary)) => TwoPly::with_dictionary(dictionary),
+        (None, None) => TwoPly::new(),
+    };
+    if let Some(answers) = settings.answers {
+        guesser = guesser.with_answers(answers);
+    }
+    Ok(cloning(guesser.with_tie_break(settings.tie_break)))
+}
+
+fn positional(settings: Settings) -> crate::Result<Factory> {
+    Ok(cloning(positional_guesser(settings)?))
+}
+
+fn positional_guesser(settings: Settings) -> crate::Result<Positional> {
+    let mut guesser = match (settings.cache, settings.dictionary) {
+        (Some(cache), dictionary) => Positional::with_cache(words(dictionary), cache)?,
+        (None, Some(dictionary)) => Positional::with_dictionary(dictionary),
+        (None, None) => Positional::new(),
+    };
+    if let Some(answers) = settings.answers {
+        guesser = guesser.with_answers(answers);
+    }
+    Ok(guesser
+        .with_pool(settings.pool)
+        .with_opener_rules(settings.opener)
+        .with_tie_break(settings.tie_break))
+}
+
+fn ensemble(mut settings: Settings) -> crate::Result<Factory> {
+    // Both guessers look the patterns up in the same cache rather than building their own.
+    if settings.cache.is_none() {
+        if let Some(dictionary) = &settings.dictionary {
+            let cache = PatternCache::new(dictionary.iter().map(|&(word, _)| word));
+            settings.cache = Some(Arc::new(cache));
+        }
+    }
+    let crossover = settings.crossover;
+    let early = naive_guesser(settings.clone())?;
+    let late = positional_guesser(settings)?;
+    Ok(cloning(
+        Ensemble::new(early, late).with_crossover(crossover),
+    ))
+}
+
+fn random(settings: Settings) -> crate::Result<Factory> {
+    let mut guesser = match (settings.cache, settings.dictionary) {
+        (Some(cache), _) => Random::with_cache(cache),
+        (None, Some(dictionary)) => Random::with_dictionary(dictionary),
+        (None, None) => Random::new(),
+    };
+    if let Some(answers) = settings.answers {
+        guesser = guesser.with_answers(answers);
+    }
+    Ok(cloning(guesser.with_seed(settings.seed)))
+}
+
+/// Returns the words of the dictionary of the settings, or of the built-in one.
+fn words(dictionary: Option<Vec<(&'static str, usize)>>) -> Vec<(&'static str, usize)> {
+    dictionary.unwrap_or_else(|| builtin_dictionary().collect())
+}
+
+fn cloning<G: Guesser + Clone + Send + Sync + 'static>(prototype: G) -> Factory {
+    Box::new(move || Box::new(prototype.clone()))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::{lookup, registry, PatternCache, Settings};
+    use crate::{Correctness, Guess, History, Wordle, WORDS};
+
+    #[test]
+    fn lookup_by_name_or_alias() {
+        assert_eq!(lookup("naive").map(|a| a.name), Some("naive"));
+        assert_eq!(lookup("entropy").map(|a| a.name), Some("naive"));
+        assert_eq!(lookup("two-ply").map(|a| a.name), Some("two-ply"));
+        assert!(lookup("oracle").is_none());
+    }
+
+    #[test]
+    fn every_algorithm_plays() {
+        let wordle = Wordle::with_dictionary(WORDS.iter().copied());
+        for algorithm in registry() {
+            let factory = algorithm
+                .factory(Settings {
+                    dictionary: Some(WORDS.to_vec()),
+                    ..Settings::default()
+                })
+                .unwrap();
+            for &(answer, _) in WORDS {
+                assert!(
+                    matches!(wordle.play(answer, factory()), Ok(Some(_))),
+                    "{} did not find {}",
+                    algorithm.name,
+                    answer
+                );
+            }
+
+            let answers = vec!["cigar", "humph"];
+            let factory = algorithm
+                .factory(Settings {
+                    dictionary: Some(WORDS.to_vec()),
+                    answers: Some(answers.clone()),
+                    ..Settings::default()
+                })
+                .unwrap();
+            for answer in answers {
+                assert!(
+                    matches!(wordle.play(answer, factory()), Ok(Some(_))),
+                    "{} did not find {}",
+                    algorithm.name,
+                    answer
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn every_algorithm_shares_the_cache() {
+        let wordle = Wordle::with_dictionary(WORDS.iter().copied());
+        let cache = Arc::new(PatternCache::new(WORDS.iter().map(|&(word, _)| word)));
+        for algorithm in registry() {
+            let factory = algorithm
+                .factory(Settings {
+                    dictionary: Some(WORDS.to_vec()),
+                    cache: Some(cache.clone()),
+                    ..Settings::default()
+                })
+                .unwrap();
+            for &(answer, _) in WORDS {
+                assert!(matches!(wordle.play(answer, factory()), Ok(Some(_))));
+            }
+        }
+    }
+
+    #[test]
+    fn every_algorithm_reports_remaining() {
+        for algorithm in registry() {
+            let mut guesser = algorithm
+                .factory(Settings {
+                    dictionary: Some(WORDS.to_vec()),
+                    ..Settings::default()
+                })
+                .unwrap()();
+            guesser.guess(&History::new());
+            assert_eq!(guesser.remaining().map(|r| r.len()), Some(WORDS.len()));
+
+            let history = History::from(vec![Guess::new(
+                "blush",
+                Correctness::compute("humph", "blush"),
+            )]);
+            guesser.guess(&history);
+            assert_eq!(
+                guesser.remaining(),
+                Some(vec!["humph"]),
+                "{} has other candidates",
+                algorithm.name
+            );
+        }
+    }
+
+    #[test]
+    fn every_algorithm_survives_contradictions() {
+        let history = History::from(vec![
+            Guess::new("cigar", [Correctness::Wrong; 5]),
+            Guess::new("humph", [Correctness::Wrong; 5]),
+        ]);
+        for algorithm in registry() {
+            let mut guesser = algorithm
+                .factory(Settings {
+                    dictionary: Some(WORDS.to_vec()),
+                    ..Settings::default()
+                })
+                .unwrap()();
+            // The first word of the dictionary that was not played.
+            assert_eq!(guesser.guess(&history), "rebut", "{}", algorithm.name);
+        }
+    }
+
+    #[test]
+    fn rejects_the_cache_of_another_dictionary() {
+        let cache = Arc::new(PatternCache::new(WORDS[1..].iter().map(|&(word, _)| word)));
+        let factory = lookup("naive").unwrap().factory(Settings {
+            dictionary: Some(WORDS.to_vec()),
+            cache: Some(cache),
+            ..Settings::default()
+        });
+        assert!(matches!(
+            factory.err(),
+            Some(crate::Error::CacheMismatch {
+                words: 12,
+                cache: 11
+            })
+        ));
+    }
+}
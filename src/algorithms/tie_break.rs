@@ -0,0 +1,87 @@
+use std::cmp::Reverse;
+
+/// How a guesser picks among guesses that score exactly the same.
+///
+/// Guesses that still tie under the policy are picked in dictionary order, so that a guesser picks
+/// the same guesses on every run, whatever the number of threads scoring them.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub enum TieBreak {
+    /// The word that could still be the answer, which gives the guess a chance to win.
+    #[default]
+    PossibleAnswer,
+    /// The word with the highest frequency count.
+    Frequency,
+    /// The word that comes first alphabetically.
+    Alphabetical,
+    /// The word that comes first in an order shuffled by the seed, e.g. to check how much an
+    /// algorithm owes to the order of the dictionary. The same seed gives the same order on every
+    /// run and platform.
+    Shuffled(u64),
+}
+
+/// How much a [`TieBreak`] likes a guess, compared after the score of the guess: the higher the
+/// better.
+pub(crate) type Preference = (bool, usize, u64, Reverse<&'static str>);
+
+impl TieBreak {
+    /// Returns how much the policy likes guessing `word`, whose frequency count is `count`, and
+    /// which may be the answer if `possible`.
+    pub(crate) fn preference(self, word: &'static str, count: usize, possible: bool) -> Preference {
+        match self {
+            TieBreak::PossibleAnswer => (possible, 0, 0, Reverse("")),
+            TieBreak::Frequency => (false, count, 0, Reverse("")),
+            TieBreak::Alphabetical => (false, 0, 0, Reverse(word)),
+            TieBreak::Shuffled(seed) => {
+                let key = crate::fnv1a(seed.to_le_bytes().iter().copied().chain(word.bytes()));
+                (false, 0, key, Reverse(""))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use super::TieBreak;
+    use crate::algorithms::{ExpectedSize, Minimax, Naive, Prior};
+    use crate::{Guesser, History};
+
+    /// Words without a letter in common, so that every guess splits the others off the same way.
+    const WORDS: &[(&str, usize)] = &[("fghij", 1), ("klmno", 5), ("abcde", 3)];
+
+    fn first_guesses(tie_break: TieBreak) -> [String; 3] {
+        let words = || WORDS.iter().copied();
+        let mut guessers: [Box<dyn Guesser>; 3] = [
+            Box::new(
+                Naive::with_dictionary(words())
+                    .with_prior(Prior::Uniform)
+                    .with_tie_break(tie_break),
+            ),
+            Box::new(Minimax::with_dictionary(words()).with_tie_break(tie_break)),
+            Box::new(ExpectedSize::with_dictionary(words()).with_tie_break(tie_break)),
+        ];
+        guessers
+            .each_mut()
+            .map(|g| g.guess(&History::new()).into_owned())
+    }
+
+    #[test]
+    fn breaks_ties() {
+        assert_eq!(first_guesses(TieBreak::PossibleAnswer), ["fghij"; 3]);
+        assert_eq!(first_guesses(TieBreak::Frequency), ["klmno"; 3]);
+        assert_eq!(first_guesses(TieBreak::Alphabetical), ["abcde"; 3]);
+    }
+
+    #[test]
+    fn shuffles_ties_by_seed() {
+        let mut picked = BTreeSet::new();
+        for seed in 0..20 {
+            let guesses = first_guesses(TieBreak::Shuffled(seed));
+            assert_eq!(guesses, first_guesses(TieBreak::Shuffled(seed)));
+            assert!(guesses.iter().all(|guess| *guess == guesses[0]));
+            picked.insert(guesses[0].clone());
+        }
+        assert_eq!(picked.len(), WORDS.len());
+    }
+}
@@ -0,0 +1,206 @@
+use super::{Pack, PatternCache};
+use crate::Correctness;
+
+/// How every letter would color the tile at every position against the remaining candidates, to
+/// bound how well a guess splits them without going through the candidates for every guess.
+///
+/// The pattern a guess reveals is made of its tiles, so it carries no more information than its
+/// tiles do on their own, and there are no more distinct patterns than the product of the numbers
+/// of colors every tile may take.
+pub(crate) struct Tiles<'a, const N: usize>
+where
+    Correctness: Pack<N>,
+{
+    cache: &'a PatternCache<N>,
+    /// `None` if some candidate is not made of lowercase ASCII letters, in which case nothing is
+    /// bounded.
+    bounds: Option<Bounds<N>>,
+}
+
+/// Bounds for the tile of every letter at every position, indexed by position then letter.
+struct Bounds<const N: usize> {
+    /// The entropy (in bits) of the color of the tile, if the letter appears once in the guess.
+    once: [[f64; 26]; N],
+    /// At least the entropy of the color of the tile, if the letter appears more than once.
+    repeated: [[f64; 26]; N],
+    /// How many colors the tile takes, if the letter appears once in the guess.
+    colors: [[usize; 26]; N],
+    /// At least how many colors the tile takes, if the letter appears more than once.
+    repeated_colors: [[usize; 26]; N],
+}
+
+impl<'a, const N: usize> Tiles<'a, N>
+where
+    Correctness: Pack<N>,
+{
+    /// Goes through the `candidates`, each given along with its likelihood of being the answer, to
+    /// bound the guesses of `cache` against them.
+    pub(crate) fn new(
+        cache: &'a PatternCache<N>,
+        candidates: impl IntoIterator<Item = (usize, f64)>,
+    ) -> Self {
+        Self {
+            cache,
+            bounds: Bounds::new(cache, candidates),
+        }
+    }
+
+    /// Returns at least as much information (in bits) as the guess at index `guess` reveals about
+    /// the candidates, or infinity if that is not known.
+    pub(crate) fn information(&self, guess: usize) -> f64 {
+        let (Some(bounds), Some(letters)) = (&self.bounds, self.cache.dictionary().letters(guess))
+        else {
+            return f64::INFINITY;
+        };
+
+        let letters = letters.as_bytes();
+        (0..N)
+            .map(|position| {
+                let letter = (letters[position] - b'a') as usize;
+                if repeated(letters, position) {
+                    bounds.repeated[position][letter]
+                } else {
+                    bounds.once[position][letter]
+                }
+            })
+            .sum()
+    }
+
+    /// Returns at least as many distinct patterns as the guess at index `guess` reveals against
+    /// the candidates, or `usize::MAX` if that is not known.
+    pub(crate) fn patterns(&self, guess: usize) -> usize {
+        let (Some(bounds), Some(letters)) = (&self.bounds, self.cache.dictionary().letters(guess))
+        else {
+            return usize::MAX;
+        };
+
+        let letters = letters.as_bytes();
+        (0..N)
+            .map(|position| {
+                let letter = (letters[position] - b'a') as usize;
+                if repeated(letters, position) {
+                    bounds.repeated_colors[position][letter]
+                } else {
+                    bounds.colors[position][letter]
+                }
+            })
+            .product()
+    }
+}
+
+impl<const N: usize> Bounds<N> {
+    fn new(
+        cache: &PatternCache<N>,
+        candidates: impl IntoIterator<Item = (usize, f64)>,
+    ) -> Option<Self>
+    where
+        Correctness: Pack<N>,
+    {
+        // The weight and number of the candidates with every letter at every position, and with
+        // every letter anywhere.
+        let mut green = [[(0.0, 0); 26]; N];
+        let mut present = [(0.0, 0); 26];
+        let mut total = (0.0, 0);
+        for (candidate, weight) in candidates {
+            let letters = cache.dictionary().letters(candidate)?;
+            let mut seen = [false; 26];
+            for (position, &letter) in letters.as_bytes().iter().enumerate() {
+                let letter = (letter - b'a') as usize;
+                add(&mut green[position][letter], weight);
+                if !std::mem::replace(&mut seen[letter], true) {
+                    add(&mut present[letter], weight);
+                }
+            }
+            add(&mut total, weight);
+        }
+        if total.0 <= 0.0 {
+            return None;
+        }
+
+        let mut bounds = Bounds {
+            once: [[0.0; 26]; N],
+            repeated: [[0.0; 26]; N],
+            colors: [[0; 26]; N],
+            repeated_colors: [[0; 26]; N],
+        };
+        for position in 0..N {
+            for letter in 0..26 {
+                let green = green[position][letter];
+                let present = present[letter];
+                // A letter that appears once in the guess is yellow exactly when the candidate has
+                // it elsewhere, and gray when the candidate does not have it at all.
+                let yellow = (present.0 - green.0, present.1 - green.1);
+                let gray = (total.0 - present.0, total.1 - present.1);
+                let once = [green, yellow, gray];
+                bounds.once[position][letter] = entropy(&once.map(|(weight, _)| weight), total.0);
+                bounds.colors[position][letter] = once.iter().filter(|&&(_, n)| n > 0).count();
+
+                // Otherwise, whether it is yellow or gray also depends on the other copies of the
+                // letter, which is worth at most a bit.
+                let other = (total.0 - green.0, total.1 - green.1);
+                bounds.repeated[position][letter] =
+                    entropy(&[green.0, other.0], total.0) + other.0 / total.0;
+                bounds.repeated_colors[position][letter] =
+                    usize::from(green.1 > 0) + 2 * usize::from(other.1 > 0);
+            }
+        }
+        Some(bounds)
+    }
+}
+
+fn add(totals: &mut (f64, usize), weight: f64) {
+    totals.0 += weight;
+    totals.1 += 1;
+}
+
+/// Returns the entropy (in bits) of the distribution that gives each outcome its share of
+/// `total`.
+fn entropy(weights: &[f64], total: f64) -> f64 {
+    -weights
+        .iter()
+        .map(|&weight| weight / total)
+        .filter(|&p| p > 0.0)
+        .map(|p| p * p.log2())
+        .sum::<f64>()
+}
+
+/// Returns whether the letter at `position` appears elsewhere in `letters`.
+fn repeated<const N: usize>(letters: &[u8; N], position: usize) -> bool {
+    letters
+        .iter()
+        .enumerate()
+        .any(|(i, &letter)| i != position && letter == letters[position])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Tiles;
+    use crate::algorithms::{entropy, PatternBuckets, PatternCache};
+
+    const WORDS: &[&str] = &[
+        "cigar", "rebut", "sissy", "humph", "awake", "blush", "focal", "ñandu",
+    ];
+
+    #[test]
+    fn bounds_every_guess() {
+        let cache: PatternCache = PatternCache::new(WORDS[..7].iter().copied());
+        let candidates: Vec<(usize, f64)> = (0..7).map(|i| (i, (i + 1) as f64)).collect();
+        let indices: Vec<usize> = (0..7).collect();
+        let tiles = Tiles::new(&cache, candidates.iter().copied());
+        for guess in 0..cache.len() {
+            let information = entropy(cache.row(guess), &candidates);
+            assert!(tiles.information(guess) >= information - 1e-9);
+            let buckets = PatternBuckets::count(cache.row(guess), &indices);
+            let patterns = buckets.totals().iter().filter(|&&n| n > 0).count();
+            assert!(tiles.patterns(guess) >= patterns);
+        }
+    }
+
+    #[test]
+    fn bounds_nothing_with_other_letters() {
+        let cache: PatternCache = PatternCache::new(WORDS.iter().copied());
+        let tiles = Tiles::new(&cache, (0..WORDS.len()).map(|i| (i, 1.0)));
+        assert_eq!(tiles.information(0), f64::INFINITY);
+        assert_eq!(tiles.patterns(0), usize::MAX);
+    }
+}
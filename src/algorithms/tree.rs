@@ -0,0 +1,445 @@
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+use std::convert::TryFrom;
+#[cfg(feature = "std")]
+use std::fs::File;
+use std::io::{self, Read, Write};
+#[cfg(feature = "std")]
+use std::io::{BufReader, BufWriter};
+#[cfg(feature = "std")]
+use std::path::Path;
+use std::sync::Arc;
+
+use super::exact::Search;
+use super::PatternCache;
+use crate::constraints::Constraints;
+use crate::{CancelToken, Correctness, Estimate, Explanation, Guess, Guesser, History};
+
+/// Identifies the files written by [`DecisionTree::save`].
+const MAGIC: &[u8; 6] = b"CRTREE";
+const VERSION: u8 = 1;
+
+/// What to guess after every mask the previous guesses may reveal, for every answer of a list,
+/// computed once so that games are played without scoring a single guess.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DecisionTree {
+    /// Whether every guess is allowed in hard mode.
+    hard_mode: bool,
+    root: Node,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Node {
+    guess: String,
+    /// Whether the guess is one of the answers leading to this node, i.e. whether it may win.
+    answer: bool,
+    /// The node to play next, keyed by the packed mask the guess revealed, for every mask but the
+    /// one of a win.
+    children: BTreeMap<u8, Node>,
+}
+
+impl DecisionTree {
+    /// Computes a tree that finds every one of the `answers`, guessing words of `cache`, or
+    /// `None` if none of the answers is in the cache. Answers that are not in the cache are
+    /// ignored.
+    ///
+    /// At every node, the guesses are ranked by the number of answers they leave on average, and
+    /// only the `beam` best ones are expanded into full subtrees, keeping the one that takes the
+    /// fewest guesses over all its answers. Without a beam every guess is expanded, which makes
+    /// the tree optimal but is only feasible for small word lists.
+    pub fn build<'a>(
+        cache: &PatternCache,
+        answers: impl IntoIterator<Item = &'a str>,
+        hard_mode: bool,
+        beam: Option<usize>,
+    ) -> Option<Self> {
+        Self::build_until(cache, answers, hard_mode, beam, &CancelToken::new())
+    }
+
+    /// Like [`DecisionTree::build`], but once `cancel` is cancelled, the rest of the tree is built
+    /// by only expanding the most promising guess at every node, which is much faster but makes
+    /// the tree worse.
+    pub fn build_until<'a>(
+        cache: &PatternCache,
+        answers: impl IntoIterator<Item = &'a str>,
+        hard_mode: bool,
+        beam: Option<usize>,
+        cancel: &CancelToken,
+    ) -> Option<Self> {
+        let mut answers: Vec<usize> = answers
+            .into_iter()
+            .filter_map(|answer| cache.index(answer))
+            .collect();
+        answers.sort_unstable();
+        answers.dedup();
+        if answers.is_empty() {
+            return None;
+        }
+
+        let search = Search::new(cache, hard_mode, cancel)
+            .with_beam(beam.unwrap_or(usize::MAX))
+            .hurry_once_cancelled();
+        let (total, _) = search
+            .solve(&answers, &Constraints::new())
+            .expect("the search hurries rather than gives up");
+        let root = Node::build(&search, &answers, &Constraints::new());
+        tracing::debug!(answers = answers.len(), total, "built the decision tree");
+
+        Some(Self { hard_mode, root })
+    }
+
+    /// Reads a tree saved with [`DecisionTree::save`].
+    #[cfg(feature = "std")]
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        Self::read_from(BufReader::new(File::open(path)?))
+    }
+
+    #[cfg(feature = "std")]
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        self.write_to(&mut writer)?;
+        writer.flush()
+    }
+
+    /// Writes the tree in a compact binary format: a table of the distinct guesses, followed by
+    /// every node in depth-first order as the index of its guess and its children.
+    pub fn write_to(&self, mut writer: impl Write) -> io::Result<()> {
+        let mut words = Vec::new();
+        let mut index = BTreeMap::new();
+        self.root.visit(&mut |node| {
+            index.entry(node.guess.as_str()).or_insert_with(|| {
+                words.push(node.guess.as_str());
+                words.len() - 1
+            });
+        });
+
+        writer.write_all(MAGIC)?;
+        writer.write_all(&[VERSION, self.hard_mode as u8])?;
+        writer.write_all(&(words.len() as u32).to_le_bytes())?;
+        for word in &words {
+            let len = u8::try_from(word.len()).map_err(|_| invalid("guess is too long"))?;
+            writer.write_all(&[len])?;
+            writer.write_all(word.as_bytes())?;
+        }
+        self.root.write_to(&mut writer, &index)
+    }
+
+    /// Reads a tree written with [`DecisionTree::write_to`].
+    pub fn read_from(mut reader: impl Read) -> io::Result<Self> {
+        let mut magic = [0; 6];
+        reader.read_exact(&mut magic)?;
+        let [version, hard_mode] = read_bytes(&mut reader)?;
+        if &magic != MAGIC || version != VERSION {
+            return Err(invalid("not a decision tree"));
+        }
+
+        let len = u32::from_le_bytes(read_bytes(&mut reader)?) as usize;
+        let mut words = Vec::with_capacity(len.min(1 << 16));
+        for _ in 0..len {
+            let [len] = read_bytes(&mut reader)?;
+            let mut word = vec![0; len as usize];
+            reader.read_exact(&mut word)?;
+            words.push(String::from_utf8(word).map_err(|_| invalid("guess is not UTF-8"))?);
+        }
+
+        Ok(Self {
+            hard_mode: hard_mode != 0,
+            root: Node::read_from(&mut reader, &words)?,
+        })
+    }
+
+    /// Returns the guess to play after `history`, or `None` if the game strayed from the tree.
+    pub fn guess(&self, history: &[Guess]) -> Option<&str> {
+        let mut node = &self.root;
+        for guess in history {
+            if guess.word != node.guess {
+                return None;
+            }
+            node = node.children.get(&Correctness::pack(guess.mask))?;
+        }
+        Some(&node.guess)
+    }
+
+    pub fn hard_mode(&self) -> bool {
+        self.hard_mode
+    }
+
+    /// Returns the number of answers the tree finds.
+    pub fn len(&self) -> usize {
+        let mut len = 0;
+        self.root.visit(&mut |node| len += node.answer as usize);
+        len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the number of guesses it takes to find every answer, summed over all answers.
+    pub fn total_guesses(&self) -> usize {
+        self.root.depths(1).sum()
+    }
+
+    /// Returns the number of guesses it takes to find the answers on average.
+    pub fn average_guesses(&self) -> f64 {
+        self.total_guesses() as f64 / self.len() as f64
+    }
+
+    /// Returns the number of guesses it takes to find the hardest answer.
+    pub fn max_guesses(&self) -> usize {
+        self.root.depths(1).max().unwrap_or(0)
+    }
+}
+
+impl Node {
+    /// Returns the subtree playing the best guess `search` found for `answers` first, after the
+    /// hints `constraints`.
+    fn build(search: &Search, answers: &[usize], constraints: &Constraints) -> Self {
+        let (_, guess) = search
+            .solve(answers, constraints)
+            .expect("the search hurries rather than gives up");
+        let (answer, buckets) = search.split(guess, answers);
+        let children = buckets
+            .into_iter()
+            .map(|(mask, bucket)| {
+                let constraints = search.narrow(guess, mask, constraints);
+                (mask, Node::build(search, &bucket, &constraints))
+            })
+            .collect();
+
+        Self {
+            guess: search.word(guess).to_string(),
+            answer,
+            children,
+        }
+    }
+
+    /// Calls `f` on this node and all its descendants, in depth-first order.
+    fn visit<'a>(&'a self, f: &mut impl FnMut(&'a Node)) {
+        f(self);
+        for child in self.children.values() {
+            child.visit(f);
+        }
+    }
+
+    /// Returns the number of guesses it takes to find every answer below this node, which is
+    /// played as guess number `depth`.
+    fn depths(&self, depth: usize) -> Box<dyn Iterator<Item = usize> + '_> {
+        Box::new(
+            self.answer.then_some(depth).into_iter().chain(
+                self.children
+                    .values()
+                    .flat_map(move |child| child.depths(depth + 1)),
+            ),
+        )
+    }
+
+    fn write_to(&self, writer: &mut impl Write, index: &BTreeMap<&str, usize>) -> io::Result<()> {
+        writer.write_all(&(index[self.guess.as_str()] as u32).to_le_bytes())?;
+        writer.write_all(&[self.answer as u8, self.children.len() as u8])?;
+        for (&mask, child) in &self.children {
+            writer.write_all(&[mask])?;
+            child.write_to(writer, index)?;
+        }
+        Ok(())
+    }
+
+    fn read_from(reader: &mut impl Read, words: &[String]) -> io::Result<Self> {
+        let guess = u32::from_le_bytes(read_bytes(reader)?) as usize;
+        let guess = words.get(guess).ok_or_else(|| invalid("unknown guess"))?;
+        let [answer, len] = read_bytes(reader)?;
+
+        let mut children = BTreeMap::new();
+        for _ in 0..len {
+            let [mask] = read_bytes(reader)?;
+            if mask as usize >= Correctness::PATTERNS {
+                return Err(invalid("not a packed mask"));
+            }
+            children.insert(mask, Node::read_from(reader, words)?);
+        }
+
+        Ok(Self {
+            guess: guess.clone(),
+            answer: answer != 0,
+            children,
+        })
+    }
+}
+
+fn read_bytes<const N: usize>(reader: &mut impl Read) -> io::Result<[u8; N]> {
+    let mut bytes = [0; N];
+    reader.read_exact(&mut bytes)?;
+    Ok(bytes)
+}
+
+fn invalid(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+/// Plays the guesses of a [`DecisionTree`], and only asks the wrapped guesser once the game strays
+/// from the tree, e.g. because the answer is not one the tree was built for.
+///
+/// The tree is ignored when the game is not played in the mode it was built for, since its
+/// guesses might not be allowed in hard mode.
+#[derive(Clone)]
+pub struct TreeGuesser<G> {
+    inner: G,
+    tree: Arc<DecisionTree>,
+    hard_mode: bool,
+}
+
+impl<G> TreeGuesser<G> {
+    pub fn new(inner: G, tree: Arc<DecisionTree>) -> Self {
+        Self {
+            inner,
+            tree,
+            hard_mode: false,
+        }
+    }
+}
+
+impl<G> TreeGuesser<G> {
+    /// Returns the guess the tree has for `history`, if any.
+    fn tree_guess(&self, history: &[Guess]) -> Option<Cow<'static, str>> {
+        if self.hard_mode != self.tree.hard_mode {
+            return None;
+        }
+        self.tree
+            .guess(history)
+            .map(|guess| Cow::Owned(guess.to_string()))
+    }
+}
+
+impl<G: Guesser> Guesser for TreeGuesser<G> {
+    fn guess(&mut self, history: &History) -> Cow<'static, str> {
+        match self.tree_guess(history) {
+            Some(guess) => guess,
+            None => self.inner.guess(history),
+        }
+    }
+
+    fn guess_with_explanation(
+        &mut self,
+        history: &History,
+    ) -> (Cow<'static, str>, Option<Explanation>) {
+        match self.tree_guess(history) {
+            Some(guess) => (guess, None),
+            None => self.inner.guess_with_explanation(history),
+        }
+    }
+
+    fn set_hard_mode(&mut self, hard_mode: bool) {
+        self.hard_mode = hard_mode;
+        self.inner.set_hard_mode(hard_mode)
+    }
+
+    fn set_cancel_token(&mut self, token: CancelToken) {
+        self.inner.set_cancel_token(token)
+    }
+
+    fn remaining(&self) -> Option<Vec<&'static str>> {
+        self.inner.remaining()
+    }
+
+    fn rank(&self, history: &History, k: usize) -> Vec<(&'static str, f64)> {
+        self.inner.rank(history, k)
+    }
+
+    fn estimate(&self, history: &History) -> Option<Estimate> {
+        self.inner.estimate(history)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::{DecisionTree, TreeGuesser};
+    use crate::algorithms::{Naive, PatternCache};
+    use crate::{History, Wordle, WORDS};
+
+    fn build(hard_mode: bool, beam: Option<usize>) -> DecisionTree {
+        let cache = PatternCache::new(WORDS.iter().map(|&(word, _)| word));
+        let answers = WORDS.iter().map(|&(word, _)| word);
+        DecisionTree::build(&cache, answers, hard_mode, beam).unwrap()
+    }
+
+    #[test]
+    fn finds_every_answer() {
+        let wordle = Wordle::with_dictionary(WORDS.iter().copied());
+        let naive = Naive::with_dictionary(WORDS.iter().copied());
+        for beam in [Some(1), Some(3), None] {
+            let tree = Arc::new(build(false, beam));
+            assert_eq!(tree.len(), WORDS.len());
+
+            let mut total = 0;
+            for &(answer, _) in WORDS {
+                let guesser = TreeGuesser::new(naive.clone(), tree.clone());
+                let transcript = wordle.play_transcript(answer, guesser).unwrap();
+                let score = transcript.score.unwrap();
+                // Only the tree was played.
+                for (i, guess) in transcript.guesses.iter().enumerate() {
+                    assert_eq!(tree.guess(&transcript.guesses[..i]), Some(&*guess.word));
+                }
+                total += score;
+            }
+            assert_eq!(tree.total_guesses(), total);
+        }
+    }
+
+    #[test]
+    fn wider_beams_do_better() {
+        let greedy = build(false, Some(1));
+        let optimal = build(false, None);
+        assert!(optimal.total_guesses() <= build(false, Some(3)).total_guesses());
+        assert!(build(false, Some(3)).total_guesses() <= greedy.total_guesses());
+        assert!(optimal.max_guesses() <= 3);
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn same_tree_on_any_number_of_threads() {
+        let on = |threads| {
+            let pool = rayon::ThreadPoolBuilder::new()
+                .num_threads(threads)
+                .build()
+                .unwrap();
+            pool.install(|| [build(false, Some(3)), build(true, None)])
+        };
+        assert_eq!(on(1), on(4));
+    }
+
+    #[test]
+    fn hard_mode() {
+        let wordle = Wordle::with_dictionary(WORDS.iter().copied()).with_hard_mode(true);
+        let naive = Naive::with_dictionary(WORDS.iter().copied());
+        let tree = Arc::new(build(true, None));
+        assert!(tree.hard_mode());
+        for &(answer, _) in WORDS {
+            let guesser = TreeGuesser::new(naive.clone(), tree.clone());
+            assert!(wordle.play(answer, guesser).unwrap().is_some());
+        }
+    }
+
+    #[test]
+    fn unknown_answers() {
+        let cache = PatternCache::new(WORDS.iter().map(|&(word, _)| word));
+        assert_eq!(DecisionTree::build(&cache, ["crane"], false, None), None);
+
+        let tree = DecisionTree::build(&cache, ["crane", "cigar"], false, None).unwrap();
+        assert_eq!(tree.len(), 1);
+        assert_eq!(tree.guess(&History::new()), Some("cigar"));
+    }
+
+    #[test]
+    fn round_trip() {
+        let tree = build(true, Some(2));
+        let mut bytes = Vec::new();
+        tree.write_to(&mut bytes).unwrap();
+        assert_eq!(DecisionTree::read_from(&bytes[..]).unwrap(), tree);
+
+        bytes[0] = b'X';
+        assert!(DecisionTree::read_from(&bytes[..]).is_err());
+        assert!(DecisionTree::read_from(&[][..]).is_err());
+    }
+}
@@ -0,0 +1,325 @@
+use std::borrow::Cow;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+
+use super::{entropy, explain, no_candidate_left, Candidates, PatternCache, TieBreak};
+use crate::{CancelToken, Dictionary, Explanation, Guesser, History};
+
+/// Number of guesses looked ahead from by default, out of those revealing the most information.
+const DEFAULT_SHORTLIST: usize = 10;
+
+/// The candidates a guess leaves for one of its patterns, with how likely they are to be the
+/// answer.
+type Bucket = Vec<(usize, f64)>;
+
+/// Looks two guesses ahead: every guess is scored by the information it reveals, plus the expected
+/// information revealed by the best follow-up guess for each pattern it can produce.
+#[derive(Clone)]
+pub struct TwoPly {
+    remaining: Candidates,
+    /// Number of guesses of the history `remaining` was narrowed down with.
+    seen: usize,
+    /// Frequency count of every word, indexed like the pattern cache.
+    counts: Arc<[usize]>,
+    cache: Arc<PatternCache>,
+    shortlist: usize,
+    tie_break: TieBreak,
+    cancel: CancelToken,
+}
+
+impl TwoPly {
+    /// Creates a guesser that picks its words from the built-in dictionary.
+    pub fn new() -> Self {
+        Self::from_cache(PatternCache::global())
+    }
+
+    /// Creates a guesser that picks its words from the given dictionary, with a new
+    /// [`PatternCache`] for it.
+    pub fn with_dictionary(words: impl IntoIterator<Item = (&'static str, usize)>) -> Self {
+        let dictionary = Arc::new(Dictionary::with_counts(words));
+        Self::from_cache(Arc::new(PatternCache::for_dictionary(dictionary)))
+    }
+
+    /// Creates a guesser that picks its words from the dictionary of `cache`, with the frequency
+    /// counts it was built with, rather than reading the word list again.
+    pub fn from_cache(cache: Arc<PatternCache>) -> Self {
+        Self::with_counts(cache.dictionary().counts(), cache)
+    }
+
+    /// Creates a guesser that picks its words from the given dictionary, looking their patterns
+    /// up in `cache`, e.g. one loaded with [`PatternCache::load`].
+    ///
+    /// `words` must be in the same order as in `cache`, or this fails with
+    /// [`Error::CacheMismatch`](crate::Error::CacheMismatch) if there are not as many.
+    pub fn with_cache(
+        words: impl IntoIterator<Item = (&'static str, usize)>,
+        cache: Arc<PatternCache>,
+    ) -> crate::Result<Self> {
+        let counts = cache.counts_of(words)?;
+        Ok(Self::with_counts(counts, cache))
+    }
+
+    fn with_counts(counts: Arc<[usize]>, cache: Arc<PatternCache>) -> Self {
+        TwoPly {
+            remaining: Candidates::all(counts.len()),
+            seen: 0,
+            counts,
+            cache,
+            shortlist: DEFAULT_SHORTLIST,
+            tie_break: TieBreak::default(),
+            cancel: CancelToken::new(),
+        }
+    }
+
+    /// Sets how many of the guesses revealing the most information on their own are looked ahead
+    /// from. Looking ahead is expensive, so the best guesses are first shortlisted by their
+    /// immediate entropy. At least one guess is always looked ahead from, so a shortlist of zero
+    /// is taken as one.
+    pub fn with_shortlist(mut self, shortlist: usize) -> Self {
+        self.shortlist = shortlist.max(1);
+        self
+    }
+
+    /// Sets how to pick among guesses that score the same. Defaults to
+    /// [`TieBreak::PossibleAnswer`].
+    pub fn with_tie_break(mut self, tie_break: TieBreak) -> Self {
+        self.tie_break = tie_break;
+        self
+    }
+
+    /// Only considers the given words as possible answers, see [`Candidates::retain_words`].
+    pub fn with_answers<'a>(mut self, answers: impl IntoIterator<Item = &'a str>) -> Self {
+        self.remaining.retain_words(&self.cache, answers);
+        self
+    }
+}
+
+impl Default for TwoPly {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Guesser for TwoPly {
+    fn guess(&mut self, history: &History) -> Cow<'static, str> {
+        self.remaining
+            .catch_up(&self.cache, history, &mut self.seen);
+        if self.remaining.is_empty() {
+            return no_candidate_left(&self.cache, history);
+        }
+
+        let cache = &self.cache;
+        let candidates: Vec<(usize, f64)> = self
+            .remaining
+            .iter()
+            .map(|index| (index, self.counts[index] as f64))
+            .collect();
+        let total: f64 = candidates.iter().map(|&(_, weight)| weight).sum();
+
+        let preference = |guess| {
+            self.tie_break
+                .preference(cache.word(guess), self.counts[guess], true)
+        };
+        let mut shortlist: Vec<(usize, f64)> = candidates
+            .iter()
+            .map(|&(guess, _)| (guess, entropy(cache.row(guess), &candidates)))
+            .collect();
+        shortlist.sort_by(|a, b| {
+            (b.1, preference(b.0))
+                .partial_cmp(&(a.1, preference(a.0)))
+                .expect("entropy is never NaN")
+        });
+        shortlist.truncate(self.shortlist);
+
+        // Several guesses often split the candidates into some of the same buckets, so remember
+        // the best follow-up for each bucket. The guesses are looked ahead from one after the
+        // other, which keeps the memo to this thread and lets a cancelled search stop after any
+        // of them, while the new buckets of every guess are scored on all cores.
+        let mut memo: HashMap<Vec<usize>, f64> = HashMap::new();
+        let mut best: Option<(usize, (f64, _))> = None;
+        for (i, &(guess, immediate)) in shortlist.iter().enumerate() {
+            if i > 0 && self.cancel.is_cancelled() {
+                break;
+            }
+
+            let patterns = cache.row(guess);
+            let mut buckets: BTreeMap<u8, Bucket> = BTreeMap::new();
+            for &(candidate, weight) in &candidates {
+                buckets
+                    .entry(patterns[candidate])
+                    .or_default()
+                    .push((candidate, weight));
+            }
+            let buckets: Vec<(Vec<usize>, Bucket)> = buckets
+                .into_values()
+                .map(|bucket| {
+                    (
+                        bucket.iter().map(|&(candidate, _)| candidate).collect(),
+                        bucket,
+                    )
+                })
+                .collect();
+
+            let unknown: Vec<&(Vec<usize>, Bucket)> = buckets
+                .iter()
+                .filter(|(key, _)| !memo.contains_key(key))
+                .collect();
+            let scores = score_buckets(cache, &unknown);
+            memo.extend(unknown.into_iter().map(|(key, _)| key.clone()).zip(scores));
+
+            // Summed in the order of the patterns, so that the score is the same on any number
+            // of threads.
+            let follow_up: f64 = buckets
+                .iter()
+                .map(|(key, bucket)| {
+                    let p = bucket.iter().map(|&(_, weight)| weight).sum::<f64>() / total;
+                    p * memo[key]
+                })
+                .sum();
+
+            let next = (guess, (immediate + follow_up, preference(guess)));
+            best = match best {
+                Some(best) if next.1 <= best.1 => Some(best),
+                _ => Some(next),
+            };
+        }
+        let (best, (goodness, _)) = best.expect("there is always at least one remaining candidate");
+        tracing::debug!(
+            guess = cache.word(best),
+            entropy = goodness,
+            candidates = candidates.len(),
+            shortlist = shortlist.len(),
+            "picked the guess revealing the most information over two guesses"
+        );
+
+        Cow::Borrowed(cache.word(best))
+    }
+
+    fn guess_with_explanation(
+        &mut self,
+        history: &History,
+    ) -> (Cow<'static, str>, Option<Explanation>) {
+        let guess = self.guess(history);
+        let explanation = explain(&self.cache, &self.remaining, |_| 1.0, &guess);
+        (guess, Some(explanation))
+    }
+
+    fn set_cancel_token(&mut self, token: CancelToken) {
+        self.cancel = token;
+    }
+
+    fn remaining(&self) -> Option<Vec<&'static str>> {
+        Some(self.remaining.words(&self.cache))
+    }
+}
+
+/// Returns the [`best_follow_up`] of every bucket, in order, scoring the buckets on all cores with
+/// the `parallel` feature.
+fn score_buckets(cache: &PatternCache, buckets: &[&(Vec<usize>, Bucket)]) -> Vec<f64> {
+    #[cfg(feature = "parallel")]
+    {
+        use rayon::prelude::*;
+
+        buckets
+            .par_iter()
+            .map(|(_, bucket)| best_follow_up(cache, bucket))
+            .collect()
+    }
+
+    #[cfg(not(feature = "parallel"))]
+    {
+        buckets
+            .iter()
+            .map(|(_, bucket)| best_follow_up(cache, bucket))
+            .collect()
+    }
+}
+
+/// Returns the most information a guess out of `bucket` can reveal about which word of the bucket
+/// is the answer.
+fn best_follow_up(cache: &PatternCache, bucket: &[(usize, f64)]) -> f64 {
+    if bucket.len() <= 1 {
+        return 0.0;
+    }
+
+    bucket
+        .iter()
+        .map(|&(guess, _)| entropy(cache.row(guess), bucket))
+        .fold(0.0, f64::max)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::{best_follow_up, entropy, TwoPly};
+    use crate::algorithms::PatternCache;
+    use crate::{Correctness, Guesser, History, WORDS};
+
+    #[test]
+    fn looks_past_the_most_informative_guess() {
+        let words = [
+            "arbor", "asset", "chain", "dogma", "gawky", "glyph", "reset", "sheen", "sumac",
+            "uncle", "villa", "wrest",
+        ];
+        let dictionary = || words.iter().map(|&word| (word, 1));
+        let one_ply = TwoPly::with_dictionary(dictionary())
+            .with_shortlist(1)
+            .guess(&History::new());
+        let two_ply = TwoPly::with_dictionary(dictionary()).guess(&History::new());
+        // `sumac` reveals the most on its own, but leaves some answers that no second guess tells
+        // apart, unlike other guesses.
+        assert_eq!(one_ply, "sumac");
+        assert_ne!(two_ply, "sumac");
+
+        let cache: PatternCache = PatternCache::new(words.iter().copied());
+        let candidates: Vec<(usize, f64)> = (0..words.len()).map(|i| (i, 1.0)).collect();
+        let immediate = |word| entropy(cache.row(cache.index(word).unwrap()), &candidates);
+        assert!(immediate(&two_ply) < immediate("sumac"));
+    }
+
+    #[test]
+    fn best_follow_up_is_the_most_informative_guess_of_the_bucket() {
+        let cache: PatternCache = PatternCache::new(WORDS.iter().map(|&(word, _)| word));
+        let weighted: Vec<(usize, f64)> = WORDS
+            .iter()
+            .enumerate()
+            .map(|(i, &(_, count))| (i, count as f64))
+            .collect();
+
+        // Every guess splits the words into buckets, scored by trying every guess of the bucket.
+        for (guess, _) in WORDS {
+            let mut buckets: HashMap<[Correctness; 5], Vec<(usize, f64)>> = HashMap::new();
+            for &(candidate, weight) in &weighted {
+                let mask = Correctness::compute(WORDS[candidate].0, guess);
+                buckets.entry(mask).or_default().push((candidate, weight));
+            }
+
+            for bucket in buckets.values() {
+                let total: f64 = bucket.iter().map(|&(_, weight)| weight).sum();
+                let brute_force = bucket
+                    .iter()
+                    .map(|&(follow_up, _)| {
+                        let mut split: HashMap<[Correctness; 5], f64> = HashMap::new();
+                        for &(candidate, weight) in bucket {
+                            let mask = Correctness::compute(WORDS[candidate].0, WORDS[follow_up].0);
+                            *split.entry(mask).or_default() += weight;
+                        }
+                        split
+                            .values()
+                            .map(|&weight| -(weight / total) * (weight / total).log2())
+                            .sum::<f64>()
+                    })
+                    .fold(0.0, f64::max);
+                let follow_up = best_follow_up(&cache, bucket);
+                assert!(
+                    (follow_up - brute_force).abs() < 1e-9,
+                    "{} != {} after {}",
+                    follow_up,
+                    brute_force,
+                    guess
+                );
+            }
+        }
+    }
+}
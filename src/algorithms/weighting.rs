@@ -0,0 +1,137 @@
+use std::collections::BTreeMap;
+#[cfg(feature = "std")]
+use std::fs;
+use std::io;
+#[cfg(feature = "std")]
+use std::path::Path;
+
+/// Factors the prior likelihood of words is multiplied by, e.g. to favor the words the official
+/// game actually picks as answers rather than the most common words.
+///
+/// They are read from a CSV file with a header row, such as historical solve data with a row per
+/// past answer, out of a `word` column and a numeric column named when loading it. A column of how
+/// many times a word was the answer, or of how hard players found it, lets the solver learn what
+/// the answers tend to look like. Words without a row keep the `unlisted` factor, 1 unless set
+/// otherwise.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Weighting {
+    factors: BTreeMap<String, f64>,
+    unlisted: f64,
+}
+
+impl Weighting {
+    /// Reads the factors out of the `column` column of the CSV file at `path`.
+    #[cfg(feature = "std")]
+    pub fn load(path: impl AsRef<Path>, column: &str) -> io::Result<Self> {
+        Self::parse(&fs::read_to_string(path)?, column)
+    }
+
+    /// Parses the contents of a CSV file, see [`Weighting::load`].
+    ///
+    /// Fields may not be quoted, and every factor must be a non-negative number.
+    pub fn parse(contents: &str, column: &str) -> io::Result<Self> {
+        let invalid = |message: String| io::Error::new(io::ErrorKind::InvalidData, message);
+
+        let mut lines = contents.lines().filter(|line| !line.trim().is_empty());
+        let header: Vec<&str> = lines
+            .next()
+            .ok_or_else(|| invalid("the file has no header row".to_string()))?
+            .split(',')
+            .map(str::trim)
+            .collect();
+        let position = |name: &str| {
+            header
+                .iter()
+                .position(|&field| field == name)
+                .ok_or_else(|| invalid(format!("the file has no `{}` column", name)))
+        };
+        let (word, factor) = (position("word")?, position(column)?);
+
+        let mut factors = BTreeMap::new();
+        for (i, line) in lines.enumerate() {
+            let fields: Vec<&str> = line.split(',').map(str::trim).collect();
+            let row = i + 2;
+            let (Some(&word), Some(&value)) = (fields.get(word), fields.get(factor)) else {
+                return Err(invalid(format!("row {} is missing fields", row)));
+            };
+            let value: f64 = value
+                .parse()
+                .ok()
+                .filter(|value: &f64| value.is_finite() && *value >= 0.0)
+                .ok_or_else(|| {
+                    invalid(format!(
+                        "`{}` on row {} is not a non-negative number",
+                        value, row
+                    ))
+                })?;
+            factors.insert(word.to_lowercase(), value);
+        }
+
+        Ok(Self {
+            factors,
+            unlisted: 1.0,
+        })
+    }
+
+    /// Sets the factor of the words the file has no row for. Defaults to 1, which keeps their
+    /// prior as is.
+    pub fn with_unlisted(mut self, unlisted: f64) -> Self {
+        self.unlisted = unlisted;
+        self
+    }
+
+    /// Returns the factor the prior likelihood of `word` is multiplied by.
+    pub fn factor(&self, word: &str) -> f64 {
+        self.factors.get(word).copied().unwrap_or(self.unlisted)
+    }
+
+    /// Returns a hash of the factors that is stable across runs and platforms, to tell apart
+    /// guesses computed with different weightings.
+    pub fn fingerprint(&self) -> u64 {
+        crate::fnv1a(
+            self.factors
+                .iter()
+                .flat_map(|(word, factor)| {
+                    word.bytes()
+                        .chain(std::iter::once(b' '))
+                        .chain(factor.to_le_bytes())
+                })
+                .chain(self.unlisted.to_le_bytes()),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Weighting;
+
+    #[test]
+    fn parse() {
+        let weighting = Weighting::parse(
+            "date,word,picks\n2022-01-01,Cigar,2\n2022-01-02,rebut,0.5\n",
+            "picks",
+        )
+        .unwrap()
+        .with_unlisted(0.1);
+        assert_eq!(weighting.factor("cigar"), 2.0);
+        assert_eq!(weighting.factor("rebut"), 0.5);
+        assert_eq!(weighting.factor("sissy"), 0.1);
+        assert_ne!(
+            weighting.fingerprint(),
+            weighting.clone().with_unlisted(1.0).fingerprint()
+        );
+    }
+
+    #[test]
+    fn invalid() {
+        for contents in [
+            "",
+            "word\ncigar\n",
+            "word,picks\ncigar\n",
+            "word,picks\ncigar,-1\n",
+        ] {
+            let e = Weighting::parse(contents, "picks").unwrap_err();
+            assert_eq!(e.kind(), std::io::ErrorKind::InvalidData, "{}", contents);
+        }
+    }
+}
@@ -0,0 +1,171 @@
+//! Statistics about the words of a dictionary, to help curating custom word lists, and about the
+//! games played over them.
+
+use std::collections::HashMap;
+
+use crate::algorithms::{entropy, PatternCache};
+use crate::{History, Transcript};
+
+/// Returns how many words have each letter at each position, indexed by position and then by
+/// letter (`a` is 0). Characters other than lowercase ASCII letters are not counted.
+pub fn letter_frequencies<'a, const N: usize>(
+    words: impl IntoIterator<Item = &'a str>,
+) -> [[usize; 26]; N] {
+    let mut frequencies = [[0; 26]; N];
+    for word in words {
+        for (position, letter) in word.bytes().take(N).enumerate() {
+            if letter.is_ascii_lowercase() {
+                frequencies[position][(letter - b'a') as usize] += 1;
+            }
+        }
+    }
+    frequencies
+}
+
+/// Returns every pair of adjacent letters along with the number of times it appears, the most
+/// common first. Pairs appearing as often are in alphabetical order.
+pub fn bigrams<'a>(words: impl IntoIterator<Item = &'a str>) -> Vec<(String, usize)> {
+    let mut counts: HashMap<&str, usize> = HashMap::new();
+    for word in words {
+        // Letters may take more than one byte, so pairs are sliced between every other boundary.
+        let boundaries: Vec<usize> = word
+            .char_indices()
+            .map(|(i, _)| i)
+            .chain(std::iter::once(word.len()))
+            .collect();
+        for pair in boundaries.windows(3) {
+            *counts.entry(&word[pair[0]..pair[2]]).or_default() += 1;
+        }
+    }
+
+    let mut bigrams: Vec<_> = counts
+        .into_iter()
+        .map(|(bigram, count)| (bigram.to_string(), count))
+        .collect();
+    bigrams.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    bigrams
+}
+
+/// Returns the `n` first guesses revealing the most information about the answer, along with that
+/// information in bits, when every word of the dictionary is as likely as its frequency count.
+///
+/// This scores every word against every other one, so it takes a few seconds on large
+/// dictionaries.
+pub fn best_openers(words: &[(&'static str, usize)], n: usize) -> Vec<(&'static str, f64)> {
+    let cache: PatternCache = PatternCache::new(words.iter().map(|&(word, _)| word));
+    let candidates: Vec<(usize, f64)> = words
+        .iter()
+        .enumerate()
+        .map(|(index, &(_, count))| (index, count as f64))
+        .collect();
+
+    let mut openers: Vec<(&'static str, f64)> = candidates
+        .iter()
+        .map(|&(guess, _)| (cache.word(guess), entropy(cache.row(guess), &candidates)))
+        .collect();
+    openers.sort_by(|a, b| b.1.partial_cmp(&a.1).expect("entropy is never NaN"));
+    openers.truncate(n);
+    openers
+}
+
+/// How many answers were left at a turn of a game, see [`turns`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TurnEntropy {
+    /// The number of guesses played so far, 0 before the first one.
+    pub turn: usize,
+    /// How many of the answers match every guess played so far.
+    pub remaining: usize,
+    /// The entropy of the remaining answers in bits, every one of them being as likely.
+    pub entropy: f64,
+}
+
+/// Returns how many of the `answers` were left before the first guess of the game and after every
+/// guess, to plot how much information a guesser gains at every turn.
+///
+/// The answers are weighed the same rather than by the prior of the guesser, so that guessers with
+/// different priors are measured alike.
+pub fn turns<const N: usize>(answers: &[&str], transcript: &Transcript<N>) -> Vec<TurnEntropy> {
+    let mut history = History::new();
+    let mut remaining: Vec<&str> = answers.to_vec();
+    let mut turns = Vec::with_capacity(transcript.guesses.len() + 1);
+    for turn in 0..=transcript.guesses.len() {
+        if turn > 0 {
+            history.push(transcript.guesses[turn - 1].clone());
+            remaining.retain(|answer| history.matches(answer));
+        }
+        turns.push(TurnEntropy {
+            turn,
+            remaining: remaining.len(),
+            entropy: (remaining.len().max(1) as f64).log2(),
+        });
+    }
+    turns
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{best_openers, bigrams, letter_frequencies, turns};
+    use crate::{Correctness, Guess, Transcript};
+
+    #[test]
+    fn frequencies() {
+        let frequencies: [[usize; 26]; 3] = letter_frequencies(vec!["cab", "cat", "dog"]);
+        assert_eq!(frequencies[0][2], 2);
+        assert_eq!(frequencies[0][3], 1);
+        assert_eq!(frequencies[1][0], 2);
+        assert_eq!(frequencies[2][(b'g' - b'a') as usize], 1);
+        assert_eq!(frequencies.iter().flatten().sum::<usize>(), 9);
+    }
+
+    #[test]
+    fn bigram_counts() {
+        assert_eq!(
+            bigrams(vec!["cab", "cat", "dog"]),
+            vec![
+                ("ca".to_string(), 2),
+                ("ab".to_string(), 1),
+                ("at".to_string(), 1),
+                ("do".to_string(), 1),
+                ("og".to_string(), 1),
+            ]
+        );
+    }
+
+    #[test]
+    fn unicode_bigrams() {
+        assert_eq!(
+            bigrams(vec!["año"]),
+            vec![("añ".to_string(), 1), ("ño".to_string(), 1)]
+        );
+    }
+
+    #[test]
+    fn openers() {
+        // Only "efgab" tells every word apart.
+        let words = [("abcde", 1), ("abcdf", 1), ("abcdg", 1), ("efgab", 1)];
+        let openers = best_openers(&words, 2);
+        assert_eq!(openers.len(), 2);
+        assert_eq!(openers[0].0, "efgab");
+        assert_eq!(openers[0].1, 2.0);
+    }
+
+    #[test]
+    fn entropy_per_turn() {
+        let answers = ["fight", "light", "might", "right", "wrong"];
+        let transcript: Transcript = Transcript {
+            answer: "right".to_string(),
+            guesses: ["fight", "right"]
+                .iter()
+                .map(|&guess| Guess::new(guess, Correctness::compute("right", guess)))
+                .collect(),
+            score: Some(2),
+        };
+
+        let turns = turns(&answers, &transcript);
+        let remaining: Vec<usize> = turns.iter().map(|turn| turn.remaining).collect();
+        assert_eq!(remaining, [5, 3, 1]);
+        assert_eq!(turns[1].turn, 1);
+        assert!((turns[0].entropy - 5f64.log2()).abs() < 1e-9);
+        assert_eq!(turns[2].entropy, 0.0);
+    }
+}
@@ -0,0 +1,225 @@
+//! The engine behind the interactive frontends, where the player reports every guess they play
+//! along with the colors the game showed, and a guesser suggests the next one.
+
+use std::borrow::Cow;
+
+use crate::{
+    validate_word, Correctness, Estimate, Explanation, Guess, GuessError, Guesser, History, Wordle,
+};
+
+/// Suggests the guesses of a game the player is playing elsewhere, e.g. in the official game.
+pub struct Assistant<G> {
+    guesser: G,
+    history: History,
+    /// The game being played, to check that the words played are in its dictionary.
+    wordle: Option<Wordle>,
+    explain: bool,
+    /// The suggestion for the next guess, once the guesser was asked for it.
+    suggestion: Option<Suggestion>,
+}
+
+/// What the [`Assistant`] suggests playing next.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Suggestion {
+    pub guess: Cow<'static, str>,
+    /// Why the guesser picked the guess, if explanations were asked for and the guesser can
+    /// explain itself.
+    pub explanation: Option<Explanation>,
+    /// The words the guesser still considers possible answers, if it keeps track of them.
+    pub remaining: Option<Vec<&'static str>>,
+    /// How the guesser expects the rest of the game to go, if it can tell.
+    pub estimate: Option<Estimate>,
+}
+
+/// Why a line entered by the player is not a turn of the game.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum InvalidTurn {
+    /// The line is neither a word followed by its colors nor only colors.
+    #[error("expected a word and its colors, e.g. `crane GYXXX`")]
+    Malformed,
+    /// The word played may not be guessed.
+    #[error(transparent)]
+    Guess(GuessError),
+    /// The colors are not five of G, Y and X.
+    #[error("`{0}` is not five colors out of G, Y and X")]
+    Mask(String),
+    /// No answer reveals the colors, see [`Correctness::is_possible`].
+    #[error("no answer shows the colors `{0}`")]
+    Impossible(String),
+    /// None of the words the guesser considers agrees with the colors of every turn so far, e.g.
+    /// because a turn was mistyped.
+    #[error("no word agrees with all the colors so far")]
+    Contradictory,
+}
+
+impl<G: Guesser> Assistant<G> {
+    pub fn new(guesser: G) -> Self {
+        Self {
+            guesser,
+            history: History::new(),
+            wordle: None,
+            explain: false,
+            suggestion: None,
+        }
+    }
+
+    /// Also explains every suggestion, see [`Guesser::guess_with_explanation`].
+    pub fn with_explanations(mut self, explain: bool) -> Self {
+        self.explain = explain;
+        self
+    }
+
+    /// Also rejects the words played that `wordle` does not allow, e.g. typos, rather than only
+    /// those that are not five lowercase letters.
+    pub fn with_wordle(mut self, wordle: Wordle) -> Self {
+        self.wordle = Some(wordle);
+        self
+    }
+
+    /// Returns the guesses played so far.
+    pub fn history(&self) -> &History {
+        &self.history
+    }
+
+    /// Returns whether the last guess played was the answer.
+    pub fn is_solved(&self) -> bool {
+        self.history
+            .last()
+            .is_some_and(|guess| guess.mask == [Correctness::Correct; 5])
+    }
+
+    /// Returns what to play next, asking the guesser the first time it is called every turn.
+    pub fn suggestion(&mut self) -> &Suggestion {
+        let (guesser, history, explain) = (&mut self.guesser, &self.history, self.explain);
+        self.suggestion.get_or_insert_with(|| {
+            let (guess, explanation) = if explain {
+                guesser.guess_with_explanation(history)
+            } else {
+                (guesser.guess(history), None)
+            };
+            Suggestion {
+                guess,
+                explanation,
+                remaining: guesser.remaining(),
+                estimate: guesser.estimate(history),
+            }
+        })
+    }
+
+    /// Records the turn the player entered as a line: the word they played followed by the
+    /// colors the game showed, e.g. `crane GYXXX`, or only the colors if they played the
+    /// suggested word.
+    pub fn enter(&mut self, line: &str) -> Result<(), InvalidTurn> {
+        let tokens: Vec<&str> = line.split_whitespace().collect();
+        let (word, mask) = match tokens.as_slice() {
+            [mask] => (self.suggestion().guess.to_string(), *mask),
+            [word, mask] => (word.to_lowercase(), *mask),
+            _ => return Err(InvalidTurn::Malformed),
+        };
+
+        match &self.wordle {
+            Some(wordle) => wordle.validate_guess(&word),
+            None => validate_word::<5>(&word),
+        }
+        .map_err(InvalidTurn::Guess)?;
+        let mask = match Correctness::parse_mask(mask) {
+            Some(colors) if Correctness::is_possible(colors) => colors,
+            Some(_) => return Err(InvalidTurn::Impossible(mask.into())),
+            None => return Err(InvalidTurn::Mask(mask.into())),
+        };
+
+        let guess = Guess::new(word, mask);
+        if let Some(candidates) = self.guesser.remaining() {
+            // The candidates may date back a few turns, but the history rules out the rest.
+            let mut history = self.history.clone();
+            history.push(guess.clone());
+            if !candidates
+                .iter()
+                .any(|candidate| history.matches(candidate))
+            {
+                return Err(InvalidTurn::Contradictory);
+            }
+        }
+
+        self.play(guess);
+        Ok(())
+    }
+
+    /// Records a guess the player played.
+    pub fn play(&mut self, guess: Guess) {
+        self.history.push(guess);
+        self.suggestion = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Assistant, InvalidTurn};
+    use crate::algorithms::Minimax;
+    use crate::{GuessError, Wordle, WORDS};
+
+    #[test]
+    fn suggests_until_solved() {
+        let mut assistant = Assistant::new(Minimax::with_dictionary(WORDS.iter().copied()));
+        let first = assistant.suggestion().guess.clone();
+        assert_eq!(assistant.suggestion().remaining.as_ref().unwrap().len(), 12);
+
+        assistant.enter("blush xxyxg").unwrap();
+        assert_ne!(assistant.suggestion().guess, first);
+        assert_eq!(assistant.suggestion().guess, "humph");
+        assert!(!assistant.is_solved());
+
+        assistant.enter("GGGGG").unwrap();
+        assert!(assistant.is_solved());
+        assert_eq!(assistant.history()[1].word, "humph");
+    }
+
+    #[test]
+    fn rejects_invalid_turns() {
+        let mut assistant = Assistant::new(Minimax::with_dictionary(WORDS.iter().copied()));
+        assert_eq!(assistant.enter(""), Err(InvalidTurn::Malformed));
+        assert_eq!(
+            assistant.enter("blus GGGGG"),
+            Err(InvalidTurn::Guess(GuessError::WrongLength {
+                guess: "blus".to_string(),
+                expected: 5
+            }))
+        );
+        assert_eq!(
+            assistant.enter("blu5h GGGGG"),
+            Err(InvalidTurn::Guess(GuessError::NotLetters(
+                "blu5h".to_string()
+            )))
+        );
+        assert_eq!(
+            assistant.enter("blush GGGG"),
+            Err(InvalidTurn::Mask("GGGG".to_string()))
+        );
+        assert_eq!(
+            assistant.enter("blush GGYGG"),
+            Err(InvalidTurn::Impossible("GGYGG".to_string()))
+        );
+        assert!(assistant.history().is_empty());
+
+        assistant.enter("cigar XXXXX").unwrap();
+        assistant.enter("sissy XXXXX").unwrap();
+        assert_eq!(
+            assistant.enter("humph XXXXX"),
+            Err(InvalidTurn::Contradictory)
+        );
+        assert_eq!(assistant.history().len(), 2);
+        assert_eq!(assistant.suggestion().guess, "humph");
+    }
+    #[test]
+    fn rejects_words_out_of_the_dictionary() {
+        let assistant = Assistant::new(Minimax::with_dictionary(WORDS.iter().copied()));
+        let mut assistant = assistant.with_wordle(Wordle::with_dictionary(WORDS.iter().copied()));
+        assert_eq!(
+            assistant.enter("crane XXXXX"),
+            Err(InvalidTurn::Guess(GuessError::NotInDictionary(
+                "crane".to_string()
+            )))
+        );
+        assert!(assistant.enter("cigar XXXXX").is_ok());
+    }
+}
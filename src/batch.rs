@@ -0,0 +1,347 @@
+//! Playing a game for every answer of a list, e.g. to evaluate a guesser on the whole dictionary.
+
+use std::collections::BTreeMap;
+use std::io::{self, Write};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
+
+use indicatif::{ProgressBar, ProgressDrawTarget, ProgressStyle};
+
+use crate::analysis;
+use crate::stats::Stats;
+use crate::{GameResult, Guesser, PlayError, Transcript, Wordle};
+
+const TEMPLATE: &str = "{wide_bar} {pos}/{len} games, average score {msg}, {eta} left";
+
+/// Plays a game for every answer of a list with a fresh guesser each, and optionally reports
+/// progress on the terminal as it goes.
+pub struct Batch<'w, const N: usize = 5> {
+    wordle: &'w Wordle<N>,
+    progress: bool,
+    jobs: usize,
+}
+
+impl<'w, const N: usize> Batch<'w, N> {
+    pub fn new(wordle: &'w Wordle<N>) -> Self {
+        Self {
+            wordle,
+            progress: false,
+            jobs: 1,
+        }
+    }
+
+    /// Plays this many games at once, each on its own thread with its own guesser. Defaults to 1,
+    /// which plays every game on the current thread.
+    ///
+    /// This is independent of the `parallel` feature, which scores the guesses of a single game
+    /// on all cores.
+    pub fn with_jobs(mut self, jobs: usize) -> Self {
+        self.jobs = jobs.max(1);
+        self
+    }
+
+    /// Whether to draw a progress bar on stderr, with the number of games played, the average
+    /// score so far and an estimate of the time left.
+    pub fn with_progress(mut self, progress: bool) -> Self {
+        self.progress = progress;
+        self
+    }
+
+    /// Plays a game for every answer with a guesser from `mk`, calls `on_game` with the outcome of
+    /// each, and returns the outcomes of all the games.
+    ///
+    /// Games that cannot be played count as failures and do not stop the batch. Anything
+    /// `on_game` prints to the terminal does not mess with the progress bar. `on_game` is called
+    /// on the current thread in the order of the answers, even when games are played on several
+    /// threads.
+    pub fn run<'a, G: Guesser<N>>(
+        &self,
+        answers: &[&'a str],
+        mk: impl Fn() -> G + Sync,
+        mut on_game: impl FnMut(&'a str, Result<GameResult<N>, PlayError>),
+    ) -> Stats {
+        let bar = if self.progress {
+            let bar = ProgressBar::with_draw_target(
+                Some(answers.len() as u64),
+                ProgressDrawTarget::stderr(),
+            );
+            bar.set_style(ProgressStyle::with_template(TEMPLATE).expect("the template is valid"));
+            bar.set_message("-");
+            // Games can take a while, keep the estimate ticking in the meantime.
+            bar.enable_steady_tick(Duration::from_millis(200));
+            bar
+        } else {
+            ProgressBar::hidden()
+        };
+
+        let mut stats = Stats::new();
+        let mut finish = |answer, result: Result<GameResult<N>, PlayError>| {
+            stats.record(result.as_ref().ok().and_then(|game| game.transcript.score));
+            bar.suspend(|| on_game(answer, result));
+
+            bar.inc(1);
+            if let Some(mean) = stats.mean() {
+                bar.set_message(format!("{:.3}", mean));
+            }
+        };
+        let play = |answer| self.wordle.play_recorded(answer, mk());
+
+        if self.jobs == 1 {
+            for &answer in answers {
+                finish(answer, play(answer));
+            }
+        } else {
+            let next = AtomicUsize::new(0);
+            let (sender, receiver) = mpsc::channel();
+            thread::scope(|scope| {
+                for _ in 0..self.jobs.min(answers.len()) {
+                    let (play, next, sender) = (&play, &next, sender.clone());
+                    scope.spawn(move || loop {
+                        let i = next.fetch_add(1, Ordering::Relaxed);
+                        let Some(&answer) = answers.get(i) else {
+                            break;
+                        };
+                        if sender.send((i, play(answer))).is_err() {
+                            break;
+                        }
+                    });
+                }
+                drop(sender);
+
+                // Games finish in any order, hold them back until the ones before are reported.
+                let mut finished = BTreeMap::new();
+                let mut reported = 0;
+                for (i, result) in receiver {
+                    finished.insert(i, result);
+                    while let Some(result) = finished.remove(&reported) {
+                        finish(answers[reported], result);
+                        reported += 1;
+                    }
+                }
+            });
+        }
+
+        bar.finish_and_clear();
+        stats
+    }
+}
+
+/// Writes the outcome of every game as a row of CSV, for analysis in a spreadsheet.
+///
+/// The columns are the answer, the score (empty if the answer was not found), the guesses
+/// separated by spaces, and the time the game took in milliseconds.
+pub struct CsvWriter<W> {
+    inner: W,
+}
+
+impl<W: Write> CsvWriter<W> {
+    /// Writes the header row to `inner`.
+    pub fn new(mut inner: W) -> io::Result<Self> {
+        writeln!(inner, "answer,score,guesses,elapsed_ms")?;
+        Ok(Self { inner })
+    }
+
+    pub fn write<const N: usize>(&mut self, game: &GameResult<N>) -> io::Result<()> {
+        let transcript = &game.transcript;
+        let guesses: Vec<&str> = transcript
+            .guesses
+            .iter()
+            .map(|guess| guess.word.as_ref())
+            .collect();
+        writeln!(
+            self.inner,
+            "{},{},{},{:.3}",
+            escape(&transcript.answer),
+            transcript
+                .score
+                .map_or_else(String::new, |score| score.to_string()),
+            escape(&guesses.join(" ")),
+            game.elapsed.as_secs_f64() * 1000.0
+        )
+    }
+
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+/// Writes how many answers every game left at every turn as rows of CSV in long format, to plot
+/// how much information every algorithm gains per turn, see [`analysis::turns`].
+///
+/// The columns are the algorithm, the answer, the turn (0 before the first guess), the guess played
+/// at that turn, how many answers were left after it, their entropy in bits, and the information
+/// the guess revealed in bits. The guess and the information are empty at turn 0.
+pub struct TurnsCsvWriter<W> {
+    inner: W,
+    answers: Vec<&'static str>,
+}
+
+impl<W: Write> TurnsCsvWriter<W> {
+    /// Writes the header row to `inner`. Candidates are counted out of `answers`, e.g. the answers
+    /// of the game.
+    pub fn new(mut inner: W, answers: &[&'static str]) -> io::Result<Self> {
+        writeln!(
+            inner,
+            "algorithm,answer,turn,guess,remaining,entropy,information"
+        )?;
+        Ok(Self {
+            inner,
+            answers: answers.to_vec(),
+        })
+    }
+
+    pub fn write<const N: usize>(
+        &mut self,
+        algorithm: &str,
+        transcript: &Transcript<N>,
+    ) -> io::Result<()> {
+        let mut before = None;
+        for turn in analysis::turns(&self.answers, transcript) {
+            let guess = turn
+                .turn
+                .checked_sub(1)
+                .map_or("", |i| transcript.guesses[i].word.as_ref());
+            let information = before.map_or_else(String::new, |before: f64| {
+                format!("{:.4}", before - turn.entropy)
+            });
+            writeln!(
+                self.inner,
+                "{},{},{},{},{},{:.4},{}",
+                escape(algorithm),
+                escape(&transcript.answer),
+                turn.turn,
+                escape(guess),
+                turn.remaining,
+                turn.entropy,
+                information
+            )?;
+            before = Some(turn.entropy);
+        }
+        Ok(())
+    }
+
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+/// Quotes a field if it contains characters that have a meaning in CSV.
+fn escape(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::{Batch, CsvWriter, TurnsCsvWriter};
+    use crate::{GameResult, Guess, Transcript, Wordle};
+
+    #[test]
+    fn plays_every_answer() {
+        let wordle = Wordle::with_dictionary(vec![("right", 1), ("wrong", 1)]);
+        let mut played = Vec::new();
+        let stats = Batch::new(&wordle).run(
+            &["right", "wrong", "rights"],
+            || guesser!(|_history| { "right".into() }),
+            |answer, result| played.push((answer, result.is_ok())),
+        );
+
+        assert_eq!(
+            played,
+            vec![("right", true), ("wrong", true), ("rights", false)]
+        );
+        assert_eq!(stats.games(), 3);
+        assert_eq!(stats.histogram(), &[1]);
+        // The guesser never finds "wrong", so its game fails after `DEFAULT_MAX_GUESSES`
+        // guesses, and "rights" cannot be an answer.
+        assert_eq!(stats.failures(), 2);
+    }
+
+    #[test]
+    fn csv() {
+        let mut csv = CsvWriter::new(Vec::new()).unwrap();
+        let solved = GameResult {
+            transcript: Transcript {
+                answer: "right".to_string(),
+                guesses: vec![
+                    Guess::new("wrong", mask!(W M W W M)),
+                    Guess::new("right", mask!(C C C C C)),
+                ],
+                score: Some(2),
+            },
+            elapsed: Duration::from_micros(1500),
+        };
+        csv.write(&solved).unwrap();
+        let unsolved = GameResult {
+            transcript: Transcript {
+                answer: "a,b".to_string(),
+                guesses: vec![],
+                score: None,
+            },
+            elapsed: Duration::ZERO,
+        };
+        csv.write::<5>(&unsolved).unwrap();
+
+        assert_eq!(
+            String::from_utf8(csv.into_inner()).unwrap(),
+            "answer,score,guesses,elapsed_ms\n\
+             right,2,wrong right,1.500\n\
+             \"a,b\",,,0.000\n"
+        );
+    }
+
+    #[test]
+    fn turns_csv() {
+        let mut csv = TurnsCsvWriter::new(Vec::new(), &["girth", "right", "wrong"]).unwrap();
+        let transcript = Transcript {
+            answer: "right".to_string(),
+            guesses: vec![
+                Guess::new("wrong", mask!(W M W W M)),
+                Guess::new("right", mask!(C C C C C)),
+            ],
+            score: Some(2),
+        };
+        csv.write("naive", &transcript).unwrap();
+
+        assert_eq!(
+            String::from_utf8(csv.into_inner()).unwrap(),
+            "algorithm,answer,turn,guess,remaining,entropy,information\n\
+             naive,right,0,,3,1.5850,\n\
+             naive,right,1,wrong,2,1.0000,0.5850\n\
+             naive,right,2,right,1,0.0000,1.0000\n"
+        );
+    }
+
+    #[test]
+    fn plays_on_several_threads() {
+        fn shareable<T: Send + Sync>() {}
+        shareable::<Wordle>();
+
+        const WORDS: [&str; 7] = [
+            "cigar", "rebut", "sissy", "humph", "awake", "blush", "focal",
+        ];
+        let wordle = Wordle::with_dictionary(WORDS.iter().map(|&word| (word, 1)));
+        let mut played = Vec::new();
+        let stats = Batch::new(&wordle).with_jobs(3).run(
+            &WORDS,
+            || guesser!(|history| { WORDS[history.len()].into() }),
+            |answer, result| played.push((answer, result.unwrap().transcript.score)),
+        );
+
+        // Games are reported in the order of the answers, whichever thread played them.
+        let expected: Vec<_> = WORDS
+            .iter()
+            .enumerate()
+            .map(|(i, &answer)| (answer, Some(i + 1)))
+            .collect();
+        assert_eq!(played, expected);
+        assert_eq!(stats.histogram(), &[1; 7]);
+    }
+}
@@ -0,0 +1,175 @@
+//! Replaying many games with a guesser to measure how fast and how well it plays.
+
+use std::borrow::Cow;
+use std::fmt;
+use std::time::{Duration, Instant};
+
+use crate::stats::Stats;
+use crate::{
+    builtin_answers, CancelToken, Estimate, Explanation, Guesser, History, PlayError, Wordle,
+};
+
+/// Performance of a guesser over a batch of games.
+#[derive(Debug, Clone)]
+pub struct Report {
+    /// Outcome of every game.
+    pub stats: Stats,
+    /// Number of guesses made across all games, including those of unsolved games.
+    pub guesses: usize,
+    /// Time spent coming up with guesses, across all games.
+    pub guessing: Duration,
+    /// Wall-clock time of the whole run, including setting up every guesser.
+    pub elapsed: Duration,
+}
+
+impl Report {
+    /// Average time spent coming up with a single guess.
+    pub fn per_guess(&self) -> Duration {
+        if self.guesses == 0 {
+            return Duration::ZERO;
+        }
+
+        self.guessing / self.guesses as u32
+    }
+
+    /// Average number of guesses over the solved games.
+    pub fn average_score(&self) -> Option<f64> {
+        self.stats.mean()
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "played {} games in {:.2?} ({:.2?} per guess)",
+            self.stats.games(),
+            self.elapsed,
+            self.per_guess()
+        )?;
+        write!(f, "{}", self.stats)
+    }
+}
+
+/// Plays a game for every answer, with a fresh guesser from `mk` for each, and reports how it went.
+///
+/// Stops at the first game that cannot be played.
+pub fn run<'a, G, const N: usize>(
+    wordle: &Wordle<N>,
+    answers: impl IntoIterator<Item = &'a str>,
+    mut mk: impl FnMut() -> G,
+) -> Result<Report, PlayError>
+where
+    G: Guesser<N>,
+{
+    let start = Instant::now();
+    let mut report = Report {
+        stats: Stats::new(),
+        guesses: 0,
+        guessing: Duration::ZERO,
+        elapsed: Duration::ZERO,
+    };
+
+    for answer in answers {
+        let mut guesser = Timed {
+            inner: (mk)(),
+            guesses: 0,
+            elapsed: Duration::ZERO,
+        };
+        let score = wordle.play(answer, &mut guesser)?;
+        report.stats.record(score);
+        report.guesses += guesser.guesses;
+        report.guessing += guesser.elapsed;
+    }
+
+    report.elapsed = start.elapsed();
+    Ok(report)
+}
+
+/// Replays every answer of the official game with the built-in dictionary.
+pub fn replay_answers<G: Guesser>(mk: impl FnMut() -> G) -> Result<Report, PlayError> {
+    run(&Wordle::new(), builtin_answers(), mk)
+}
+
+/// Keeps track of the time spent in a guesser.
+struct Timed<G> {
+    inner: G,
+    guesses: usize,
+    elapsed: Duration,
+}
+
+impl<G: Guesser<N>, const N: usize> Guesser<N> for Timed<G> {
+    fn guess(&mut self, history: &History<N>) -> Cow<'static, str> {
+        let start = Instant::now();
+        let guess = self.inner.guess(history);
+        self.elapsed += start.elapsed();
+        self.guesses += 1;
+        guess
+    }
+
+    fn guess_with_explanation(
+        &mut self,
+        history: &History<N>,
+    ) -> (Cow<'static, str>, Option<Explanation<N>>) {
+        let start = Instant::now();
+        let explained = self.inner.guess_with_explanation(history);
+        self.elapsed += start.elapsed();
+        self.guesses += 1;
+        explained
+    }
+
+    fn set_hard_mode(&mut self, hard_mode: bool) {
+        self.inner.set_hard_mode(hard_mode)
+    }
+
+    fn set_cancel_token(&mut self, token: CancelToken) {
+        self.inner.set_cancel_token(token)
+    }
+
+    fn remaining(&self) -> Option<Vec<&'static str>> {
+        self.inner.remaining()
+    }
+
+    fn rank(&self, history: &History<N>, k: usize) -> Vec<(&'static str, f64)> {
+        self.inner.rank(history, k)
+    }
+
+    fn estimate(&self, history: &History<N>) -> Option<Estimate> {
+        self.inner.estimate(history)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::run;
+    use crate::Wordle;
+
+    #[test]
+    fn counts_guesses() {
+        let wordle = Wordle::with_dictionary(vec![("right", 1), ("wrong", 1)]);
+        let report = run(&wordle, vec!["right", "wrong"], || {
+            guesser!(|history| {
+                if history.is_empty() {
+                    return "wrong".into();
+                }
+
+                "right".into()
+            })
+        })
+        .unwrap();
+
+        assert_eq!(report.guesses, 3);
+        assert_eq!(report.stats.histogram(), &[1, 1]);
+        assert_eq!(report.average_score(), Some(1.5));
+        assert!(report.per_guess() <= report.guessing);
+    }
+
+    #[test]
+    fn stops_on_errors() {
+        let wordle = Wordle::with_dictionary(vec![("right", 1)]);
+        let report = run(&wordle, vec!["right"], || {
+            guesser!(|_history| { "wrong".into() })
+        });
+        assert!(report.is_err());
+    }
+}
@@ -0,0 +1,254 @@
+//! Configuring a [`Wordle`] game with every setting checked up front, see [`WordleBuilder`].
+
+#[cfg(feature = "std")]
+use std::fs;
+#[cfg(feature = "std")]
+use std::path::PathBuf;
+
+use crate::{DictVersion, Wordle, DEFAULT_MAX_GUESSES};
+
+/// Builds a [`Wordle`] game out of a dictionary, an answer list and the rules, reporting what is
+/// wrong with them rather than panicking like the constructors of [`Wordle`].
+///
+/// The words of the game have `N` letters, e.g. `Wordle::<6>::builder()` for six-letter words,
+/// which requires a dictionary since the built-in one only has five-letter words.
+#[derive(Debug, Clone)]
+pub struct WordleBuilder<const N: usize = 5> {
+    dictionary: Source,
+    answers: Option<Vec<&'static str>>,
+    hard_mode: bool,
+    max_guesses: Option<usize>,
+}
+
+#[derive(Debug, Clone)]
+enum Source {
+    Builtin(DictVersion),
+    Words(Vec<(&'static str, usize)>),
+    #[cfg(feature = "std")]
+    File(PathBuf),
+}
+
+/// What is wrong with the settings of a [`WordleBuilder`].
+#[derive(Debug, thiserror::Error)]
+pub enum BuildError {
+    /// The dictionary file could not be read, or is not a valid dictionary, e.g.
+    /// [`Error::Io`](crate::Error::Io) or [`Error::Dictionary`](crate::Error::Dictionary) with the
+    /// lines at fault.
+    #[error("could not load the dictionary: {0}")]
+    Dictionary(#[source] Box<crate::Error>),
+    /// The built-in dictionary only has five-letter words.
+    #[error("the built-in dictionary has no {len}-letter words, a dictionary is required")]
+    NoBuiltinDictionary { len: usize },
+    /// The dictionary has no words.
+    #[error("the dictionary is empty")]
+    EmptyDictionary,
+    /// A word of the dictionary or an answer does not have the number of letters of the game.
+    #[error("`{word}` does not have {expected} letters")]
+    WrongLength { word: String, expected: usize },
+    /// An answer is not in the dictionary, so it could never be guessed.
+    #[error("the answer `{0}` is not in the dictionary")]
+    UnknownAnswer(String),
+    /// The answer list is empty.
+    #[error("the answer list is empty")]
+    NoAnswers,
+    /// The game would not let the guesser play a single guess.
+    #[error("the game must allow at least one guess")]
+    NoGuesses,
+}
+
+impl<const N: usize> Wordle<N> {
+    /// Configures a game whose settings are checked when it is built, starting from the rules of
+    /// [`Wordle::new`]: the latest built-in word lists, normal mode and [`DEFAULT_MAX_GUESSES`].
+    pub fn builder() -> WordleBuilder<N> {
+        WordleBuilder {
+            dictionary: Source::Builtin(DictVersion::default()),
+            answers: None,
+            hard_mode: false,
+            max_guesses: Some(DEFAULT_MAX_GUESSES),
+        }
+    }
+}
+
+impl WordleBuilder {
+    /// Configures a game of five-letter words, see [`Wordle::builder`].
+    pub fn new() -> Self {
+        Wordle::builder()
+    }
+}
+
+impl<const N: usize> WordleBuilder<N> {
+    /// Uses the built-in word lists of the given version, see [`Wordle::with_builtin`].
+    pub fn builtin(mut self, version: DictVersion) -> Self {
+        self.dictionary = Source::Builtin(version);
+        self
+    }
+
+    /// Uses the given words as the dictionary. Unless set with [`WordleBuilder::answers`], every
+    /// word of it is a possible answer.
+    pub fn dictionary(mut self, words: impl IntoIterator<Item = (&'static str, usize)>) -> Self {
+        self.dictionary = Source::Words(words.into_iter().collect());
+        self
+    }
+
+    /// Reads the dictionary from a file when building, see [`load_dictionary`](crate::load_dictionary).
+    /// Unless set with [`WordleBuilder::answers`], every word of it is a possible answer.
+    #[cfg(feature = "std")]
+    pub fn dictionary_file(mut self, path: impl Into<PathBuf>) -> Self {
+        self.dictionary = Source::File(path.into());
+        self
+    }
+
+    /// Sets the words that may be the answer, which must all be in the dictionary.
+    pub fn answers(mut self, answers: impl IntoIterator<Item = &'static str>) -> Self {
+        self.answers = Some(answers.into_iter().collect());
+        self
+    }
+
+    /// See [`Wordle::with_hard_mode`].
+    pub fn hard_mode(mut self, hard_mode: bool) -> Self {
+        self.hard_mode = hard_mode;
+        self
+    }
+
+    /// See [`Wordle::with_max_guesses`].
+    pub fn max_guesses(mut self, max_guesses: Option<usize>) -> Self {
+        self.max_guesses = max_guesses;
+        self
+    }
+
+    /// Checks the settings and builds the game.
+    pub fn build(self) -> Result<Wordle<N>, BuildError> {
+        if self.max_guesses == Some(0) {
+            return Err(BuildError::NoGuesses);
+        }
+
+        let (words, builtin_answers) = match self.dictionary {
+            Source::Builtin(_) if N != 5 => return Err(BuildError::NoBuiltinDictionary { len: N }),
+            Source::Builtin(version) => (
+                version.dictionary().collect(),
+                Some(version.answers().collect()),
+            ),
+            Source::Words(words) => (words, None),
+            #[cfg(feature = "std")]
+            Source::File(path) => {
+                let invalid = |e: crate::Error| BuildError::Dictionary(Box::new(e));
+                let contents = fs::read_to_string(path).map_err(|e| invalid(e.into()))?;
+                let contents: &'static str = Box::leak(contents.into_boxed_str());
+                let words = crate::parse_dictionary_of(contents, N).map_err(invalid)?;
+                (words, None)
+            }
+        };
+        if words.is_empty() {
+            return Err(BuildError::EmptyDictionary);
+        }
+        let wrong_length = |word: &str| BuildError::WrongLength {
+            word: word.to_string(),
+            expected: N,
+        };
+        if let Some(&(word, _)) = words.iter().find(|(word, _)| word.chars().count() != N) {
+            return Err(wrong_length(word));
+        }
+
+        let wordle = Wordle::with_dictionary(words);
+        let wordle = match self.answers.or(builtin_answers) {
+            Some(answers) => {
+                if let Some(answer) = answers.iter().find(|answer| answer.chars().count() != N) {
+                    return Err(wrong_length(answer));
+                }
+                if let Some(answer) = answers.iter().find(|answer| !wordle.is_allowed(answer)) {
+                    return Err(BuildError::UnknownAnswer(answer.to_string()));
+                }
+                if answers.is_empty() {
+                    return Err(BuildError::NoAnswers);
+                }
+                wordle.with_answers(answers)
+            }
+            None => wordle,
+        };
+
+        Ok(wordle
+            .with_hard_mode(self.hard_mode)
+            .with_max_guesses(self.max_guesses))
+    }
+}
+
+impl Default for WordleBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BuildError, WordleBuilder};
+    use crate::{DictVersion, Wordle};
+
+    #[test]
+    fn builds_the_official_game() {
+        let wordle = WordleBuilder::new()
+            .builtin(DictVersion::Original)
+            .hard_mode(true)
+            .max_guesses(Some(6))
+            .build()
+            .unwrap();
+        assert_eq!(wordle.answers().len(), 2315);
+        assert_eq!(wordle.max_guesses(), Some(6));
+        assert_eq!(
+            WordleBuilder::new().build().unwrap().answers(),
+            Wordle::new().answers()
+        );
+    }
+
+    #[test]
+    fn builds_games_of_other_lengths() {
+        let wordle = Wordle::<6>::builder()
+            .dictionary(vec![("bright", 1), ("fright", 1)])
+            .answers(vec!["fright"])
+            .build()
+            .unwrap();
+        assert_eq!(wordle.answers(), &["fright"]);
+        assert!(wordle.is_allowed("bright"));
+    }
+
+    #[test]
+    fn reports_invalid_settings() {
+        let words = vec![("fight", 1), ("light", 1)];
+        let build = |builder: WordleBuilder| builder.build().err().expect("invalid settings");
+        assert!(matches!(
+            build(WordleBuilder::new().dictionary(vec![])),
+            BuildError::EmptyDictionary
+        ));
+        assert!(matches!(
+            build(WordleBuilder::new().dictionary(vec![("fights", 1)])),
+            BuildError::WrongLength { expected: 5, .. }
+        ));
+        assert!(matches!(
+            build(WordleBuilder::new().dictionary(words.clone()).answers(vec!["might"])),
+            BuildError::UnknownAnswer(answer) if answer == "might"
+        ));
+        assert!(matches!(
+            build(WordleBuilder::new().dictionary(words).answers(vec![])),
+            BuildError::NoAnswers
+        ));
+        assert!(matches!(
+            build(WordleBuilder::new().max_guesses(Some(0))),
+            BuildError::NoGuesses
+        ));
+        assert!(matches!(
+            Wordle::<6>::builder().build(),
+            Err(BuildError::NoBuiltinDictionary { len: 6 })
+        ));
+        assert!(matches!(
+            build(WordleBuilder::new().dictionary_file("/nonexistent/dictionary.txt")),
+            BuildError::Dictionary(e) if matches!(*e, crate::Error::Io(_))
+        ));
+
+        let path = std::env::temp_dir().join("crane-builder-invalid-dictionary.txt");
+        std::fs::write(&path, "right 10\nwrong\n").unwrap();
+        assert!(matches!(
+            build(WordleBuilder::new().dictionary_file(&path)),
+            BuildError::Dictionary(e)
+                if matches!(&*e, crate::Error::Dictionary(invalid) if invalid.diagnostics[0].line == 2)
+        ));
+    }
+}
@@ -0,0 +1,64 @@
+//! Cooperative cancellation of long searches, e.g. to bound how long a guesser thinks.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Instant;
+
+/// Tells a long search to stop and settle for the best result found so far, either once
+/// [`CancelToken::cancel`] is called on any of its clones or once its deadline passes.
+///
+/// Searches only check the token between steps, so they still finish the step they are in and
+/// always return a result. A new token is never cancelled.
+#[derive(Debug, Clone, Default)]
+pub struct CancelToken {
+    cancelled: Arc<AtomicBool>,
+    deadline: Option<Instant>,
+}
+
+impl CancelToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Also cancels the token once `deadline` passes.
+    pub fn with_deadline(mut self, deadline: Instant) -> Self {
+        self.deadline = Some(deadline);
+        self
+    }
+
+    /// Cancels the token and all its clones, e.g. from another thread.
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+            || self
+                .deadline
+                .is_some_and(|deadline| Instant::now() >= deadline)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use super::CancelToken;
+
+    #[test]
+    fn cancel() {
+        let token = CancelToken::new();
+        let clone = token.clone();
+        assert!(!clone.is_cancelled());
+        token.cancel();
+        assert!(clone.is_cancelled());
+    }
+
+    #[test]
+    fn deadline() {
+        let now = Instant::now();
+        assert!(CancelToken::new().with_deadline(now).is_cancelled());
+        let later = now + Duration::from_secs(3600);
+        assert!(!CancelToken::new().with_deadline(later).is_cancelled());
+    }
+}
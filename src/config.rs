@@ -0,0 +1,139 @@
+//! Defaults read from a `crane.toml` file, which the options given on the command line override.
+//!
+//! ```toml
+//! algorithm = "minimax"
+//! dictionary = "words.txt"
+//! hard = true
+//! openers = ["crane", "moist"]
+//! output = "json"
+//! threads = 4
+//! ```
+
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+
+use crate::algorithms::lookup;
+
+/// The name of the configuration file looked for in the current directory.
+pub const FILE_NAME: &str = "crane.toml";
+
+/// Defaults for the command line, all of which are optional.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// The name or alias of the guessing algorithm, see [`registry`](crate::algorithms::registry()).
+    pub algorithm: Option<String>,
+    /// The dictionary file to read instead of the built-in one.
+    pub dictionary: Option<PathBuf>,
+    /// Whether to play in hard mode.
+    pub hard: Option<bool>,
+    /// The guesses to always open with, in order, before the algorithm takes over.
+    pub openers: Option<Vec<String>>,
+    /// The format of reports, e.g. `text`, `json` or `csv`.
+    pub output: Option<String>,
+    /// How many threads score guesses with the `parallel` feature, rather than one per core.
+    pub threads: Option<usize>,
+}
+
+/// Why the contents of a configuration file are not a valid [`Config`].
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum ConfigError {
+    /// The file is not valid TOML, or has unknown fields or fields of the wrong type.
+    #[error("{0}")]
+    Syntax(String),
+    /// The algorithm is not in the [`registry`](crate::algorithms::registry()).
+    #[error("unknown algorithm `{0}`")]
+    UnknownAlgorithm(String),
+    /// Guesses cannot be scored on zero threads.
+    #[error("there must be at least one thread")]
+    NoThreads,
+}
+
+impl Config {
+    /// Reads the configuration file at `path`, whose relative paths are relative to the directory
+    /// of the file.
+    pub fn load(path: impl AsRef<Path>) -> crate::Result<Self> {
+        let path = path.as_ref();
+        let mut config = Self::parse(&std::fs::read_to_string(path)?)?;
+        if let (Some(dictionary), Some(dir)) = (&mut config.dictionary, path.parent()) {
+            *dictionary = dir.join(&*dictionary);
+        }
+        Ok(config)
+    }
+
+    /// Reads [`FILE_NAME`] from the current directory, if there is one.
+    pub fn find() -> crate::Result<Option<Self>> {
+        let path = Path::new(FILE_NAME);
+        if !path.exists() {
+            return Ok(None);
+        }
+        Self::load(path).map(Some)
+    }
+
+    /// Parses the contents of a configuration file, see [`Config::load`].
+    pub fn parse(contents: &str) -> Result<Self, ConfigError> {
+        let config: Self =
+            toml::from_str(contents).map_err(|e| ConfigError::Syntax(e.to_string()))?;
+        if let Some(algorithm) = &config.algorithm {
+            if lookup(algorithm).is_none() {
+                return Err(ConfigError::UnknownAlgorithm(algorithm.clone()));
+            }
+        }
+        if config.threads == Some(0) {
+            return Err(ConfigError::NoThreads);
+        }
+        Ok(config)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::{Config, ConfigError};
+
+    #[test]
+    fn parse() {
+        let config = Config::parse(
+            "algorithm = \"minimax\"\ndictionary = \"words.txt\"\nhard = true\nopeners = [\"crane\"]\noutput = \"json\"\nthreads = 4\n",
+        )
+        .unwrap();
+        assert_eq!(
+            config,
+            Config {
+                algorithm: Some("minimax".to_string()),
+                dictionary: Some(PathBuf::from("words.txt")),
+                hard: Some(true),
+                openers: Some(vec!["crane".to_string()]),
+                output: Some("json".to_string()),
+                threads: Some(4),
+            }
+        );
+        assert_eq!(Config::parse("").unwrap(), Config::default());
+    }
+
+    #[test]
+    fn invalid() {
+        assert_eq!(
+            Config::parse("algorithm = \"oracle\""),
+            Err(ConfigError::UnknownAlgorithm("oracle".to_string()))
+        );
+        assert_eq!(Config::parse("threads = 0"), Err(ConfigError::NoThreads));
+        for contents in ["hard = \"yes\"", "colour = true"] {
+            let e = Config::parse(contents).unwrap_err();
+            assert!(matches!(e, ConfigError::Syntax(_)), "{}", contents);
+        }
+    }
+
+    #[test]
+    fn relative_to_the_file() {
+        let dir = std::env::temp_dir().join("crane-config-test");
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(super::FILE_NAME);
+        std::fs::write(&path, "dictionary = \"words.txt\"").unwrap();
+        let config = Config::load(&path);
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(config.unwrap().dictionary, Some(dir.join("words.txt")));
+    }
+}
@@ -0,0 +1,296 @@
+//! What the hints of a game so far reveal about the answer, letter by letter.
+
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+
+use crate::{Correctness, Guess};
+
+/// Everything a history of guesses reveals about the answer: which letters are known at which
+/// position, which letters cannot be at a position, and how many times each letter appears at
+/// least and at most.
+///
+/// A word matches the constraints exactly when it matches every guess of the history, but
+/// checking it against the constraints costs the same no matter how long the history is.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Constraints<const N: usize = 5> {
+    /// The green letter at every position, if any.
+    fixed: [Option<char>; N],
+    /// Letters that were yellow or gray at every position.
+    excluded: [Vec<char>; N],
+    /// How many times each letter appears at least, counting greens and yellows.
+    min: BTreeMap<char, usize>,
+    /// How many times each letter appears at most, known once a copy of the letter was gray.
+    max: BTreeMap<char, usize>,
+}
+
+/// How many times a letter appears in the answer, as far as the hints tell.
+///
+/// A letter found twice in a guess, once yellow and once gray, appears exactly once, and so does a
+/// letter found once and gray at another position of the same guess.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct LetterCount {
+    /// The number of copies of the letter that were green or yellow in a single guess, at most.
+    pub min: usize,
+    /// The number of copies of the letter found by a guess where another copy was gray, if any.
+    pub max: Option<usize>,
+}
+
+impl LetterCount {
+    /// Returns how many times the letter appears, if the hints tell exactly.
+    pub fn exact(&self) -> Option<usize> {
+        self.max.filter(|&max| max == self.min)
+    }
+
+    /// Returns whether the answer may have `count` copies of the letter.
+    pub fn allows(&self, count: usize) -> bool {
+        count >= self.min && self.max.is_none_or(|max| count <= max)
+    }
+}
+
+impl<const N: usize> Constraints<N> {
+    /// Creates constraints that every word of `N` letters satisfies.
+    pub fn new() -> Self {
+        Self {
+            fixed: [None; N],
+            excluded: core::array::from_fn(|_| Vec::new()),
+            min: BTreeMap::new(),
+            max: BTreeMap::new(),
+        }
+    }
+
+    /// Gathers the constraints revealed by every guess of `history`.
+    pub fn from_history(history: &[Guess<N>]) -> Self {
+        let mut constraints = Self::new();
+        for guess in history {
+            constraints.add(guess);
+        }
+        constraints
+    }
+
+    /// Narrows the constraints down with the hints revealed by `guess`.
+    pub fn add(&mut self, guess: &Guess<N>) {
+        let mut found: BTreeMap<char, usize> = BTreeMap::new();
+        let mut gray: Vec<char> = Vec::new();
+        for (i, (letter, &mask)) in guess.word.chars().zip(&guess.mask).enumerate() {
+            match mask {
+                Correctness::Correct => {
+                    self.fixed[i] = Some(letter);
+                    *found.entry(letter).or_default() += 1;
+                }
+                Correctness::Misplaced => {
+                    self.exclude(i, letter);
+                    *found.entry(letter).or_default() += 1;
+                }
+                Correctness::Wrong => {
+                    self.exclude(i, letter);
+                    gray.push(letter);
+                }
+            }
+        }
+
+        for (&letter, &count) in &found {
+            let min = self.min.entry(letter).or_default();
+            *min = (*min).max(count);
+        }
+        // A gray copy of a letter means the answer has no more copies of it than were found.
+        for letter in gray {
+            let count = found.get(&letter).copied().unwrap_or(0);
+            let max = self.max.entry(letter).or_insert(count);
+            *max = (*max).min(count);
+        }
+    }
+
+    fn exclude(&mut self, position: usize, letter: char) {
+        if !self.excluded[position].contains(&letter) {
+            self.excluded[position].push(letter);
+        }
+    }
+
+    /// Returns the letter known to be at `position`, if any.
+    pub fn fixed(&self, position: usize) -> Option<char> {
+        self.fixed[position]
+    }
+
+    /// Returns whether `letter` is known not to be at `position`.
+    pub fn is_excluded(&self, position: usize, letter: char) -> bool {
+        self.excluded[position].contains(&letter)
+    }
+
+    /// Returns how many times `letter` appears in the answer at least.
+    pub fn min_count(&self, letter: char) -> usize {
+        self.min.get(&letter).copied().unwrap_or(0)
+    }
+
+    /// Returns how many times `letter` appears in the answer at most, if known.
+    pub fn max_count(&self, letter: char) -> Option<usize> {
+        self.max.get(&letter).copied()
+    }
+
+    /// Returns how many times `letter` appears in the answer.
+    pub fn letter_count(&self, letter: char) -> LetterCount {
+        LetterCount {
+            min: self.min_count(letter),
+            max: self.max_count(letter),
+        }
+    }
+
+    /// Returns how many times every letter that was guessed appears in the answer, in
+    /// alphabetical order.
+    pub fn letter_counts(&self) -> BTreeMap<char, LetterCount> {
+        self.min
+            .keys()
+            .chain(self.max.keys())
+            .map(|&letter| (letter, self.letter_count(letter)))
+            .collect()
+    }
+
+    /// Returns whether `word` could still be the answer, i.e. whether it matches every guess the
+    /// constraints were gathered from.
+    pub fn matches(&self, word: &str) -> bool {
+        if word.chars().count() != N {
+            return false;
+        }
+
+        for (i, letter) in word.chars().enumerate() {
+            match self.fixed[i] {
+                Some(fixed) if fixed != letter => return false,
+                Some(_) => {}
+                None if self.excluded[i].contains(&letter) => return false,
+                None => {}
+            }
+        }
+
+        self.min
+            .iter()
+            .all(|(&letter, &min)| count(word, letter) >= min)
+            && self
+                .max
+                .iter()
+                .all(|(&letter, &max)| count(word, letter) <= max)
+    }
+
+    /// Returns whether `word` may be played in hard mode, i.e. whether it keeps every green letter
+    /// in place and contains every yellow letter.
+    pub fn hard_mode_allows(&self, word: &str) -> bool {
+        word.chars().count() == N
+            && word
+                .chars()
+                .zip(&self.fixed)
+                .all(|(letter, fixed)| fixed.is_none_or(|fixed| fixed == letter))
+            && self
+                .min
+                .iter()
+                .all(|(&letter, &min)| count(word, letter) >= min)
+    }
+}
+
+impl<const N: usize> Default for Constraints<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn count(word: &str, letter: char) -> usize {
+    word.chars().filter(|&c| c == letter).count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Constraints, LetterCount};
+    use crate::{Correctness, Guess};
+    use proptest::prelude::*;
+
+    #[test]
+    fn gathers_hints() {
+        let c: Constraints = Constraints::from_history(&[
+            Guess::new("crane", mask!(W M W W M)),
+            Guess::new("route", mask!(C W W W M)),
+            Guess::new("rerun", mask!(C C W W W)),
+        ]);
+        assert_eq!(c.fixed(0), Some('r'));
+        assert_eq!(c.fixed(1), Some('e'));
+        assert_eq!(c.fixed(2), None);
+        assert!(c.is_excluded(1, 'r'));
+        // The second "r" of "rerun" was gray, so there is exactly one.
+        assert_eq!(c.min_count('r'), 1);
+        assert_eq!(c.max_count('r'), Some(1));
+        assert_eq!(c.max_count('c'), Some(0));
+        assert_eq!(c.max_count('e'), None);
+
+        assert!(c.matches("rebel"));
+        assert!(!c.matches("refer"));
+        assert!(!c.matches("rebels"));
+    }
+
+    #[test]
+    fn letter_counts() {
+        // One "e" is yellow and the other one gray, so the answer has exactly one.
+        let c: Constraints = Constraints::from_history(&[Guess::new("geese", mask!(W M W W W))]);
+        assert_eq!(c.letter_count('e').exact(), Some(1));
+        assert_eq!(c.letter_count('g').exact(), Some(0));
+        assert_eq!(c.letter_counts().len(), 3);
+
+        // Two copies were found, and none was gray.
+        let c: Constraints = Constraints::from_history(&[Guess::new("eerie", mask!(C M W W W))]);
+        let e = c.letter_count('e');
+        assert_eq!(
+            e,
+            LetterCount {
+                min: 2,
+                max: Some(2)
+            }
+        );
+        let c: Constraints = Constraints::from_history(&[Guess::new("melee", mask!(W M W C W))]);
+        assert_eq!(c.letter_count('e').exact(), Some(2));
+        let c: Constraints = Constraints::from_history(&[Guess::new("sheen", mask!(W W C M W))]);
+        assert_eq!(c.letter_count('e'), LetterCount { min: 2, max: None });
+        assert!(c.letter_count('e').allows(3));
+        assert!(!c.letter_count('e').allows(1));
+    }
+
+    #[test]
+    fn hard_mode_allows() {
+        let c: Constraints = Constraints::from_history(&[Guess::new("crane", mask!(W M W W M))]);
+        assert!(c.hard_mode_allows("route"));
+        // Hard mode does not forbid reusing gray letters or moving yellow ones back.
+        assert!(c.hard_mode_allows("rcane"));
+        assert!(!c.hard_mode_allows("cling"));
+        assert!(!c.hard_mode_allows("abode"));
+    }
+
+    /// Five-letter words over a small alphabet, so that repeated letters are common.
+    fn word() -> impl Strategy<Value = String> {
+        "[abc]{5}"
+    }
+
+    proptest! {
+        #[test]
+        fn agrees_with_guesses(
+            answer in word(),
+            guesses in prop::collection::vec(word(), 0..4),
+            candidate in word(),
+        ) {
+            let history: Vec<Guess> = guesses
+                .into_iter()
+                .map(|guess| {
+                    let mask = Correctness::compute(&answer, &guess);
+                    Guess::new(guess, mask)
+                })
+                .collect();
+            let c = Constraints::from_history(&history);
+
+            prop_assert!(c.matches(&answer));
+            for (letter, counts) in c.letter_counts() {
+                prop_assert!(counts.allows(answer.chars().filter(|&c| c == letter).count()));
+            }
+            prop_assert_eq!(
+                c.matches(&candidate),
+                history.iter().all(|g| g.matches(&candidate))
+            );
+            prop_assert_eq!(
+                c.hard_mode_allows(&candidate),
+                history.iter().all(|g| g.hard_mode_allows(&candidate))
+            );
+        }
+    }
+}
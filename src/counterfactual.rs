@@ -0,0 +1,216 @@
+//! What a game would have looked like had another word been played at one of its turns, see
+//! [`Wordle::counterfactual`].
+
+use std::borrow::Cow;
+
+use crate::game::{answer_hash, GameState};
+use crate::{Correctness, Guess, Guesser, PlayError, Transcript, Wordle};
+
+/// A game replayed with another word at one of its turns.
+///
+/// Unless the game was solved, several answers may agree with every guess of it, and the other
+/// word would have revealed a different mask against each of them, so there is an [`Outcome`]
+/// for each.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Counterfactual<const N: usize = 5> {
+    /// The guess actually played at the turn.
+    pub actual: Guess<N>,
+    /// The answers left after the guess actually played.
+    pub actual_remaining: Vec<&'static str>,
+    /// What would have happened against every answer agreeing with the game, in alphabetical
+    /// order.
+    pub outcomes: Vec<Outcome<N>>,
+}
+
+/// What would have happened against one of the answers of a [`Counterfactual`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Outcome<const N: usize = 5> {
+    pub answer: &'static str,
+    /// The mask the other word would have revealed.
+    pub mask: [Correctness; N],
+    /// The answers left after the other word, in alphabetical order.
+    pub remaining: Vec<&'static str>,
+    /// The game up to the turn, then the other word, then the guesses of the guesser until the
+    /// end of the game.
+    pub transcript: Transcript<N>,
+}
+
+impl<const N: usize> Counterfactual<N> {
+    /// Returns how many answers the other word was expected to leave, over the answers agreeing
+    /// with the game.
+    pub fn expected_remaining(&self) -> f64 {
+        let total: usize = self
+            .outcomes
+            .iter()
+            .map(|outcome| outcome.remaining.len())
+            .sum();
+        total as f64 / self.outcomes.len() as f64
+    }
+
+    /// Returns the number of guesses the game was expected to take with the other word, over the
+    /// answers agreeing with the game, or `None` if the guesser did not find one of them.
+    pub fn expected_score(&self) -> Option<f64> {
+        let total: usize = self
+            .outcomes
+            .iter()
+            .map(|outcome| outcome.transcript.score)
+            .sum::<Option<usize>>()?;
+        Some(total as f64 / self.outcomes.len() as f64)
+    }
+}
+
+impl<const N: usize> Wordle<N> {
+    /// Replays the game of `history` with `alternative` played at `turn`, counted from 0, instead
+    /// of the word actually played, and lets guessers from `mk` play the rest of the game.
+    ///
+    /// Fails if the game has no such turn, if no answer agrees with every guess of the game, or
+    /// if `alternative` or a guess of the guessers could not be played.
+    pub fn counterfactual<G: Guesser<N>>(
+        &self,
+        history: &[Guess<N>],
+        turn: usize,
+        alternative: &str,
+        mut mk: impl FnMut() -> G,
+    ) -> Result<Counterfactual<N>, PlayError> {
+        let actual = history
+            .get(turn)
+            .ok_or_else(|| PlayError::InvalidState(format!("the game has no turn {}", turn + 1)))?;
+        let before = &history[..turn];
+        let alternative = self.check(Cow::Owned(alternative.to_string()), &before.into())?;
+
+        let agreeing = |guesses: &[Guess<N>]| -> Vec<&'static str> {
+            self.answers
+                .iter()
+                .copied()
+                .filter(|answer| guesses.iter().all(|guess| guess.matches(answer)))
+                .collect()
+        };
+        let answers = agreeing(history);
+        if answers.is_empty() {
+            return Err(PlayError::InvalidState(
+                "no answer agrees with every guess of the game".to_string(),
+            ));
+        }
+
+        let mut outcomes = Vec::new();
+        for answer in answers {
+            let mut guesses = before.to_vec();
+            guesses.push(Guess::new(
+                alternative.clone(),
+                Correctness::compute(answer, &alternative),
+            ));
+            let remaining = agreeing(&guesses);
+
+            let state = GameState {
+                answer_hash: answer_hash(answer),
+                history: guesses,
+            };
+            let mut game = self.resume(state, mk())?;
+            for step in &mut game {
+                step?;
+            }
+            let transcript = game.into_transcript();
+
+            outcomes.push(Outcome {
+                answer,
+                mask: transcript.guesses[turn].mask,
+                remaining,
+                transcript,
+            });
+        }
+
+        Ok(Counterfactual {
+            actual: actual.clone(),
+            actual_remaining: agreeing(&history[..=turn]),
+            outcomes,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::algorithms::Minimax;
+    use crate::{Correctness, Guess, PlayError, Wordle};
+
+    const WORDS: &[(&str, usize)] = &[
+        ("fight", 1),
+        ("light", 1),
+        ("might", 1),
+        ("right", 1),
+        ("sight", 1),
+        ("wrong", 1),
+    ];
+
+    fn play(answer: &str, words: &[&'static str]) -> Vec<Guess> {
+        words
+            .iter()
+            .map(|&word| Guess::new(word, Correctness::compute(answer, word)))
+            .collect()
+    }
+
+    #[test]
+    fn replays_another_guess() {
+        let wordle = Wordle::with_dictionary(WORDS.iter().copied());
+        let history = play("right", &["fight", "light", "might", "right"]);
+        let counterfactual = wordle
+            .counterfactual(&history, 1, "wrong", || {
+                guesser!(|_history| { "right".into() })
+            })
+            .unwrap();
+
+        assert_eq!(counterfactual.actual.word, "light");
+        assert_eq!(
+            counterfactual.actual_remaining,
+            vec!["might", "right", "sight"]
+        );
+        // The game was solved, so only one answer agrees with it.
+        assert_eq!(counterfactual.outcomes.len(), 1);
+        let outcome = &counterfactual.outcomes[0];
+        assert_eq!(outcome.answer, "right");
+        assert_eq!(outcome.remaining, vec!["right"]);
+        assert_eq!(outcome.transcript.score, Some(3));
+        assert_eq!(counterfactual.expected_remaining(), 1.0);
+        assert_eq!(counterfactual.expected_score(), Some(3.0));
+    }
+
+    #[test]
+    fn averages_over_the_answers_left() {
+        let wordle = Wordle::with_dictionary(WORDS.iter().copied());
+        let guesser = Minimax::with_dictionary(WORDS.iter().copied());
+        // Every other "-ight" word still agrees with the game.
+        let history = play("might", &["sight", "right"]);
+        let counterfactual = wordle
+            .counterfactual(&history, 1, "wrong", || guesser.clone())
+            .unwrap();
+
+        let answers: Vec<_> = counterfactual
+            .outcomes
+            .iter()
+            .map(|outcome| outcome.answer)
+            .collect();
+        assert_eq!(answers, vec!["fight", "light", "might"]);
+        // "wrong" only rules "right" out.
+        assert_eq!(counterfactual.expected_remaining(), 3.0);
+        let scores: usize = counterfactual
+            .outcomes
+            .iter()
+            .map(|outcome| outcome.transcript.score.unwrap())
+            .sum();
+        assert_eq!(counterfactual.expected_score(), Some(scores as f64 / 3.0));
+    }
+
+    #[test]
+    fn invalid() {
+        let wordle = Wordle::with_dictionary(WORDS.iter().copied());
+        let history = play("right", &["fight", "right"]);
+        let mk = || guesser!(|_history| { "right".into() });
+        assert!(matches!(
+            wordle.counterfactual(&history, 2, "wrong", mk),
+            Err(PlayError::InvalidState(_))
+        ));
+        assert_eq!(
+            wordle.counterfactual(&history, 0, "crane", mk),
+            Err(PlayError::NotInDictionary("crane".to_string()))
+        );
+    }
+}
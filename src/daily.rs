@@ -0,0 +1,103 @@
+//! The answer of the official puzzle of a given day.
+//!
+//! The official game published the words of the built-in answer list one per day, in order,
+//! starting on June 19, 2021, so the answer of those days is looked up without going online. That
+//! lasted until February 14, 2022 (see [`last_day`]): the New York Times then started removing
+//! words from the list, and later picked the answers by hand. With the `fetch` feature, the answer
+//! of any day can be fetched from the official game instead.
+
+use std::convert::TryFrom;
+
+use chrono::{Local, NaiveDate};
+
+use crate::builtin_answers;
+
+/// Returns the day of the first puzzle, whose answer is the first word of the built-in answer
+/// list.
+pub fn first_day() -> NaiveDate {
+    NaiveDate::from_ymd_opt(2021, 6, 19).expect("the first day is a valid date")
+}
+
+/// Returns the day of the last puzzle whose answer is known to follow the built-in answer list.
+pub fn last_day() -> NaiveDate {
+    NaiveDate::from_ymd_opt(2022, 2, 14).expect("the last day is a valid date")
+}
+
+/// Returns the local date, to look up the puzzle of the day.
+pub fn today() -> NaiveDate {
+    Local::now().date_naive()
+}
+
+/// Returns the number of the puzzle published on `date`, counting from 0 for the first one, or
+/// `None` if no puzzle was published yet on that day.
+pub fn puzzle_number(date: NaiveDate) -> Option<usize> {
+    let days = date.signed_duration_since(first_day()).num_days();
+    usize::try_from(days).ok()
+}
+
+/// Returns the answer of the puzzle published on `date` according to the built-in answer list, or
+/// `None` if the date is before the first puzzle or after the [`last_day`] the list is known to
+/// be followed.
+pub fn answer(date: NaiveDate) -> Option<&'static str> {
+    if date > last_day() {
+        return None;
+    }
+    builtin_answers().nth(puzzle_number(date)?)
+}
+
+/// Fetches the answer of the puzzle published on `date` from the official game.
+#[cfg(feature = "fetch")]
+pub fn fetch(date: NaiveDate) -> std::io::Result<String> {
+    use std::io;
+
+    #[derive(serde::Deserialize)]
+    struct Puzzle {
+        solution: String,
+    }
+
+    let url = format!(
+        "https://www.nytimes.com/svc/wordle/v2/{}.json",
+        date.format("%Y-%m-%d")
+    );
+    let body = ureq::get(&url)
+        .call()
+        .and_then(|response| response.into_body().read_to_string())
+        .map_err(io::Error::other)?;
+    let puzzle: Puzzle =
+        serde_json::from_str(&body).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+    Ok(puzzle.solution)
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+
+    use super::{answer, last_day, puzzle_number};
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn schedule() {
+        assert_eq!(puzzle_number(date(2021, 6, 19)), Some(0));
+        assert_eq!(answer(date(2021, 6, 19)), Some("cigar"));
+        assert_eq!(answer(date(2021, 6, 20)), Some("rebut"));
+        assert_eq!(puzzle_number(date(2022, 6, 19)), Some(365));
+    }
+
+    #[test]
+    fn out_of_schedule() {
+        assert_eq!(puzzle_number(date(2021, 6, 18)), None);
+        assert_eq!(answer(date(2021, 6, 18)), None);
+        assert_eq!(answer(date(2100, 1, 1)), None);
+    }
+
+    #[test]
+    fn stops_at_the_last_day() {
+        assert_eq!(answer(last_day()), Some("cynic"));
+        // The list still has words for the next days, which the official game stopped following.
+        assert_eq!(answer(date(2022, 2, 15)), None);
+        assert_eq!(puzzle_number(date(2022, 2, 15)), Some(241));
+    }
+}
@@ -0,0 +1,258 @@
+//! A SQLite database of the games played by the solver, so that runs can be compared across
+//! algorithms and versions of the code.
+//!
+//! Games are recorded with `crane play --record PATH` and queried with `crane history PATH`.
+
+use std::io;
+use std::path::Path;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use rusqlite::{params, Connection, Row};
+
+use crate::{GameResult, Guess};
+
+const SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS games (
+        id INTEGER PRIMARY KEY,
+        played_at INTEGER NOT NULL,
+        version TEXT NOT NULL,
+        algorithm TEXT NOT NULL,
+        answer TEXT NOT NULL,
+        guesses TEXT NOT NULL,
+        score INTEGER,
+        elapsed_us INTEGER NOT NULL
+    );
+    CREATE INDEX IF NOT EXISTS games_by_run ON games (version, algorithm);
+";
+
+/// The games recorded so far, see the [module documentation](self).
+pub struct Database {
+    conn: Connection,
+    version: String,
+}
+
+/// A game recorded in the [`Database`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Game {
+    /// When the game was played, in seconds since the Unix epoch.
+    pub played_at: u64,
+    /// The version of the code that played the game, see [`Database::with_version`].
+    pub version: String,
+    pub algorithm: String,
+    pub answer: String,
+    pub guesses: Vec<Guess>,
+    /// Number of guesses it took to find the answer, if it was found.
+    pub score: Option<usize>,
+    pub elapsed: Duration,
+}
+
+/// How an algorithm did over every game it played with a version of the code.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Summary {
+    pub version: String,
+    pub algorithm: String,
+    pub games: usize,
+    pub failures: usize,
+    /// Average score of the games where the answer was found.
+    pub average: Option<f64>,
+    pub elapsed: Duration,
+    /// When the first of the games was played, in seconds since the Unix epoch.
+    pub first_played_at: u64,
+}
+
+impl Database {
+    /// Opens the database at `path`, creating it if it does not exist yet.
+    pub fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+        let conn = Connection::open(path).map_err(io::Error::other)?;
+        // Every game is committed on its own, so keep commits from waiting on the disk.
+        conn.pragma_update(None, "journal_mode", "WAL")
+            .and_then(|_| conn.pragma_update(None, "synchronous", "NORMAL"))
+            .map_err(io::Error::other)?;
+        Self::with_connection(conn)
+    }
+
+    /// Opens a database that only lives in memory, e.g. for tests.
+    pub fn open_in_memory() -> io::Result<Self> {
+        Self::with_connection(Connection::open_in_memory().map_err(io::Error::other)?)
+    }
+
+    fn with_connection(conn: Connection) -> io::Result<Self> {
+        conn.execute_batch(SCHEMA).map_err(io::Error::other)?;
+        Ok(Self {
+            conn,
+            version: env!("CARGO_PKG_VERSION").to_string(),
+        })
+    }
+
+    /// Records the games played from now on under the given version, e.g. a commit hash, rather
+    /// than the version of the crate.
+    pub fn with_version(mut self, version: impl Into<String>) -> Self {
+        self.version = version.into();
+        self
+    }
+
+    /// Records a game played by `algorithm`.
+    pub fn record(&self, algorithm: &str, game: &GameResult) -> io::Result<()> {
+        let transcript = &game.transcript;
+        let played_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |since| since.as_secs());
+        let guesses =
+            serde_json::to_string(&transcript.guesses).expect("guesses are always serializable");
+        self.conn
+            .execute(
+                "INSERT INTO games (played_at, version, algorithm, answer, guesses, score, elapsed_us)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                params![
+                    played_at as i64,
+                    self.version,
+                    algorithm,
+                    transcript.answer,
+                    guesses,
+                    transcript.score.map(|score| score as i64),
+                    game.elapsed.as_micros() as i64,
+                ],
+            )
+            .map_err(io::Error::other)?;
+        Ok(())
+    }
+
+    /// Returns how every algorithm did with every version of the code, in the order they were
+    /// first played.
+    pub fn summaries(&self) -> io::Result<Vec<Summary>> {
+        let mut statement = self
+            .conn
+            .prepare(
+                "SELECT version, algorithm, COUNT(*), COUNT(score), AVG(score), SUM(elapsed_us),
+                        MIN(played_at)
+                 FROM games
+                 GROUP BY version, algorithm
+                 ORDER BY MIN(played_at), MIN(id)",
+            )
+            .map_err(io::Error::other)?;
+        let summaries = statement
+            .query_map([], |row| {
+                let games: i64 = row.get(2)?;
+                let found: i64 = row.get(3)?;
+                Ok(Summary {
+                    version: row.get(0)?,
+                    algorithm: row.get(1)?,
+                    games: games as usize,
+                    failures: (games - found) as usize,
+                    average: row.get(4)?,
+                    elapsed: Duration::from_micros(row.get::<_, i64>(5)? as u64),
+                    first_played_at: row.get::<_, i64>(6)? as u64,
+                })
+            })
+            .and_then(|rows| rows.collect())
+            .map_err(io::Error::other)?;
+        Ok(summaries)
+    }
+
+    /// Returns the `limit` games found in the fewest guesses, the fastest first on ties,
+    /// optionally only those played by `algorithm`.
+    pub fn best(&self, algorithm: Option<&str>, limit: usize) -> io::Result<Vec<Game>> {
+        self.games(algorithm, "score IS NULL, score, elapsed_us, id", limit)
+    }
+
+    /// Returns the `limit` games that took the most guesses, failures first and the slowest
+    /// first on ties, optionally only those played by `algorithm`.
+    pub fn worst(&self, algorithm: Option<&str>, limit: usize) -> io::Result<Vec<Game>> {
+        self.games(
+            algorithm,
+            "score IS NULL DESC, score DESC, elapsed_us DESC, id",
+            limit,
+        )
+    }
+
+    fn games(&self, algorithm: Option<&str>, order: &str, limit: usize) -> io::Result<Vec<Game>> {
+        let mut statement = self
+            .conn
+            .prepare(&format!(
+                "SELECT played_at, version, algorithm, answer, guesses, score, elapsed_us
+                 FROM games
+                 WHERE ?1 IS NULL OR algorithm = ?1
+                 ORDER BY {}
+                 LIMIT ?2",
+                order
+            ))
+            .map_err(io::Error::other)?;
+        let games = statement
+            .query_map(params![algorithm, limit as i64], game)
+            .and_then(|rows| rows.collect())
+            .map_err(io::Error::other)?;
+        Ok(games)
+    }
+}
+
+fn game(row: &Row<'_>) -> rusqlite::Result<Game> {
+    let guesses: String = row.get(4)?;
+    Ok(Game {
+        played_at: row.get::<_, i64>(0)? as u64,
+        version: row.get(1)?,
+        algorithm: row.get(2)?,
+        answer: row.get(3)?,
+        guesses: serde_json::from_str(&guesses).map_err(|e| {
+            rusqlite::Error::FromSqlConversionFailure(4, rusqlite::types::Type::Text, Box::new(e))
+        })?,
+        score: row.get::<_, Option<i64>>(5)?.map(|score| score as usize),
+        elapsed: Duration::from_micros(row.get::<_, i64>(6)? as u64),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::Database;
+    use crate::{Correctness, GameResult, Guess, Transcript};
+
+    fn game(answer: &str, guesses: &[&'static str], found: bool, elapsed: Duration) -> GameResult {
+        GameResult {
+            transcript: Transcript {
+                answer: answer.to_string(),
+                guesses: guesses
+                    .iter()
+                    .map(|&guess| Guess::new(guess, Correctness::compute(answer, guess)))
+                    .collect(),
+                score: Some(guesses.len()).filter(|_| found),
+            },
+            elapsed,
+        }
+    }
+
+    #[test]
+    fn queries_the_games_recorded() {
+        let db = Database::open_in_memory().unwrap();
+        let ms = Duration::from_millis;
+        db.record("naive", &game("light", &["fight", "light"], true, ms(2)))
+            .unwrap();
+        db.record("naive", &game("wight", &["fight", "sight"], false, ms(1)))
+            .unwrap();
+        let db = db.with_version("next");
+        db.record("naive", &game("light", &["light"], true, ms(3)))
+            .unwrap();
+        db.record("minimax", &game("right", &["fight", "right"], true, ms(1)))
+            .unwrap();
+
+        let summaries = db.summaries().unwrap();
+        assert_eq!(summaries.len(), 3);
+        assert_eq!(summaries[0].algorithm, "naive");
+        assert_eq!(summaries[0].games, 2);
+        assert_eq!(summaries[0].failures, 1);
+        assert_eq!(summaries[0].average, Some(2.0));
+        assert_eq!(summaries[1].version, "next");
+        assert_eq!(summaries[1].elapsed, ms(3));
+
+        let best = db.best(Some("naive"), 2).unwrap();
+        assert_eq!(best[0].score, Some(1));
+        assert_eq!(best[1].guesses[0].word, "fight");
+        let worst = db.worst(None, 1).unwrap();
+        assert_eq!(worst[0].answer, "wight");
+        assert_eq!(worst[0].score, None);
+        assert_eq!(
+            worst[0].guesses[1].mask,
+            Correctness::compute("wight", "sight")
+        );
+    }
+}
@@ -0,0 +1,265 @@
+//! The words of a dictionary in one arena, so that the rest of the solver can refer to them by
+//! index rather than by string.
+
+use std::sync::{Arc, OnceLock};
+
+use crate::{builtin_answers, builtin_dictionary, Error, Word};
+
+/// Most words a [`Dictionary`] can hold, so that their indices fit in a `u16`.
+pub const MAX_WORDS: usize = u16::MAX as usize + 1;
+
+/// The answers of the original game that the New York Times removed when it took the game over,
+/// none of which are in the built-in dictionary. Their frequency counts are not known, so they get
+/// the lowest count of the built-in dictionary.
+const REMOVED_BY_NYT: &[(&str, usize)] = &[
+    ("agora", 1),
+    ("fibre", 1),
+    ("lynch", 1),
+    ("pupal", 1),
+    ("slave", 1),
+    ("wench", 1),
+];
+
+/// A snapshot of the word lists of the official game, to compare results with those published
+/// against a specific one.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Default)]
+pub enum DictVersion {
+    /// The lists of the original game, before the New York Times removed six of its answers.
+    Original,
+    /// The lists of the New York Times game, as shipped in the built-in dictionary and answers.
+    #[default]
+    Nyt2023,
+}
+
+impl DictVersion {
+    /// Returns the words the New York Times removed that are still part of this version.
+    fn removed(self) -> &'static [(&'static str, usize)] {
+        match self {
+            DictVersion::Original => REMOVED_BY_NYT,
+            DictVersion::Nyt2023 => &[],
+        }
+    }
+
+    /// Returns the words that may be guessed, along with their frequency counts.
+    pub fn dictionary(self) -> impl Iterator<Item = (&'static str, usize)> + Clone {
+        builtin_dictionary().chain(self.removed().iter().copied())
+    }
+
+    /// Returns the answers, in the order they were published, except that the answers removed by
+    /// the New York Times come last.
+    pub fn answers(self) -> impl Iterator<Item = &'static str> + Clone {
+        builtin_answers().chain(self.removed().iter().map(|&(word, _)| word))
+    }
+}
+
+/// An arena of the words of a dictionary of `N` letters, identified by their index in it, along
+/// with their frequency counts.
+///
+/// Guessers share the dictionary of their [`PatternCache`](crate::algorithms::PatternCache)
+/// rather than reading the word list again, so that creating one for every game only clones
+/// pointers, see [`Dictionary::builtin`].
+///
+/// Indices fit in a `u16`, see [`MAX_WORDS`]. Lists of indices kept across guesses store them that
+/// way, e.g. the alphabetical order of the words or the buckets of
+/// [`Naive`](crate::algorithms::Naive), which takes a quarter of the memory of `usize` on 64-bit
+/// targets. Lists only built for the current guess hold `usize`, like the indices handed out.
+/// Words are looked up by binary search over their indices sorted alphabetically, rather than
+/// through a map keyed by string.
+pub struct Dictionary<const N: usize = 5> {
+    words: Vec<&'static str>,
+    /// Every word made of lowercase ASCII letters, whose masks can be computed on their bytes.
+    letters: Vec<Option<Word<N>>>,
+    /// The indices of the words, in the alphabetical order of the words.
+    sorted: Vec<u16>,
+    counts: Arc<[usize]>,
+}
+
+impl<const N: usize> Dictionary<N> {
+    /// Creates an arena of `words`, indexed in the order they come in, each with a frequency
+    /// count of 1.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are more than [`MAX_WORDS`] words or if a word is not `N` letters long,
+    /// see [`Dictionary::try_new`].
+    pub fn new(words: impl IntoIterator<Item = &'static str>) -> Self {
+        Self::with_counts(words.into_iter().map(|word| (word, 1)))
+    }
+
+    /// Like [`Dictionary::new`], but fails with [`Error::TooManyWords`] or
+    /// [`Error::WordLength`] rather than panicking.
+    pub fn try_new(words: impl IntoIterator<Item = &'static str>) -> crate::Result<Self> {
+        Self::try_with_counts(words.into_iter().map(|word| (word, 1)))
+    }
+
+    /// Creates an arena of `words` along with their frequency counts, indexed in the order they
+    /// come in.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are more than [`MAX_WORDS`] words or if a word is not `N` letters long,
+    /// see [`Dictionary::try_with_counts`].
+    pub fn with_counts(words: impl IntoIterator<Item = (&'static str, usize)>) -> Self {
+        Self::try_with_counts(words).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Like [`Dictionary::with_counts`], but fails with [`Error::TooManyWords`] or
+    /// [`Error::WordLength`] rather than panicking.
+    pub fn try_with_counts(
+        words: impl IntoIterator<Item = (&'static str, usize)>,
+    ) -> crate::Result<Self> {
+        let (words, counts): (Vec<_>, Vec<_>) = words.into_iter().unzip();
+        if words.len() > MAX_WORDS {
+            return Err(Error::TooManyWords {
+                len: words.len(),
+                max: MAX_WORDS,
+            });
+        }
+        if let Some(word) = words.iter().find(|word| word.chars().count() != N) {
+            return Err(Error::WordLength {
+                word: word.to_string(),
+                len: N,
+            });
+        }
+
+        let mut sorted: Vec<u16> = (0..words.len()).map(|i| i as u16).collect();
+        sorted.sort_by_key(|&i| words[i as usize]);
+        Ok(Self {
+            letters: words.iter().map(|word| Word::new(word)).collect(),
+            words,
+            sorted,
+            counts: counts.into(),
+        })
+    }
+}
+
+impl Dictionary {
+    /// The built-in dictionary, read once and shared by every guesser in the process.
+    pub fn builtin() -> Arc<Self> {
+        static BUILTIN: OnceLock<Arc<Dictionary>> = OnceLock::new();
+        BUILTIN
+            .get_or_init(|| Arc::new(Self::with_counts(builtin_dictionary())))
+            .clone()
+    }
+}
+
+impl<const N: usize> Dictionary<N> {
+    /// Returns the number of words in the dictionary.
+    pub fn len(&self) -> usize {
+        self.words.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+
+    /// Returns the index of `word`, if it is part of the dictionary.
+    pub fn index(&self, word: &str) -> Option<usize> {
+        let position = self
+            .sorted
+            .binary_search_by(|&i| self.words[i as usize].cmp(word))
+            .ok()?;
+        Some(self.sorted[position] as usize)
+    }
+
+    /// Returns the word at `index`.
+    pub fn word(&self, index: usize) -> &'static str {
+        self.words[index]
+    }
+
+    /// Returns the letters of the word at `index`, if it is made of lowercase ASCII letters.
+    pub fn letters(&self, index: usize) -> Option<&Word<N>> {
+        self.letters[index].as_ref()
+    }
+
+    /// Returns every word, in the order of their indices.
+    pub fn words(&self) -> &[&'static str] {
+        &self.words
+    }
+
+    /// Returns a hash of the words, in order, that is stable across runs and platforms, to tell
+    /// whether data computed for a dictionary still fits it.
+    pub fn fingerprint(&self) -> u64 {
+        crate::fnv1a(
+            self.words
+                .iter()
+                .flat_map(|word| word.bytes().chain(std::iter::once(b'\n'))),
+        )
+    }
+
+    /// Returns the frequency count of every word, in the order of their indices.
+    pub fn counts(&self) -> Arc<[usize]> {
+        self.counts.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+    use std::sync::Arc;
+
+    use super::{DictVersion, Dictionary};
+
+    #[test]
+    fn looks_words_up() {
+        let dictionary: Dictionary = Dictionary::new(vec!["fghij", "abcde", "ñandu"]);
+        assert_eq!(dictionary.index("abcde"), Some(1));
+        assert_eq!(dictionary.index("ñandu"), Some(2));
+        assert_eq!(dictionary.index("klmno"), None);
+        assert_eq!(dictionary.word(0), "fghij");
+        assert_eq!(
+            dictionary.letters(1).map(|word| word.as_str()),
+            Some("abcde")
+        );
+        assert_eq!(dictionary.letters(2), None);
+        assert_eq!(&dictionary.counts()[..], [1, 1, 1]);
+    }
+
+    #[test]
+    fn too_many_words() {
+        let words = std::iter::repeat_n("aaaaa", super::MAX_WORDS + 1);
+        assert!(matches!(
+            Dictionary::<5>::try_new(words),
+            Err(crate::Error::TooManyWords { len, max }) if len == max + 1
+        ));
+    }
+
+    #[test]
+    fn wrong_length() {
+        assert!(matches!(
+            Dictionary::<5>::try_new(vec!["abcde", "bright"]),
+            Err(crate::Error::WordLength { word, len: 5 }) if word == "bright"
+        ));
+        let dictionary = Dictionary::<6>::new(vec!["bright", "ñandus"]);
+        assert_eq!(
+            dictionary.letters(0).map(|word| word.as_str()),
+            Some("bright")
+        );
+    }
+
+    #[test]
+    fn builtin() {
+        let builtin = Dictionary::builtin();
+        assert!(Arc::ptr_eq(&builtin, &Dictionary::builtin()));
+        assert!(Arc::ptr_eq(&builtin.counts(), &builtin.counts()));
+        let (words, counts): (Vec<_>, Vec<_>) = crate::builtin_dictionary().unzip();
+        assert_eq!(builtin.words(), words);
+        assert_eq!(&builtin.counts()[..], counts);
+    }
+
+    #[test]
+    fn versions() {
+        let nyt = DictVersion::Nyt2023;
+        assert_eq!(nyt.answers().count(), 2309);
+        assert_eq!(
+            nyt.dictionary().count(),
+            crate::builtin_dictionary().count()
+        );
+
+        let original = DictVersion::Original;
+        assert_eq!(original.answers().count(), 2315);
+        assert!(original.answers().any(|answer| answer == "fibre"));
+        let words: HashSet<_> = original.dictionary().map(|(word, _)| word).collect();
+        assert!(original.answers().all(|answer| words.contains(answer)));
+    }
+}
@@ -0,0 +1,87 @@
+//! How hard an answer is to find, e.g. for setters picking the answers of their own puzzles, see
+//! [`Wordle::difficulty`].
+
+use crate::{Guesser, PlayError, Transcript, Wordle};
+
+/// How hard an answer is to find, out of a game of a reference guesser against it and of the
+/// answers that look like it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Difficulty<const N: usize = 5> {
+    /// The game of the reference guesser.
+    pub transcript: Transcript<N>,
+    /// How many answers were left after every guess of the game.
+    pub remaining: Vec<usize>,
+    /// The other answers that only differ from it at a single position, e.g. "bound", "found" and
+    /// "hound" for "round", in alphabetical order.
+    pub family: Vec<&'static str>,
+    /// The most answers, counting this one, that only differ from each other at the same single
+    /// position. Unless a guess tells them apart, finding the answer takes one guess for each.
+    pub trap: usize,
+}
+
+impl<const N: usize> Wordle<N> {
+    /// Estimates how hard `answer` is to find, by letting `guesser` play a game against it and
+    /// looking for the answers that only differ from it at a single position.
+    ///
+    /// Fails like [`Wordle::play_transcript`].
+    pub fn difficulty<G: Guesser<N>>(
+        &self,
+        answer: &str,
+        guesser: G,
+    ) -> Result<Difficulty<N>, PlayError> {
+        let mut game = self.game(answer, guesser)?.track_answers();
+        let mut remaining = Vec::new();
+        for step in &mut game {
+            remaining.push(step?.remaining.len());
+        }
+
+        let mut family = Vec::new();
+        let mut traps = [1; N];
+        for &other in &self.answers {
+            let mut differing = other
+                .chars()
+                .zip(answer.chars())
+                .enumerate()
+                .filter(|(_, (a, b))| a != b);
+            if let (Some((position, _)), None) = (differing.next(), differing.next()) {
+                family.push(other);
+                traps[position] += 1;
+            }
+        }
+
+        Ok(Difficulty {
+            transcript: game.into_transcript(),
+            remaining,
+            family,
+            trap: traps.iter().copied().max().unwrap_or(1),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Wordle;
+
+    #[test]
+    fn families() {
+        let wordle = Wordle::with_dictionary(
+            [
+                "bound", "found", "hound", "round", "rouse", "wound", "cigar",
+            ]
+            .map(|word| (word, 1)),
+        );
+        let guesser = guesser!(|history| {
+            match history.len() {
+                0 => "cigar".into(),
+                _ => "round".into(),
+            }
+        });
+
+        let difficulty = wordle.difficulty("round", guesser).unwrap();
+        assert_eq!(difficulty.transcript.score, Some(2));
+        // Only "rouse" has an "r" too, but it differs from "round" at two positions.
+        assert_eq!(difficulty.remaining, [2, 1]);
+        assert_eq!(difficulty.family, ["bound", "found", "hound", "wound"]);
+        assert_eq!(difficulty.trap, 5);
+    }
+}
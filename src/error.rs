@@ -0,0 +1,112 @@
+//! Everything that can go wrong in the crate in a single type, see [`Error`].
+
+use std::io;
+
+use crate::algorithms::opener_rules::InvalidOpenerRule;
+use crate::assistant::InvalidTurn;
+#[cfg(feature = "std")]
+use crate::config::ConfigError;
+#[cfg(feature = "gpu")]
+use crate::gpu::GpuError;
+use crate::validation::InvalidDictionary;
+use crate::{BuildError, GuessError, InvalidWord, PlayError};
+
+/// Any error of the crate, for users of the library that handle them all in one place.
+///
+/// Functions return the narrowest error that describes what went wrong, e.g. [`PlayError`] when a
+/// game cannot be played, all of which convert into this one with `?`. Reading files returns this
+/// one, since they can be missing as well as malformed.
+///
+/// The constructors that panic on bad data, like [`Wordle::with_dictionary`](crate::Wordle::with_dictionary) or
+/// [`Dictionary::with_counts`](crate::Dictionary::with_counts), are meant for word lists known to
+/// be valid, and have `try_` counterparts returning this error for the others. A
+/// [`WordleBuilder`](crate::WordleBuilder) checks every setting of a game at once.
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum Error {
+    /// A file could not be read or written.
+    #[error(transparent)]
+    Io(#[from] io::Error),
+    /// A dictionary has malformed lines, duplicates or words of the wrong length.
+    #[error(transparent)]
+    Dictionary(#[from] InvalidDictionary),
+    /// A dictionary has more words than a [`Dictionary`](crate::Dictionary) can hold.
+    #[error("dictionaries hold at most {max} words, not {len}")]
+    TooManyWords { len: usize, max: usize },
+    /// A word of a dictionary does not have as many letters as the others must.
+    #[error("`{word}` is not {len} letters long")]
+    WordLength { word: String, len: usize },
+    /// A pattern cache was handed along with the words of another dictionary.
+    #[error("the pattern cache is for a dictionary of {cache} words, not {words}")]
+    CacheMismatch { words: usize, cache: usize },
+    /// A string is not a word.
+    #[error(transparent)]
+    Word(#[from] InvalidWord),
+    /// A word may not be guessed.
+    #[error(transparent)]
+    Guess(#[from] GuessError),
+    /// A game could not be played to completion.
+    #[error(transparent)]
+    Play(#[from] PlayError),
+    /// A line entered by the player is not a turn of the game.
+    #[error(transparent)]
+    Turn(#[from] InvalidTurn),
+    /// The settings of a game are inconsistent.
+    #[error(transparent)]
+    Build(#[from] BuildError),
+    /// A rule for the opening guess is not written like one.
+    #[error(transparent)]
+    OpenerRule(#[from] InvalidOpenerRule),
+    /// A configuration file is not valid.
+    #[cfg(feature = "std")]
+    #[error(transparent)]
+    Config(#[from] ConfigError),
+    /// The GPU could not compute the masks.
+    #[cfg(feature = "gpu")]
+    #[error(transparent)]
+    Gpu(#[from] GpuError),
+}
+
+/// A result whose error defaults to [`Error`].
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+/// Frontends built on [`io::Result`], like the command line, keep propagating errors with `?`.
+/// The errors read from files are [`io::ErrorKind::InvalidData`], and the others
+/// [`io::ErrorKind::InvalidInput`]. Either way, the original error can be downcast from
+/// [`io::Error::get_ref`].
+impl From<Error> for io::Error {
+    fn from(e: Error) -> Self {
+        match e {
+            Error::Io(e) => e,
+            Error::Dictionary(e) => io::Error::new(io::ErrorKind::InvalidData, e),
+            #[cfg(feature = "std")]
+            Error::Config(e) => io::Error::new(io::ErrorKind::InvalidData, e),
+            e @ Error::TooManyWords { .. } => io::Error::new(io::ErrorKind::InvalidData, e),
+            e @ Error::WordLength { .. } => io::Error::new(io::ErrorKind::InvalidData, e),
+            e => io::Error::new(io::ErrorKind::InvalidInput, e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io;
+
+    use super::Error;
+    use crate::validation::InvalidDictionary;
+
+    #[test]
+    fn converts_into_io_errors() {
+        let e = crate::parse_dictionary("right 10\nwrong\n").unwrap_err();
+        assert!(matches!(&e, Error::Dictionary(invalid) if invalid.diagnostics[0].line == 2));
+
+        let message = e.to_string();
+        let e = io::Error::from(e);
+        assert_eq!(e.kind(), io::ErrorKind::InvalidData);
+        assert_eq!(e.to_string(), message);
+        assert!(e.get_ref().unwrap().is::<InvalidDictionary>());
+
+        let e = io::Error::from(Error::from(io::Error::from(io::ErrorKind::NotFound)));
+        assert_eq!(e.kind(), io::ErrorKind::NotFound);
+    }
+}
@@ -0,0 +1,81 @@
+//! How a guesser expects the rest of the game to go, for players using it as an assistant.
+
+use std::fmt;
+
+use serde::Serialize;
+
+use crate::Correctness;
+
+/// What a guesser expects of the rest of the game before playing its next guess, see
+/// [`Guesser::estimate`](crate::Guesser::estimate).
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct Estimate {
+    /// How many guesses it is expected to take to find the answer, including the next one.
+    pub expected_turns: f64,
+    /// How likely the next guess is to be the answer.
+    pub win_prob: f64,
+}
+
+impl Estimate {
+    /// Estimates a game with `candidates` words left that are all as likely to be the answer, if
+    /// the next guess is one of them and every guess splits the others as well as any guess can:
+    /// one of them is found, and the rest spread over every other mask.
+    ///
+    /// No guesser does better, so the expected turns are a lower bound.
+    ///
+    /// Returns `None` if there is no candidate.
+    pub fn at_best(candidates: usize) -> Option<Self> {
+        if candidates == 0 {
+            return None;
+        }
+
+        // The candidates found with every guess, the first one alone, then one for every mask
+        // but a win left by the guess before.
+        let (mut left, mut found, mut turns, mut total) = (candidates, 1usize, 1, 0);
+        while left > 0 {
+            let now = found.min(left);
+            total += now * turns;
+            left -= now;
+            found = found.saturating_mul(Correctness::PATTERNS - 1);
+            turns += 1;
+        }
+
+        Some(Self {
+            expected_turns: total as f64 / candidates as f64,
+            win_prob: 1.0 / candidates as f64,
+        })
+    }
+}
+
+impl fmt::Display for Estimate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "expected to take {:.2} more guesses, with a {:.1}% chance to win with the next one",
+            self.expected_turns,
+            self.win_prob * 100.0
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Estimate;
+
+    #[test]
+    fn at_best() {
+        assert_eq!(Estimate::at_best(0), None);
+        let one = Estimate::at_best(1).unwrap();
+        assert_eq!((one.expected_turns, one.win_prob), (1.0, 1.0));
+        // One candidate is found at once, and the other ones with the next guess.
+        let few = Estimate::at_best(4).unwrap();
+        assert_eq!((few.expected_turns, few.win_prob), (1.75, 0.25));
+        // Beyond the 1 + 242 candidates found by the first two guesses, the others take three.
+        let many = Estimate::at_best(1000).unwrap();
+        assert_eq!(many.expected_turns, (1 + 2 * 242 + 3 * 757) as f64 / 1000.0);
+        assert_eq!(
+            many.to_string(),
+            "expected to take 2.76 more guesses, with a 0.1% chance to win with the next one"
+        );
+    }
+}
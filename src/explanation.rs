@@ -0,0 +1,59 @@
+//! Why a guesser picked its guess, for players using it as an assistant.
+
+use std::fmt;
+
+use crate::Correctness;
+
+/// Number of alternatives an [`Explanation`] lists.
+pub const ALTERNATIVES: usize = 5;
+
+/// What a guess was expected to reveal, compared to the best other guesses, see
+/// [`Guesser::guess_with_explanation`](crate::Guesser::guess_with_explanation).
+///
+/// The information revealed by guesses weighs the candidates the way the guesser does, e.g. by
+/// their frequency for the naive guesser, and counts them all the same otherwise.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Explanation<const N: usize = 5> {
+    /// How many words could still be the answer.
+    pub candidates: usize,
+    /// The information the guess was expected to reveal, in bits.
+    pub entropy: f64,
+    /// The other words expected to reveal the most information, along with that information,
+    /// the best first.
+    pub alternatives: Vec<(&'static str, f64)>,
+    /// How many candidates would be left after every mask the guess may reveal, the mask leaving
+    /// the most first.
+    pub distribution: Vec<([Correctness; N], usize)>,
+}
+
+impl<const N: usize> fmt::Display for Explanation<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "expected to reveal {:.2} bits about {} candidates",
+            self.entropy, self.candidates
+        )?;
+        if !self.alternatives.is_empty() {
+            let alternatives: Vec<String> = self
+                .alternatives
+                .iter()
+                .map(|(word, entropy)| format!("{} ({:.2})", word, entropy))
+                .collect();
+            writeln!(f, "alternatives: {}", alternatives.join(", "))?;
+        }
+        let masks: Vec<String> = self
+            .distribution
+            .iter()
+            .take(ALTERNATIVES)
+            .map(|(mask, count)| {
+                let mask: String = mask.iter().map(|c| c.to_char()).collect();
+                format!("{} {}", mask, count)
+            })
+            .collect();
+        write!(f, "candidates per mask: {}", masks.join(", "))?;
+        if self.distribution.len() > ALTERNATIVES {
+            write!(f, " and {} more", self.distribution.len() - ALTERNATIVES)?;
+        }
+        writeln!(f)
+    }
+}
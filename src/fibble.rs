@@ -0,0 +1,282 @@
+//! Fibble, a variant where the mask of every guess lies about exactly one of its tiles, which is
+//! shown with one of the two other colors.
+
+use std::borrow::Cow;
+use std::sync::Arc;
+
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+use crate::algorithms::{best_by, entropy, no_candidate_left, Candidates, PatternCache};
+use crate::{
+    builtin_dictionary, fnv1a, CancelToken, Correctness, Guess, Guesser, History, PlayError,
+    Transcript, Wordle,
+};
+
+/// A game of Wordle where one tile of every mask lies, except for the guess that finds the
+/// answer, whose tiles are all green.
+///
+/// The lying tile and the color it shows are picked at random, reproducibly for a given seed and
+/// answer.
+pub struct Fibble<const N: usize = 5> {
+    wordle: Wordle<N>,
+    seed: u64,
+}
+
+impl Fibble {
+    /// Creates a game where any word of the built-in dictionary may be guessed.
+    pub fn new() -> Self {
+        Self::with_dictionary(builtin_dictionary())
+    }
+}
+
+impl<const N: usize> Fibble<N> {
+    /// Creates a game whose valid guesses are the words of the given dictionary. Every word must
+    /// be `N` letters long.
+    pub fn with_dictionary(words: impl IntoIterator<Item = (&'static str, usize)>) -> Self {
+        Self {
+            wordle: Wordle::with_dictionary(words),
+            seed: 0,
+        }
+    }
+
+    /// Sets the seed the lies are picked with. Defaults to 0.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Plays a game with the given answer, until the guesser finds it or runs out of guesses.
+    ///
+    /// Fails if the answer is not `N` letters long, or if the guesser plays an invalid word.
+    pub fn play<G: Guesser<N>>(
+        &self,
+        answer: &str,
+        mut guesser: G,
+    ) -> Result<Transcript<N>, PlayError> {
+        if N == 0 || answer.chars().count() != N {
+            return Err(PlayError::InvalidAnswer(answer.to_string()));
+        }
+
+        let _span = tracing::info_span!("fibble", answer).entered();
+        let mut rng = ChaCha8Rng::seed_from_u64(self.seed ^ fnv1a(answer.bytes()));
+        let mut history = History::new();
+        while self.wordle.has_guesses_left(history.len()) {
+            let guess = self.wordle.check(guesser.guess(&history), &history)?;
+            if guess == answer {
+                history.push(Guess::new(guess, [Correctness::Correct; N]));
+                return Ok(Transcript {
+                    answer: answer.to_string(),
+                    score: Some(history.len()),
+                    guesses: history.into_guesses(),
+                });
+            }
+
+            let mut mask = Correctness::compute(answer, &guess);
+            let position = rng.random_range(0..N);
+            let lies: Vec<Correctness> = [
+                Correctness::Correct,
+                Correctness::Misplaced,
+                Correctness::Wrong,
+            ]
+            .iter()
+            .copied()
+            .filter(|&color| color != mask[position])
+            .collect();
+            mask[position] = lies[rng.random_range(0..lies.len())];
+            tracing::debug!(
+                turn = history.len() + 1,
+                guess = %guess,
+                position,
+                "lied about a tile"
+            );
+            history.push(Guess::new(guess, mask));
+        }
+
+        Ok(Transcript {
+            answer: answer.to_string(),
+            guesses: history.into_guesses(),
+            score: None,
+        })
+    }
+}
+
+impl Default for Fibble {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Plays [`Fibble`], keeping apart the candidates left by every position the lie of each mask may
+/// be at.
+///
+/// A word is still a candidate if exactly one tile of every mask differs from the mask it would
+/// have revealed as the answer, and it was not played already. The guesser picks the candidate
+/// whose true patterns are spread the most over the others, since the lie only blurs them.
+#[derive(Clone)]
+pub struct LieTolerantGuesser {
+    remaining: Candidates,
+    /// For every guess seen so far, the candidates left if the lie of its mask is at each position.
+    hypotheses: Vec<[Candidates; 5]>,
+    cancel: CancelToken,
+    cache: Arc<PatternCache>,
+}
+
+impl LieTolerantGuesser {
+    /// Creates a guesser that picks its words from the built-in dictionary.
+    pub fn new() -> Self {
+        Self::with_cache(PatternCache::global())
+    }
+
+    /// Creates a guesser that picks its words from the given dictionary, with a new
+    /// [`PatternCache`] for it.
+    pub fn with_dictionary(words: impl IntoIterator<Item = (&'static str, usize)>) -> Self {
+        Self::with_cache(Arc::new(PatternCache::new(
+            words.into_iter().map(|(word, _)| word),
+        )))
+    }
+
+    /// Creates a guesser that picks its words from the words of `cache`.
+    pub fn with_cache(cache: Arc<PatternCache>) -> Self {
+        Self {
+            remaining: Candidates::all(cache.len()),
+            hypotheses: Vec::new(),
+            cancel: CancelToken::new(),
+            cache,
+        }
+    }
+
+    /// Only considers the given words as possible answers, see [`Candidates::retain_words`].
+    pub fn with_answers<'a>(mut self, answers: impl IntoIterator<Item = &'a str>) -> Self {
+        self.remaining.retain_words(&self.cache, answers);
+        self
+    }
+
+    /// Returns, for every guess seen so far, how many of the remaining candidates are left if the
+    /// lie of its mask is at each position.
+    pub fn hypotheses(&self) -> Vec<[usize; 5]> {
+        self.hypotheses
+            .iter()
+            .map(|row| {
+                row.each_ref().map(|candidates| {
+                    candidates
+                        .iter()
+                        .filter(|&index| self.remaining.contains(index))
+                        .count()
+                })
+            })
+            .collect()
+    }
+
+    /// Narrows the candidates down with a guess that lied about one of its tiles.
+    fn add(&mut self, guess: &Guess) {
+        let mut hypotheses: [Vec<usize>; 5] = Default::default();
+        for candidate in self.remaining.iter() {
+            let word = self.cache.word(candidate);
+            if word == guess.word {
+                // It would have ended the game.
+                continue;
+            }
+            let lies = guess.lies(word);
+            let mut positions = (0..5).filter(|&i| lies[i]);
+            if let (Some(position), None) = (positions.next(), positions.next()) {
+                hypotheses[position].push(candidate);
+            }
+        }
+
+        let len = self.cache.len();
+        self.remaining = Candidates::from_indices(len, hypotheses.iter().flatten().copied());
+        self.hypotheses
+            .push(hypotheses.map(|indices| Candidates::from_indices(len, indices)));
+    }
+}
+
+impl Default for LieTolerantGuesser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Guesser for LieTolerantGuesser {
+    fn guess(&mut self, history: &History) -> Cow<'static, str> {
+        for guess in &history[self.hypotheses.len()..] {
+            self.add(guess);
+        }
+        if self.remaining.is_empty() {
+            return no_candidate_left(&self.cache, history);
+        }
+
+        let candidates: Vec<(usize, f64)> =
+            self.remaining.iter().map(|index| (index, 1.0)).collect();
+        let guesses: Vec<usize> = candidates.iter().map(|&(index, _)| index).collect();
+        let cache = &self.cache;
+        let (best, information) = best_by(&guesses, &self.cancel, |guess| {
+            entropy(cache.row(guess), &candidates)
+        })
+        .expect("the answer is always among the candidates");
+        tracing::debug!(
+            guess = cache.word(best),
+            information,
+            candidates = candidates.len(),
+            "picked the candidate with the most spread out patterns"
+        );
+
+        Cow::Borrowed(cache.word(best))
+    }
+
+    fn set_cancel_token(&mut self, token: CancelToken) {
+        self.cancel = token;
+    }
+
+    fn remaining(&self) -> Option<Vec<&'static str>> {
+        Some(self.remaining.words(&self.cache))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Fibble, LieTolerantGuesser};
+    use crate::{Correctness, Guess, Guesser, History, WORDS};
+
+    #[test]
+    fn lies_about_one_tile_per_guess() {
+        let fibble = Fibble::with_dictionary(WORDS.iter().copied()).with_seed(3);
+        let guesser = LieTolerantGuesser::with_dictionary(WORDS.iter().copied());
+        for &(answer, _) in WORDS {
+            let transcript = fibble.play(answer, guesser.clone()).unwrap();
+            assert!(transcript.score.is_some(), "{} was not found", answer);
+
+            let (last, rest) = transcript.guesses.split_last().unwrap();
+            assert_eq!(last.word, answer);
+            for guess in rest {
+                let lies = guess.lies(answer).iter().filter(|&&lie| lie).count();
+                assert_eq!(lies, 1, "{} did not lie once", guess.word);
+            }
+        }
+    }
+
+    #[test]
+    fn tracks_every_hypothesis() {
+        let mut guesser = LieTolerantGuesser::with_dictionary(WORDS.iter().copied());
+        // The truth against "blush" is XYXXG, and the mask lies about the first tile.
+        let mask = [
+            Correctness::Misplaced,
+            Correctness::Misplaced,
+            Correctness::Wrong,
+            Correctness::Wrong,
+            Correctness::Correct,
+        ];
+        let history = History::from(vec![Guess::new("humph", mask)]);
+        guesser.guess(&history);
+
+        assert!(!guesser.remaining().unwrap().contains(&"humph"));
+        assert!(guesser.remaining().unwrap().contains(&"blush"));
+        let hypotheses = guesser.hypotheses();
+        assert_eq!(hypotheses.len(), 1);
+        assert!(hypotheses[0][0] >= 1);
+        assert_eq!(
+            hypotheses[0].iter().sum::<usize>(),
+            guesser.remaining().unwrap().len()
+        );
+    }
+}
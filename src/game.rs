@@ -0,0 +1,413 @@
+//! Playing a game one guess at a time, see [`Wordle::game`].
+
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{fnv1a, Correctness, Guess, Guesser, History, PlayError, Transcript, Wordle};
+
+/// A game in progress, which plays one guess every time it is advanced.
+///
+/// Iterating over it yields every turn until the answer is found or the guesser runs out of
+/// guesses, or until the guesser plays an invalid word, after which it stops.
+///
+/// Only the guesses are kept track of by default, see [`Game::track_answers`] to also learn which
+/// answers every turn rules out.
+pub struct Game<'a, G, const N: usize = 5> {
+    wordle: &'a Wordle<N>,
+    answer: &'a str,
+    guesser: G,
+    history: History<N>,
+    remaining: Vec<&'static str>,
+    tracking: bool,
+    score: Option<usize>,
+    over: bool,
+    span: tracing::Span,
+}
+
+/// What happened during a single turn of a [`Game`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Step<const N: usize = 5> {
+    /// The word played this turn and the mask it revealed.
+    pub guess: Guess<N>,
+    /// The answers of the game that are still possible after this turn, in alphabetical order.
+    ///
+    /// Empty unless the game tracks answers, see [`Game::track_answers`], as is `eliminated`.
+    pub remaining: Vec<&'static str>,
+    /// The answers this turn ruled out, in alphabetical order.
+    pub eliminated: Vec<Elimination<N>>,
+}
+
+/// An answer ruled out by a guess, e.g. for a frontend to animate it away along with the tiles
+/// that gave it away.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Elimination<const N: usize = 5> {
+    pub word: &'static str,
+    /// Which tiles of the guess would have been colored differently if the word were the answer,
+    /// see [`Guess::lies`].
+    pub tiles: [bool; N],
+}
+
+/// What a frontend is told after every turn of a game played with [`Wordle::play_streaming`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TurnEvent<const N: usize = 5> {
+    /// The number of the turn, from 1.
+    pub turn: usize,
+    /// The word played this turn and the mask it revealed.
+    pub guess: Guess<N>,
+    /// The number of answers of the game that are still possible after this turn.
+    pub remaining: usize,
+    /// The answers this turn ruled out, see [`Step::eliminated`].
+    pub eliminated: Vec<Elimination<N>>,
+    /// How long the turn took, mostly spent by the guesser picking its word.
+    pub elapsed: Duration,
+}
+
+/// A snapshot of a [`Game`], which can be saved and resumed later with [`Wordle::resume`].
+///
+/// The answer is only stored as a hash, so that a saved game does not give it away at a glance.
+/// It is looked up among the answers of the game when resuming.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GameState<const N: usize = 5> {
+    pub answer_hash: u64,
+    pub history: Vec<Guess<N>>,
+}
+
+impl<const N: usize> GameState<N> {
+    /// Returns the number of guesses played so far.
+    pub fn turn(&self) -> usize {
+        self.history.len()
+    }
+}
+
+pub(crate) fn answer_hash(answer: &str) -> u64 {
+    fnv1a(answer.bytes())
+}
+
+impl<'a, G: Guesser<N>, const N: usize> Game<'a, G, N> {
+    pub(crate) fn new(
+        wordle: &'a Wordle<N>,
+        answer: &'a str,
+        mut guesser: G,
+    ) -> Result<Self, PlayError> {
+        if answer.chars().count() != N {
+            return Err(PlayError::InvalidAnswer(answer.to_string()));
+        }
+
+        guesser.set_hard_mode(wordle.hard_mode);
+
+        Ok(Self {
+            wordle,
+            answer,
+            guesser,
+            history: History::new(),
+            remaining: Vec::new(),
+            tracking: false,
+            score: None,
+            over: false,
+            span: tracing::info_span!("game", answer),
+        })
+    }
+
+    pub(crate) fn resume(
+        wordle: &'a Wordle<N>,
+        state: GameState<N>,
+        guesser: G,
+    ) -> Result<Self, PlayError> {
+        let answer = wordle
+            .answers
+            .iter()
+            .copied()
+            .find(|answer| answer_hash(answer) == state.answer_hash)
+            .ok_or_else(|| {
+                PlayError::InvalidState("no answer matches the saved game".to_string())
+            })?;
+        if let Some(guess) = state.history.iter().find(|guess| !guess.matches(answer)) {
+            return Err(PlayError::InvalidState(format!(
+                "`{}` was not played against the saved answer",
+                guess.word
+            )));
+        }
+
+        let mut game = Self::new(wordle, answer, guesser)?;
+        // Guessers narrow down their candidates one guess at a time, so they have to go through
+        // every turn again to catch up.
+        for guess in state.history {
+            game.guesser.guess(&game.history);
+            if guess.mask == [Correctness::Correct; N] {
+                game.over = true;
+                game.score = Some(game.history.len() + 1);
+            }
+            game.history.push(guess);
+        }
+
+        Ok(game)
+    }
+
+    /// Fills in [`Step::remaining`] and [`Step::eliminated`] on every turn from now on, which
+    /// checks every answer still possible against every guess.
+    pub fn track_answers(mut self) -> Self {
+        if !self.tracking {
+            let history = &self.history;
+            self.remaining = self
+                .wordle
+                .answers
+                .iter()
+                .copied()
+                .filter(|word| history.matches(word))
+                .collect();
+            self.tracking = true;
+        }
+        self
+    }
+
+    /// Returns a snapshot of the game, to resume it later.
+    pub fn state(&self) -> GameState<N> {
+        GameState {
+            answer_hash: answer_hash(self.answer),
+            history: self.history.to_vec(),
+        }
+    }
+
+    /// Returns the guesses played so far.
+    pub fn history(&self) -> &History<N> {
+        &self.history
+    }
+
+    /// Returns the number of guesses it took to find the answer, if it was found.
+    pub fn score(&self) -> Option<usize> {
+        self.score
+    }
+
+    /// Ends the game, returning every guess played so far.
+    pub fn into_transcript(self) -> Transcript<N> {
+        Transcript {
+            answer: self.answer.to_string(),
+            guesses: self.history.into_guesses(),
+            score: self.score,
+        }
+    }
+}
+
+impl<G: Guesser<N>, const N: usize> Iterator for Game<'_, G, N> {
+    type Item = Result<Step<N>, PlayError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.over || !self.wordle.has_guesses_left(self.history.len()) {
+            return None;
+        }
+
+        let _entered = self.span.clone().entered();
+
+        let guess = match self
+            .wordle
+            .check(self.guesser.guess(&self.history), &self.history)
+        {
+            Ok(guess) => guess,
+            Err(e) => {
+                tracing::warn!(error = %e, "the guesser played an invalid word");
+                self.over = true;
+                return Some(Err(e));
+            }
+        };
+
+        let guess = Guess {
+            mask: Correctness::compute(self.answer, &guess),
+            word: guess,
+        };
+        let mut eliminated = Vec::new();
+        if self.tracking {
+            self.remaining.retain(|&word| {
+                let matches = guess.matches(word);
+                if !matches {
+                    let tiles = guess.lies(word);
+                    eliminated.push(Elimination { word, tiles });
+                }
+                matches
+            });
+        }
+        self.history.push(guess.clone());
+        tracing::debug!(
+            turn = self.history.len(),
+            guess = %guess.word,
+            mask = %guess.mask.iter().map(|c| c.to_char()).collect::<String>(),
+            "played a guess"
+        );
+
+        if guess.word == self.answer {
+            self.score = Some(self.history.len());
+            self.over = true;
+            tracing::info!(score = self.history.len(), "found the answer");
+        }
+
+        let remaining = if self.tracking {
+            self.remaining.clone()
+        } else {
+            Vec::new()
+        };
+        Some(Ok(Step {
+            guess,
+            remaining,
+            eliminated,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Elimination, GameState, Step};
+    use crate::{Guess, PlayError, Wordle};
+
+    #[test]
+    fn steps() {
+        let w =
+            Wordle::with_dictionary(vec![("fight", 1), ("light", 1), ("right", 1), ("wrong", 1)]);
+        let guesser = guesser!(|history| {
+            match history.len() {
+                0 => "fight",
+                1 => "wrong",
+                _ => "right",
+            }
+            .into()
+        });
+
+        let mut game = w.game("right", guesser).unwrap().track_answers();
+        assert_eq!(
+            game.next(),
+            Some(Ok(Step {
+                guess: Guess::new("fight", mask!(W C C C C)),
+                remaining: vec!["light", "right"],
+                eliminated: vec![
+                    Elimination {
+                        word: "fight",
+                        tiles: [true, false, false, false, false],
+                    },
+                    Elimination {
+                        word: "wrong",
+                        tiles: [false, true, true, true, true],
+                    },
+                ],
+            }))
+        );
+        assert_eq!(game.score(), None);
+        assert_eq!(
+            game.next(),
+            Some(Ok(Step {
+                guess: Guess::new("wrong", mask!(W M W W M)),
+                remaining: vec!["right"],
+                // Had the answer been "light", the `r` would have been gray.
+                eliminated: vec![Elimination {
+                    word: "light",
+                    tiles: [false, true, false, false, false],
+                }],
+            }))
+        );
+        assert_eq!(
+            game.next(),
+            Some(Ok(Step {
+                guess: Guess::new("right", mask!(C C C C C)),
+                remaining: vec!["right"],
+                eliminated: vec![],
+            }))
+        );
+        assert_eq!(game.next(), None);
+        assert_eq!(game.score(), Some(3));
+        assert_eq!(game.history().len(), 3);
+    }
+
+    #[test]
+    fn stops_after_an_error() {
+        let w = Wordle::new();
+        let guesser = guesser!(|_history| { "zzzzz".into() });
+
+        let mut game = w.game("right", guesser).unwrap();
+        assert_eq!(
+            game.next(),
+            Some(Err(PlayError::NotInDictionary("zzzzz".to_string())))
+        );
+        assert_eq!(game.next(), None);
+    }
+
+    #[test]
+    fn resume() {
+        let w =
+            Wordle::with_dictionary(vec![("fight", 1), ("light", 1), ("right", 1), ("wrong", 1)]);
+        let guesser = || {
+            guesser!(|history| {
+                match history.len() {
+                    0 => "fight",
+                    1 => "wrong",
+                    _ => "right",
+                }
+                .into()
+            })
+        };
+
+        let mut game = w.game("right", guesser()).unwrap();
+        game.next();
+        let state = game.state();
+        assert_eq!(state.turn(), 1);
+
+        let json = serde_json::to_string(&state).unwrap();
+        assert!(!json.contains("\"right\""));
+        let state: GameState = serde_json::from_str(&json).unwrap();
+
+        let mut game = w.resume(state, guesser()).unwrap().track_answers();
+        assert_eq!(game.history().len(), 1);
+        assert_eq!(
+            game.next(),
+            Some(Ok(Step {
+                guess: Guess::new("wrong", mask!(W M W W M)),
+                remaining: vec!["right"],
+                eliminated: vec![Elimination {
+                    word: "light",
+                    tiles: [false, true, false, false, false],
+                }],
+            }))
+        );
+        assert!(matches!(game.next(), Some(Ok(_))));
+        assert_eq!(game.score(), Some(3));
+    }
+
+    #[test]
+    fn resume_invalid_state() {
+        let w = Wordle::with_dictionary(vec![("fight", 1), ("light", 1), ("right", 1)]);
+        let guesser = guesser!(|_history| { "right".into() });
+
+        let mut state = w.game("right", guesser).unwrap().state();
+        state.history.push(Guess::new("fight", mask!(C C C C C)));
+        assert!(matches!(
+            w.resume(state.clone(), guesser!(|_history| { "right".into() })),
+            Err(PlayError::InvalidState(_))
+        ));
+
+        state.history.clear();
+        state.answer_hash += 1;
+        assert!(matches!(
+            w.resume(state, guesser!(|_history| { "right".into() })),
+            Err(PlayError::InvalidState(_))
+        ));
+    }
+
+    #[test]
+    fn only_tracks_answers_on_demand() {
+        let w =
+            Wordle::with_dictionary(vec![("fight", 1), ("light", 1), ("right", 1), ("wrong", 1)]);
+        let guesser = guesser!(|_history| { "fight".into() });
+
+        let mut game = w.game("right", guesser).unwrap();
+        assert_eq!(
+            game.next(),
+            Some(Ok(Step {
+                guess: Guess::new("fight", mask!(W C C C C)),
+                remaining: vec![],
+                eliminated: vec![],
+            }))
+        );
+
+        let mut game = game.track_answers();
+        let step = game.next().unwrap().unwrap();
+        assert_eq!(step.remaining, ["light", "right"]);
+        assert!(step.eliminated.is_empty());
+    }
+}
@@ -0,0 +1,249 @@
+//! Computing the masks between many guesses and answers at once on the GPU, to fill in the whole
+//! [`PatternCache`](crate::algorithms::PatternCache) in one go, see [`Gpu`].
+//!
+//! Words are packed into buffers and every mask is computed by a compute shader, `src/gpu.wgsl`,
+//! a chunk of guesses at a time, so that the buffers stay within the limits of the device. Once
+//! the cache is filled in, scoring guesses is a lookup away just like with a matrix saved by
+//! `precompute-matrix`.
+
+use std::sync::mpsc;
+
+use wgpu::util::DeviceExt;
+
+/// At most this many bytes of masks are computed per chunk of guesses.
+const CHUNK_BYTES: u64 = 64 << 20;
+/// Number of invocations per workgroup, as declared in the shader.
+const WORKGROUP_SIZE: u32 = 64;
+
+/// Why the GPU could not compute the masks.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum GpuError {
+    /// The machine has no GPU that wgpu supports.
+    #[error("no GPU to compute the masks on: {0}")]
+    NoAdapter(String),
+    /// The GPU could not be opened, or failed to compute the masks.
+    #[error("{0}")]
+    Failed(String),
+}
+
+/// A GPU, with the shader computing masks ready to run.
+pub struct Gpu {
+    device: wgpu::Device,
+    queue: wgpu::Queue,
+    pipeline: wgpu::ComputePipeline,
+}
+
+impl Gpu {
+    /// Picks the GPU of the machine and compiles the shader for it.
+    ///
+    /// Fails if the machine has no GPU that wgpu supports, in which case the masks are best
+    /// computed on the CPU instead.
+    pub fn new() -> Result<Self, GpuError> {
+        let instance = wgpu::Instance::new(wgpu::InstanceDescriptor::new_without_display_handle());
+        let adapter =
+            pollster::block_on(instance.request_adapter(&wgpu::RequestAdapterOptions::default()))
+                .map_err(|e| GpuError::NoAdapter(e.to_string()))?;
+        let (device, queue) =
+            pollster::block_on(adapter.request_device(&wgpu::DeviceDescriptor::default()))
+                .map_err(|e| GpuError::Failed(format!("could not open the GPU: {}", e)))?;
+        tracing::debug!(adapter = ?adapter.get_info(), "computing masks on the GPU");
+
+        let module = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("masks"),
+            source: wgpu::ShaderSource::Wgsl(include_str!("gpu.wgsl").into()),
+        });
+        let pipeline = device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+            label: Some("masks"),
+            layout: None,
+            module: &module,
+            entry_point: Some("main"),
+            compilation_options: Default::default(),
+            cache: None,
+        });
+
+        Ok(Self {
+            device,
+            queue,
+            pipeline,
+        })
+    }
+
+    /// Returns the packed mask revealed by every one of `guesses` against every one of `answers`,
+    /// like [`Correctness::pack`](crate::Correctness::pack) of
+    /// [`Correctness::compute_bytes`](crate::Correctness::compute_bytes), guess by guess.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a byte is not a lowercase ASCII letter.
+    pub fn packed_patterns(
+        &self,
+        guesses: &[[u8; 5]],
+        answers: &[[u8; 5]],
+    ) -> Result<Vec<u8>, GpuError> {
+        if guesses.is_empty() || answers.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let words = |words: &[[u8; 5]], usage| {
+            let packed: Vec<u8> = words
+                .iter()
+                .flat_map(|word| pack(word).to_le_bytes())
+                .collect();
+            self.device
+                .create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                    label: None,
+                    contents: &packed,
+                    usage,
+                })
+        };
+        let guess_buffer = words(guesses, wgpu::BufferUsages::STORAGE);
+        let answer_buffer = words(answers, wgpu::BufferUsages::STORAGE);
+
+        // Every row holds a whole number of groups of four answers, one byte each.
+        let quads = answers.len().div_ceil(4);
+        let row_bytes = 4 * quads as u64;
+        let limits = self.device.limits();
+        let max_bytes = CHUNK_BYTES
+            .min(limits.max_storage_buffer_binding_size)
+            .min(limits.max_buffer_size);
+        let max_rows = (max_bytes / row_bytes)
+            .min(limits.max_compute_workgroups_per_dimension as u64)
+            .max(1) as usize;
+        let chunk_rows = max_rows.min(guesses.len());
+        if quads.div_ceil(WORKGROUP_SIZE as usize)
+            > limits.max_compute_workgroups_per_dimension as usize
+            || row_bytes > max_bytes
+        {
+            return Err(GpuError::Failed(format!(
+                "{} answers are too many for the GPU",
+                answers.len()
+            )));
+        }
+
+        let output = self.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("masks"),
+            size: chunk_rows as u64 * row_bytes,
+            usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_SRC,
+            mapped_at_creation: false,
+        });
+        let staging = self.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("masks read back"),
+            size: chunk_rows as u64 * row_bytes,
+            usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        let params = self.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("chunk"),
+            size: 16,
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        let bind_group = self.device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: None,
+            layout: &self.pipeline.get_bind_group_layout(0),
+            entries: &vec![
+                guess_buffer.as_entire_binding(),
+                answer_buffer.as_entire_binding(),
+                output.as_entire_binding(),
+                params.as_entire_binding(),
+            ]
+            .into_iter()
+            .enumerate()
+            .map(|(binding, resource)| wgpu::BindGroupEntry {
+                binding: binding as u32,
+                resource,
+            })
+            .collect::<Vec<_>>(),
+        });
+
+        let mut patterns = Vec::with_capacity(guesses.len() * answers.len());
+        for first in (0..guesses.len()).step_by(chunk_rows) {
+            let rows = chunk_rows.min(guesses.len() - first);
+            let chunk = [answers.len(), quads, first, rows].map(|value| value as u32);
+            let chunk: Vec<u8> = chunk.iter().flat_map(|value| value.to_le_bytes()).collect();
+            self.queue.write_buffer(&params, 0, &chunk);
+
+            let mut encoder = self
+                .device
+                .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+            {
+                let mut pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor::default());
+                pass.set_pipeline(&self.pipeline);
+                pass.set_bind_group(0, &bind_group, &[]);
+                pass.dispatch_workgroups((quads as u32).div_ceil(WORKGROUP_SIZE), rows as u32, 1);
+            }
+            let size = rows as u64 * row_bytes;
+            encoder.copy_buffer_to_buffer(&output, 0, &staging, 0, size);
+            self.queue.submit([encoder.finish()]);
+
+            let slice = staging.slice(..size);
+            let (sender, receiver) = mpsc::channel();
+            slice.map_async(wgpu::MapMode::Read, move |result| {
+                let _ = sender.send(result);
+            });
+            self.device
+                .poll(wgpu::PollType::wait_indefinitely())
+                .map_err(|e| {
+                    GpuError::Failed(format!("the GPU failed to compute the masks: {}", e))
+                })?;
+            receiver
+                .recv()
+                .map_err(|_| GpuError::Failed("the GPU dropped the masks".to_string()))?
+                .map_err(|e| GpuError::Failed(format!("could not read the masks back: {}", e)))?;
+            {
+                let view = slice.get_mapped_range().map_err(|e| {
+                    GpuError::Failed(format!("could not read the masks back: {}", e))
+                })?;
+                for row in view.chunks_exact(row_bytes as usize) {
+                    patterns.extend_from_slice(&row[..answers.len()]);
+                }
+            }
+            staging.unmap();
+        }
+
+        Ok(patterns)
+    }
+}
+
+/// Packs the letters of `word` five bits each, the first one in the lowest bits, as the shader
+/// reads them.
+fn pack(word: &[u8; 5]) -> u32 {
+    word.iter().rev().fold(0, |packed, &letter| {
+        assert!(letter.is_ascii_lowercase(), "not a lowercase ASCII letter");
+        packed << 5 | (letter - b'a') as u32
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Gpu, GpuError};
+    use crate::Correctness;
+
+    #[test]
+    fn agrees_with_the_cpu() {
+        // Machines without a GPU have nothing to test, but any other failure is one.
+        let gpu = match Gpu::new() {
+            Err(GpuError::NoAdapter(_)) => return,
+            gpu => gpu.unwrap(),
+        };
+
+        let words: Vec<[u8; 5]> = [
+            "cigar", "rebut", "sissy", "humph", "awake", "blush", "focal", "evade", "naval",
+            "serve", "heath", "dwarf", "eerie", "speed", "abbey", "zzzzz",
+        ]
+        .iter()
+        .map(|word| *word.as_bytes().first_chunk().unwrap())
+        .collect();
+        // An odd number of answers leaves a row partly filled with padding.
+        let answers = &words[..words.len() - 1];
+        let patterns = gpu.packed_patterns(&words, answers).unwrap();
+
+        assert_eq!(patterns.len(), words.len() * answers.len());
+        for (guess, row) in words.iter().zip(patterns.chunks(answers.len())) {
+            for (answer, &pattern) in answers.iter().zip(row) {
+                let mask = Correctness::compute_bytes(answer, guess);
+                assert_eq!(pattern, Correctness::pack(mask));
+            }
+        }
+    }
+}
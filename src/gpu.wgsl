@@ -0,0 +1,76 @@
+// Computes the packed mask of a chunk of guesses against every answer, like `Correctness::pack` of
+// `Correctness::compute_bytes`, see `src/gpu.rs`.
+//
+// Words are made of five letters from 0 to 25, five bits each starting from the lowest bits.
+// Every invocation writes the masks of a guess against four consecutive answers, one per byte
+// starting from the lowest one.
+
+struct Params {
+    // Number of answers.
+    answers: u32,
+    // Number of groups of four answers in a row, rounded up.
+    quads: u32,
+    // Index of the first guess of the chunk.
+    first: u32,
+    // Number of guesses in the chunk.
+    rows: u32,
+}
+
+@group(0) @binding(0) var<storage, read> guesses: array<u32>;
+@group(0) @binding(1) var<storage, read> answers: array<u32>;
+@group(0) @binding(2) var<storage, read_write> patterns: array<u32>;
+@group(0) @binding(3) var<uniform> params: Params;
+
+fn letter(word: u32, i: u32) -> u32 {
+    return (word >> (5u * i)) & 31u;
+}
+
+fn pattern(guess: u32, answer: u32) -> u32 {
+    // How many times every letter of the answer appears outside of the green positions.
+    var unmatched: array<u32, 26>;
+    var green = 0u;
+    for (var i = 0u; i < 5u; i += 1u) {
+        let a = letter(answer, i);
+        if a == letter(guess, i) {
+            green |= 1u << i;
+        } else {
+            unmatched[a] += 1u;
+        }
+    }
+
+    // Green letters count for 0, yellow ones for 1 and gray ones for 2, in base 3.
+    var packed = 0u;
+    for (var i = 0u; i < 5u; i += 1u) {
+        var digit = 0u;
+        if (green & (1u << i)) == 0u {
+            let g = letter(guess, i);
+            if unmatched[g] > 0u {
+                unmatched[g] -= 1u;
+                digit = 1u;
+            } else {
+                digit = 2u;
+            }
+        }
+        packed = packed * 3u + digit;
+    }
+    return packed;
+}
+
+@compute @workgroup_size(64)
+fn main(@builtin(global_invocation_id) id: vec3<u32>) {
+    let quad = id.x;
+    let row = id.y;
+    if quad >= params.quads || row >= params.rows {
+        return;
+    }
+
+    let guess = guesses[params.first + row];
+    var packed = 0u;
+    for (var k = 0u; k < 4u; k += 1u) {
+        let answer = quad * 4u + k;
+        if answer < params.answers {
+            packed |= pattern(guess, answers[answer]) << (8u * k);
+        }
+    }
+    patterns[row * params.quads + quad] = packed;
+}
@@ -0,0 +1,175 @@
+//! Grading the guesses of a game after the fact, e.g. one played by a human, against the guesses
+//! revealing the most information at every turn.
+
+use serde::Serialize;
+
+use crate::algorithms::{entropy, Candidates, PatternCache};
+use crate::{Correctness, Guess};
+
+/// How a guess compared to the best one at its turn.
+///
+/// Every candidate is considered as likely to be the answer as any other, so that the number of
+/// candidates is what a human would expect.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Grade {
+    pub word: String,
+    /// How many words could still be the answer before the guess.
+    pub candidates: usize,
+    /// The information the guess was expected to reveal, in bits.
+    pub entropy: f64,
+    /// 1 if no word of the dictionary was expected to reveal more information than the guess, 2
+    /// if a single one was, and so on.
+    pub rank: usize,
+    /// The word expected to reveal the most information, preferring words that could still be the
+    /// answer.
+    pub best: &'static str,
+    pub best_entropy: f64,
+    /// How many candidates the guess was expected to leave.
+    pub expected_remaining: f64,
+    /// How many candidates the best word was expected to leave.
+    pub best_expected_remaining: f64,
+    /// How many candidates the guess actually left.
+    pub remaining: usize,
+}
+
+impl Grade {
+    /// Returns how much less information the guess was expected to reveal than the best word, in
+    /// bits.
+    pub fn entropy_lost(&self) -> f64 {
+        (self.best_entropy - self.entropy).max(0.0)
+    }
+}
+
+/// Grades every guess of `guesses`, which may be any five-letter word, when the answer is one of
+/// `answers` and guesses are ranked among all the words of `cache`.
+///
+/// Grading stops after the first guess leaving no candidate, i.e. whose colors no answer agrees
+/// with, so the last grade has no `remaining` candidate if the colors are inconsistent.
+pub fn grade(cache: &PatternCache, answers: &Candidates, guesses: &[Guess]) -> Vec<Grade> {
+    let mut remaining = answers.clone();
+    let mut grades = Vec::new();
+    for guess in guesses {
+        if remaining.is_empty() {
+            break;
+        }
+        let candidates: Vec<(usize, f64)> = remaining.iter().map(|index| (index, 1.0)).collect();
+
+        let scores: Vec<f64> = (0..cache.len())
+            .map(|index| entropy(cache.row(index), &candidates))
+            .collect();
+        let best = (0..cache.len())
+            .max_by(|&a, &b| {
+                let key = |index: usize| (scores[index], remaining.contains(index));
+                key(a)
+                    .partial_cmp(&key(b))
+                    .expect("entropy is never NaN")
+                    // Prefer the first of equal words.
+                    .then(b.cmp(&a))
+            })
+            .expect("there is at least one candidate in the dictionary");
+
+        let patterns = match cache.index(&guess.word) {
+            Some(index) => cache.row(index).to_vec(),
+            None => {
+                let mut patterns = vec![0; cache.len()];
+                for &(candidate, _) in &candidates {
+                    patterns[candidate] =
+                        Correctness::pack(Correctness::compute(cache.word(candidate), &guess.word));
+                }
+                patterns
+            }
+        };
+        let score = entropy(&patterns, &candidates);
+        // Entropies are sums of floats, so the very same buckets may differ in the last bits.
+        let rank = 1 + scores.iter().filter(|&&s| s > score + 1e-9).count();
+
+        let before = remaining.len();
+        let pattern = Correctness::pack(guess.mask);
+        remaining.retain(|candidate| patterns[candidate] == pattern);
+
+        grades.push(Grade {
+            word: guess.word.to_string(),
+            candidates: before,
+            entropy: score,
+            rank,
+            best: cache.word(best),
+            best_entropy: scores[best],
+            expected_remaining: expected_remaining(&patterns, &candidates),
+            best_expected_remaining: expected_remaining(cache.row(best), &candidates),
+            remaining: remaining.len(),
+        });
+    }
+    grades
+}
+
+/// Returns how many of the equally likely `candidates` a guess revealing `patterns` is expected to
+/// leave.
+fn expected_remaining(patterns: &[u8], candidates: &[(usize, f64)]) -> f64 {
+    let mut sizes = [0usize; Correctness::PATTERNS];
+    for &(candidate, _) in candidates {
+        sizes[patterns[candidate] as usize] += 1;
+    }
+    let squares: usize = sizes.iter().map(|size| size * size).sum();
+    squares as f64 / candidates.len() as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::grade;
+    use crate::algorithms::{Candidates, PatternCache};
+    use crate::{Correctness, Guess};
+
+    const WORDS: &[&str] = &["abcde", "abcdf", "abcdg", "efgab"];
+
+    fn play(answer: &str, words: &[&str]) -> Vec<Guess> {
+        words
+            .iter()
+            .map(|&word| Guess::new(word.to_string(), Correctness::compute(answer, word)))
+            .collect()
+    }
+
+    #[test]
+    fn grades() {
+        let cache = PatternCache::new(WORDS.iter().copied());
+        let answers = Candidates::all(cache.len());
+        let grades = grade(&cache, &answers, &play("abcdf", &["abcde", "abcdf"]));
+        assert_eq!(grades.len(), 2);
+
+        // Only "efgab" tells every word apart.
+        let first = &grades[0];
+        assert_eq!((first.best, first.best_entropy), ("efgab", 2.0));
+        assert_eq!((first.candidates, first.remaining, first.rank), (4, 2, 2));
+        assert_eq!(first.entropy_lost(), 0.5);
+        assert_eq!(first.expected_remaining, 1.5);
+        assert_eq!(first.best_expected_remaining, 1.0);
+
+        let second = &grades[1];
+        assert_eq!(
+            (second.candidates, second.remaining, second.rank),
+            (2, 1, 1)
+        );
+        assert_eq!(second.entropy_lost(), 0.0);
+    }
+
+    #[test]
+    fn words_outside_the_dictionary() {
+        let cache = PatternCache::new(WORDS.iter().copied());
+        let answers = Candidates::all(cache.len());
+        let grades = grade(&cache, &answers, &play("efgab", &["xyzab"]));
+        assert_eq!(grades[0].remaining, 1);
+        // Every word of the dictionary reveals more.
+        assert_eq!(grades[0].rank, 5);
+    }
+
+    #[test]
+    fn stops_at_inconsistent_colors() {
+        let cache = PatternCache::new(WORDS.iter().copied());
+        let answers = Candidates::all(cache.len());
+        let mut guesses = play("abcde", &["abcde", "abcdf"]);
+        guesses[0].mask = [Correctness::Correct; 5];
+        guesses[0].word = "zzzzz".into();
+        let grades = grade(&cache, &answers, &guesses);
+        assert_eq!(grades.len(), 1);
+        assert_eq!(grades[0].remaining, 0);
+    }
+}
@@ -0,0 +1,145 @@
+//! The guesses of a game so far, as handed to guessers, see [`History`].
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::iter::FromIterator;
+use std::ops::Deref;
+use std::sync::OnceLock;
+
+use crate::constraints::{Constraints, LetterCount};
+use crate::Guess;
+
+/// The guesses played so far in a game, in order.
+///
+/// It dereferences to a slice of guesses, and also keeps the [`Constraints`] the guesses reveal
+/// once they are asked for, so that checking words against the whole history costs the same no
+/// matter how long it is. Pushing a guess narrows the constraints down with it rather than
+/// computing them again.
+#[derive(Clone, Default)]
+pub struct History<const N: usize = 5> {
+    guesses: Vec<Guess<N>>,
+    constraints: OnceLock<Constraints<N>>,
+}
+
+impl<const N: usize> History<N> {
+    /// Creates the history of a game that has not started yet.
+    pub fn new() -> Self {
+        Self {
+            guesses: Vec::new(),
+            constraints: OnceLock::new(),
+        }
+    }
+
+    /// Records the next guess of the game.
+    pub fn push(&mut self, guess: Guess<N>) {
+        if let Some(constraints) = self.constraints.get_mut() {
+            constraints.add(&guess);
+        }
+        self.guesses.push(guess);
+    }
+
+    /// Returns everything the guesses reveal about the answer, computed the first time it is
+    /// asked for.
+    pub fn constraints(&self) -> &Constraints<N> {
+        self.constraints
+            .get_or_init(|| Constraints::from_history(&self.guesses))
+    }
+
+    /// Returns how many times every letter guessed so far appears in the answer, as far as the
+    /// masks tell.
+    pub fn letter_counts(&self) -> BTreeMap<char, LetterCount> {
+        self.constraints().letter_counts()
+    }
+
+    /// Returns whether `word` could still be the answer, i.e. whether it matches every guess.
+    pub fn matches(&self, word: &str) -> bool {
+        self.constraints().matches(word)
+    }
+
+    /// Returns the guesses, in order.
+    pub fn into_guesses(self) -> Vec<Guess<N>> {
+        self.guesses
+    }
+}
+
+impl<const N: usize> Deref for History<N> {
+    type Target = [Guess<N>];
+
+    fn deref(&self) -> &Self::Target {
+        &self.guesses
+    }
+}
+
+impl<const N: usize> From<Vec<Guess<N>>> for History<N> {
+    fn from(guesses: Vec<Guess<N>>) -> Self {
+        Self {
+            guesses,
+            constraints: OnceLock::new(),
+        }
+    }
+}
+
+impl<const N: usize> From<&[Guess<N>]> for History<N> {
+    fn from(guesses: &[Guess<N>]) -> Self {
+        Self::from(guesses.to_vec())
+    }
+}
+
+impl<const N: usize> FromIterator<Guess<N>> for History<N> {
+    fn from_iter<I: IntoIterator<Item = Guess<N>>>(iter: I) -> Self {
+        Self::from(iter.into_iter().collect::<Vec<_>>())
+    }
+}
+
+impl<const N: usize> PartialEq for History<N> {
+    fn eq(&self, other: &Self) -> bool {
+        self.guesses == other.guesses
+    }
+}
+
+impl<const N: usize> Eq for History<N> {}
+
+impl<const N: usize> fmt::Debug for History<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(&self.guesses).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::History;
+    use crate::{Correctness, Guess};
+
+    #[test]
+    fn narrows_the_constraints_down_on_push() {
+        let mut history: History = History::new();
+        history.push(Guess::new("fight", Correctness::compute("right", "fight")));
+        assert!(history.matches("light"));
+        assert!(!history.matches("wrong"));
+
+        history.push(Guess::new("light", Correctness::compute("right", "light")));
+        assert!(!history.matches("light"));
+        assert!(history.matches("right"));
+        let words = ["fight", "light", "might", "right"];
+        for word in words {
+            assert_eq!(
+                history.matches(word),
+                history.iter().all(|guess| guess.matches(word))
+            );
+        }
+        assert_eq!(history.len(), 2);
+    }
+
+    #[test]
+    fn letter_counts() {
+        let guess = Guess::new("sheen", Correctness::compute("elder", "sheen"));
+        let e = guess.letter_counts()[&'e'];
+        assert_eq!((e.min, e.max), (2, None));
+
+        // The gray third "e" of "eerie" tells there is no other one.
+        let mut history: History = History::from(vec![guess]);
+        history.push(Guess::new("eerie", Correctness::compute("elder", "eerie")));
+        assert_eq!(history.letter_counts()[&'e'].exact(), Some(2));
+        assert_eq!(history.letter_counts()[&'i'].exact(), Some(0));
+    }
+}
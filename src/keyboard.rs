@@ -0,0 +1,99 @@
+//! The keyboard of the official game, which colors every letter with the best hint it got so far,
+//! e.g. to show players which letters are left to try.
+
+use std::collections::BTreeMap;
+
+use crate::{Correctness, Guess};
+
+/// The rows of letters of the keyboard, as laid out in the official game.
+pub const LAYOUT: [&str; 3] = ["qwertyuiop", "asdfghjkl", "zxcvbnm"];
+
+/// What the hints so far tell about a letter, from the least to the most.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub enum KeyState {
+    /// The letter was not guessed yet.
+    #[default]
+    Unused,
+    /// The letter was only ever gray, so the answer does not have it.
+    Absent,
+    /// The letter was yellow, but never green.
+    Present,
+    /// The letter was green at least once.
+    Correct,
+}
+
+impl From<Correctness> for KeyState {
+    fn from(c: Correctness) -> Self {
+        match c {
+            Correctness::Correct => KeyState::Correct,
+            Correctness::Misplaced => KeyState::Present,
+            Correctness::Wrong => KeyState::Absent,
+        }
+    }
+}
+
+/// The state of every letter guessed so far.
+///
+/// A letter guessed twice may be gray on one tile and yellow or green on the other, in which case
+/// the answer has it, so every letter keeps the best hint it got.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Keyboard {
+    keys: BTreeMap<char, KeyState>,
+}
+
+impl Keyboard {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn from_history<const N: usize>(history: &[Guess<N>]) -> Self {
+        let mut keyboard = Self::new();
+        for guess in history {
+            keyboard.add(guess);
+        }
+        keyboard
+    }
+
+    /// Updates the keys with the hints of another guess.
+    pub fn add<const N: usize>(&mut self, guess: &Guess<N>) {
+        for (letter, &c) in guess.word.chars().zip(&guess.mask) {
+            let key = self.keys.entry(letter).or_default();
+            *key = (*key).max(c.into());
+        }
+    }
+
+    pub fn state(&self, letter: char) -> KeyState {
+        self.keys.get(&letter).copied().unwrap_or_default()
+    }
+
+    /// Returns whether the answer is known not to have the letter.
+    pub fn is_absent(&self, letter: char) -> bool {
+        self.state(letter) == KeyState::Absent
+    }
+
+    /// Returns every letter guessed so far along with its state, in alphabetical order.
+    pub fn keys(&self) -> impl Iterator<Item = (char, KeyState)> + '_ {
+        self.keys.iter().map(|(&letter, &state)| (letter, state))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{KeyState, Keyboard};
+    use crate::Guess;
+
+    #[test]
+    fn keeps_the_best_hint() {
+        let keyboard = Keyboard::from_history(&[
+            Guess::new("humph", mask!(W W W W C)),
+            Guess::new("blush", mask!(W W M W C)),
+        ]);
+        assert_eq!(keyboard.state('h'), KeyState::Correct);
+        assert_eq!(keyboard.state('u'), KeyState::Present);
+        assert_eq!(keyboard.state('m'), KeyState::Absent);
+        assert_eq!(keyboard.state('z'), KeyState::Unused);
+        assert!(keyboard.is_absent('b'));
+        assert!(!keyboard.is_absent('u'));
+        assert_eq!(keyboard.keys().count(), 7);
+    }
+}
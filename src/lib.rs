@@ -1,251 +1,936 @@
-use std::collections::HashSet;
-use std::iter::FromIterator;
+//! A Wordle solver.
+//!
+//! The rules of the game in [`rules`], along with [`constraints`] and [`word`], only need
+//! `alloc`: building without default features leaves just those, for embedded targets without
+//! the standard library. The guessers and the games they play need the `solver` feature, e.g. for
+//! WASM, and reading or writing files, from the dictionary files to the caches, needs `std`.
 
+#![cfg_attr(not(feature = "solver"), no_std)]
+
+extern crate alloc;
+
+#[cfg(feature = "solver")]
+use std::borrow::Cow;
+#[cfg(feature = "solver")]
+use std::cmp::Reverse;
+#[cfg(feature = "solver")]
+use std::collections::{HashMap, HashSet};
+#[cfg(feature = "std")]
+use std::fs;
+#[cfg(feature = "std")]
+use std::path::Path;
+#[cfg(feature = "solver")]
+use std::time::{Duration, Instant};
+
+#[cfg(feature = "solver")]
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "solver")]
+pub use crate::builder::{BuildError, WordleBuilder};
+#[cfg(feature = "solver")]
+pub use crate::cancel::CancelToken;
+#[cfg(feature = "solver")]
+pub use crate::dictionary::{DictVersion, Dictionary};
+#[cfg(feature = "solver")]
+pub use crate::error::{Error, Result};
+#[cfg(feature = "solver")]
+pub use crate::estimate::Estimate;
+#[cfg(feature = "solver")]
+pub use crate::explanation::Explanation;
+#[cfg(feature = "solver")]
+use crate::game::{Game, GameState, TurnEvent};
+#[cfg(feature = "solver")]
+pub use crate::history::History;
+pub use crate::rules::{Correctness, Guess};
+pub use crate::word::{InvalidWord, Word};
+
+#[cfg(test)]
+macro_rules! guesser {
+    (|$history:ident| $impl:block) => {{
+        struct G;
+        impl $crate::Guesser for G {
+            fn guess(&mut self, $history: &$crate::History) -> std::borrow::Cow<'static, str> {
+                $impl
+            }
+        }
+        G
+    }};
+}
+
+#[cfg(test)]
+macro_rules! mask {
+    (C) => { $crate::Correctness::Correct };
+    (M) => { $crate::Correctness::Misplaced };
+    (W) => { $crate::Correctness::Wrong };
+    ($($c:tt)+) => {
+        [$(mask!($c)),+]
+    }
+}
+
+/// A dozen answers of the official game with made-up counts, for the tests to play games over.
+#[cfg(test)]
+const WORDS: &[(&str, usize)] = &[
+    ("cigar", 10),
+    ("rebut", 8),
+    ("sissy", 3),
+    ("humph", 2),
+    ("awake", 7),
+    ("blush", 6),
+    ("focal", 5),
+    ("evade", 4),
+    ("naval", 3),
+    ("serve", 2),
+    ("heath", 1),
+    ("dwarf", 1),
+];
+
+#[cfg(feature = "solver")]
 pub mod algorithms;
+#[cfg(feature = "solver")]
+pub mod analysis;
+#[cfg(feature = "solver")]
+pub mod assistant;
+#[cfg(feature = "std")]
+pub mod batch;
+#[cfg(feature = "solver")]
+pub mod bench;
+#[cfg(feature = "solver")]
+pub mod builder;
+#[cfg(feature = "solver")]
+pub mod cancel;
+#[cfg(feature = "std")]
+pub mod config;
+pub mod constraints;
+#[cfg(feature = "solver")]
+pub mod counterfactual;
+#[cfg(feature = "std")]
+pub mod daily;
+#[cfg(feature = "db")]
+pub mod db;
+#[cfg(feature = "solver")]
+pub mod dictionary;
+#[cfg(feature = "solver")]
+pub mod difficulty;
+#[cfg(feature = "solver")]
+pub mod error;
+#[cfg(feature = "solver")]
+pub mod estimate;
+#[cfg(feature = "solver")]
+pub mod explanation;
+#[cfg(feature = "solver")]
+pub mod fibble;
+#[cfg(feature = "solver")]
+pub mod game;
+#[cfg(feature = "gpu")]
+pub mod gpu;
+#[cfg(feature = "solver")]
+pub mod grade;
+#[cfg(feature = "solver")]
+pub mod history;
+#[cfg(feature = "solver")]
+pub mod keyboard;
+#[cfg(feature = "solver")]
+pub mod multi;
+#[cfg(feature = "solver")]
+pub mod render;
+pub mod rules;
+#[cfg(feature = "serve")]
+pub mod serve;
+#[cfg(feature = "simd")]
+pub mod simd;
+#[cfg(feature = "solver")]
+pub mod simulate;
+#[cfg(feature = "solver")]
+pub mod stats;
+#[cfg(feature = "std")]
+pub mod tournament;
+#[cfg(feature = "tui")]
+pub mod tui;
+#[cfg(feature = "solver")]
+pub mod validation;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+pub mod word;
 
 const DICTIONARY: &str = include_str!("../dictionary.txt");
+const ANSWERS: &str = include_str!("../answers.txt");
+
+/// Returns the answers of the official game, in the order they were published.
+pub fn builtin_answers() -> impl Iterator<Item = &'static str> + Clone {
+    ANSWERS.split_whitespace()
+}
+
+/// Returns the words of the built-in dictionary along with their frequency counts.
+pub fn builtin_dictionary() -> impl Iterator<Item = (&'static str, usize)> + Clone {
+    DICTIONARY.lines().map(|line| {
+        parse_dictionary_line(line)
+            .expect("every line must have the answer and its frequency count")
+    })
+}
+
+/// Reads a dictionary file in the same format as the built-in one: one word per line, followed by
+/// a space and the frequency count of the word.
+///
+/// The contents of the file are kept alive for the rest of the program, so that the words can be
+/// handed out by guessers without allocating.
+#[cfg(feature = "std")]
+pub fn load_dictionary(path: impl AsRef<Path>) -> Result<Vec<(&'static str, usize)>> {
+    let contents: &'static str = Box::leak(fs::read_to_string(path)?.into_boxed_str());
+    parse_dictionary(contents)
+}
 
-pub struct Wordle {
-    dictionary: HashSet<&'static str>,
+/// Parses the contents of a dictionary file, see [`load_dictionary`].
+///
+/// The words must be distinct five-letter words with a non-zero frequency count. Otherwise, the
+/// error is an [`Error::Dictionary`] listing every problem. There can be at most
+/// [`MAX_WORDS`](dictionary::MAX_WORDS) of them, or the error is an [`Error::TooManyWords`].
+#[cfg(feature = "solver")]
+pub fn parse_dictionary(contents: &str) -> Result<Vec<(&str, usize)>> {
+    parse_dictionary_of(contents, 5)
+}
+
+/// Parses the contents of a dictionary file whose words are `len` letters long.
+#[cfg(feature = "solver")]
+pub(crate) fn parse_dictionary_of(contents: &str, len: usize) -> Result<Vec<(&str, usize)>> {
+    let diagnostics = validation::validate(contents, len);
+    if !diagnostics.is_empty() {
+        return Err(validation::InvalidDictionary { diagnostics }.into());
+    }
+
+    let words: Vec<_> = contents
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| parse_dictionary_line(line).expect("every line was validated"))
+        .collect();
+    if words.len() > dictionary::MAX_WORDS {
+        return Err(Error::TooManyWords {
+            len: words.len(),
+            max: dictionary::MAX_WORDS,
+        });
+    }
+    Ok(words)
 }
 
+pub(crate) fn parse_dictionary_line(line: &str) -> Option<(&str, usize)> {
+    let (word, count) = line.split_once(' ')?;
+    Some((word, count.trim().parse().ok()?))
+}
+
+/// 64-bit FNV-1a, a hash that is stable across runs and platforms unlike the standard hasher.
+#[cfg(feature = "solver")]
+fn fnv1a(bytes: impl IntoIterator<Item = u8>) -> u64 {
+    const OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0100_0000_01b3;
+
+    bytes.into_iter().fold(OFFSET, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(PRIME)
+    })
+}
+
+/// Number of guesses the official game allows.
+pub const OFFICIAL_MAX_GUESSES: usize = 6;
+
+/// Number of guesses a [`Wordle`] allows unless configured otherwise.
+///
+/// This is well above [`OFFICIAL_MAX_GUESSES`] in order to avoid cutting off the score
+/// distribution for stats purposes.
+pub const DEFAULT_MAX_GUESSES: usize = 32;
+
+/// A game of Wordle where answers and guesses are words of `N` letters.
+#[cfg(feature = "solver")]
+pub struct Wordle<const N: usize = 5> {
+    allowed_guesses: HashSet<&'static str>,
+    /// Sorted, and usually a small subset of the allowed guesses.
+    answers: Vec<&'static str>,
+    hard_mode: bool,
+    /// `None` if the guesser may keep guessing until it finds the answer.
+    max_guesses: Option<usize>,
+}
+
+#[cfg(feature = "solver")]
 impl Wordle {
+    /// Creates a game like the official one, where any word of the built-in dictionary may be
+    /// guessed but only the words of the built-in answer list are answers.
     pub fn new() -> Self {
+        Self::with_builtin(DictVersion::default())
+    }
+
+    /// Creates a game like the official one, with the word lists it had at the given version.
+    pub fn with_builtin(version: DictVersion) -> Self {
+        Self::with_dictionary(version.dictionary()).with_answers(version.answers())
+    }
+}
+
+#[cfg(feature = "solver")]
+impl<const N: usize> Wordle<N> {
+    /// Creates a game whose valid guesses are the words of the given dictionary, e.g. one read
+    /// with [`load_dictionary`]. Every word must be `N` letters long.
+    ///
+    /// Every word of the dictionary is a possible answer, unless restricted with
+    /// [`Wordle::with_answers`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if a word is not `N` letters long, see [`Wordle::try_with_dictionary`].
+    pub fn with_dictionary(words: impl IntoIterator<Item = (&'static str, usize)>) -> Self {
+        let allowed_guesses: HashSet<_> = words.into_iter().map(|(word, _)| word).collect();
+        assert!(
+            allowed_guesses.iter().all(|word| word.chars().count() == N),
+            "every word in the dictionary must be {} letters long",
+            N
+        );
+
+        let mut answers: Vec<_> = allowed_guesses.iter().copied().collect();
+        answers.sort_unstable();
+
         Self {
-            dictionary: HashSet::from_iter(DICTIONARY.lines().map(|line| {
-                line.split_once(' ')
-                    .expect("every line must have the answer and its frequency count")
-                    .0
-            })),
+            allowed_guesses,
+            answers,
+            hard_mode: false,
+            max_guesses: Some(DEFAULT_MAX_GUESSES),
         }
     }
 
-    pub fn play<G: Guesser>(&self, answer: &'static str, mut guesser: G) -> Option<usize> {
-        let mut history: Vec<Guess> = Vec::new();
-        // Wordle only allows six guesses.
-        // We allow more in order to avoid cutting off the score distribution for stats purposes.
-        for i in 1..=32 {
-            let guess = guesser.guess(&history);
-            // Check that it's a valid answer.
-            assert!(self.dictionary.contains(&*guess));
+    /// Like [`Wordle::with_dictionary`], but fails with [`BuildError::WrongLength`] rather than
+    /// panicking if a word is not `N` letters long, e.g. for words read from a file.
+    pub fn try_with_dictionary(
+        words: impl IntoIterator<Item = (&'static str, usize)>,
+    ) -> Result<Self> {
+        let words: Vec<_> = words.into_iter().collect();
+        check_lengths::<N>(words.iter().map(|&(word, _)| word))?;
+        Ok(Self::with_dictionary(words))
+    }
 
-            if guess == answer {
-                return Some(i);
-            }
+    /// Sets the words that may be the answer, e.g. the official answer list which is much smaller
+    /// than the list of allowed guesses. Every word must be `N` letters long.
+    ///
+    /// Answers should also be allowed guesses, or the guesser has no way of finding them.
+    ///
+    /// # Panics
+    ///
+    /// Panics if an answer is not `N` letters long, see [`Wordle::try_with_answers`].
+    pub fn with_answers(mut self, answers: impl IntoIterator<Item = &'static str>) -> Self {
+        let mut answers: Vec<_> = answers.into_iter().collect();
+        assert!(
+            answers.iter().all(|word| word.chars().count() == N),
+            "every answer must be {} letters long",
+            N
+        );
+        answers.sort_unstable();
+        answers.dedup();
 
-            let correctness = Correctness::compute(answer, &guess);
-            history.push(Guess {
-                word: guess,
-                mask: correctness,
-            });
-        }
+        self.answers = answers;
+        self
+    }
 
-        None
+    /// Like [`Wordle::with_answers`], but fails with [`BuildError::WrongLength`] rather than
+    /// panicking if an answer is not `N` letters long.
+    pub fn try_with_answers(self, answers: impl IntoIterator<Item = &'static str>) -> Result<Self> {
+        let answers: Vec<_> = answers.into_iter().collect();
+        check_lengths::<N>(answers.iter().copied())?;
+        Ok(self.with_answers(answers))
     }
-}
 
-#[derive(Debug, Copy, Clone, Eq, PartialEq)]
-pub enum Correctness {
-    /// Green
-    Correct,
-    /// Yellow
-    Misplaced,
-    /// Gray
-    Wrong,
-}
+    /// Returns the words that may be the answer, in alphabetical order.
+    pub fn answers(&self) -> &[&'static str] {
+        &self.answers
+    }
 
-impl Correctness {
-    fn compute(answer: &str, guess: &str) -> [Self; 5] {
-        assert_eq!(answer.len(), 5);
-        assert_eq!(guess.len(), 5);
+    /// Returns whether `word` may be guessed.
+    pub fn is_allowed(&self, word: &str) -> bool {
+        self.allowed_guesses.contains(word)
+    }
 
-        let mut c = [Correctness::Wrong; 5];
-        // Mark letters green.
-        for (i, (a, g)) in answer.chars().zip(guess.chars()).enumerate() {
-            if a == g {
-                c[i] = Correctness::Correct;
-            }
+    /// Checks that `guess` may be played, e.g. a word typed in by a player, telling why not if
+    /// it has the wrong number of letters, other characters than lowercase letters, or is not in
+    /// the dictionary.
+    pub fn validate_guess(&self, guess: &str) -> Result<(), GuessError> {
+        validate_word::<N>(guess)?;
+        if !self.is_allowed(guess) {
+            return Err(GuessError::NotInDictionary(guess.to_string()));
         }
 
-        let mut used = [false; 5];
-        for (i, &c) in c.iter().enumerate() {
-            if c == Correctness::Correct {
-                used[i] = true;
-            }
+        Ok(())
+    }
+
+    /// Enables or disables hard mode, where every guess must reuse all the green letters in place
+    /// and all the yellow letters revealed by the previous guesses.
+    pub fn with_hard_mode(mut self, hard_mode: bool) -> Self {
+        self.hard_mode = hard_mode;
+        self
+    }
+
+    /// Sets how many guesses the guesser gets before the game is lost, e.g.
+    /// [`OFFICIAL_MAX_GUESSES`] to follow the official rules, or `None` to let it keep guessing
+    /// until it finds the answer. Defaults to [`DEFAULT_MAX_GUESSES`].
+    ///
+    /// Without a limit, a game only ends once the guesser plays the answer, so guessers that may
+    /// never find it should not be played that way.
+    pub fn with_max_guesses(mut self, max_guesses: Option<usize>) -> Self {
+        self.max_guesses = max_guesses;
+        self
+    }
+
+    /// Returns how many guesses the guesser gets, if limited.
+    pub fn max_guesses(&self) -> Option<usize> {
+        self.max_guesses
+    }
+
+    /// Returns whether another guess may be played after `played` guesses.
+    fn has_guesses_left(&self, played: usize) -> bool {
+        self.max_guesses.is_none_or(|max| played < max)
+    }
+
+    /// Plays a game with the given answer, returning the number of guesses it took to find it, or
+    /// `None` if the guesser did not find it at all.
+    ///
+    /// Fails if the answer is not `N` letters long, or if the guesser plays an invalid word.
+    pub fn play<G: Guesser<N>>(
+        &self,
+        answer: &str,
+        guesser: G,
+    ) -> Result<Option<usize>, PlayError> {
+        self.play_transcript(answer, guesser)
+            .map(|transcript| transcript.score)
+    }
+
+    /// Like [`Wordle::play`], but through dynamic dispatch, e.g. for guessers picked at runtime
+    /// out of the [registry](crate::algorithms::registry()) or compared against each other.
+    ///
+    /// The game logic is only compiled once for all guessers instead of once per guesser type.
+    pub fn play_dyn(
+        &self,
+        answer: &str,
+        guesser: &mut dyn Guesser<N>,
+    ) -> Result<Option<usize>, PlayError> {
+        self.play(answer, guesser)
+    }
+
+    /// Like [`Wordle::play`], but also returns every guess played along the way.
+    pub fn play_transcript<G: Guesser<N>>(
+        &self,
+        answer: &str,
+        guesser: G,
+    ) -> Result<Transcript<N>, PlayError> {
+        let mut game = self.game(answer, guesser)?;
+        for step in &mut game {
+            step?;
         }
 
-        // Mark letters yellow.
-        for (i, g) in guess.chars().enumerate() {
-            if c[i] == Correctness::Correct {
-                continue;
-            }
+        Ok(game.into_transcript())
+    }
 
-            if answer.chars().enumerate().any(|(i, a)| {
-                if a == g && !used[i] {
-                    used[i] = true;
-                    return true;
-                }
+    /// Like [`Wordle::play_transcript`], but also measures how long the game took, so that
+    /// reports and records of the game need nothing else.
+    pub fn play_recorded<G: Guesser<N>>(
+        &self,
+        answer: &str,
+        guesser: G,
+    ) -> Result<GameResult<N>, PlayError> {
+        let start = Instant::now();
+        let transcript = self.play_transcript(answer, guesser)?;
+        Ok(GameResult {
+            transcript,
+            elapsed: start.elapsed(),
+        })
+    }
 
-                false
-            }) {
-                c[i] = Correctness::Misplaced;
-            }
+    /// Like [`Wordle::play_transcript`], but also hands a [`TurnEvent`] to `on_turn` as soon as
+    /// every turn is played, so that a frontend can show the game live, e.g. by sending the events
+    /// over a channel to its UI thread with `|event| tx.send(event).unwrap_or_default()`.
+    pub fn play_streaming<G: Guesser<N>>(
+        &self,
+        answer: &str,
+        guesser: G,
+        mut on_turn: impl FnMut(TurnEvent<N>),
+    ) -> Result<Transcript<N>, PlayError> {
+        let mut game = self.game(answer, guesser)?.track_answers();
+        loop {
+            let start = Instant::now();
+            let step = match game.next() {
+                Some(step) => step?,
+                None => break,
+            };
+            on_turn(TurnEvent {
+                turn: game.history().len(),
+                guess: step.guess,
+                remaining: step.remaining.len(),
+                eliminated: step.eliminated,
+                elapsed: start.elapsed(),
+            });
         }
 
-        c
+        Ok(game.into_transcript())
     }
-}
 
-pub struct Guess {
-    pub word: String,
-    pub mask: [Correctness; 5],
-}
+    /// Like [`Wordle::play_transcript`], but the guesser only searches for its guesses until
+    /// `deadline`, and then settles for the best guesses it finds right away, see
+    /// [`Guesser::set_cancel_token`].
+    ///
+    /// The game still goes on past the deadline, which bounds how long the guesser thinks rather
+    /// than how many guesses it gets.
+    pub fn play_with_deadline<G: Guesser<N>>(
+        &self,
+        answer: &str,
+        mut guesser: G,
+        deadline: Instant,
+    ) -> Result<Transcript<N>, PlayError> {
+        guesser.set_cancel_token(CancelToken::new().with_deadline(deadline));
+        self.play_transcript(answer, guesser)
+    }
 
-impl Guess {
-    pub fn new(word: String, mask: [Correctness; 5]) -> Self {
-        Self { word, mask }
+    /// Starts a game with the given answer, which plays one guess every time it is advanced, so
+    /// that every turn can be inspected, along with the candidates it leaves once
+    /// [`Game::track_answers`] is called.
+    ///
+    /// Fails if the answer is not `N` letters long.
+    pub fn game<'a, G: Guesser<N>>(
+        &'a self,
+        answer: &'a str,
+        guesser: G,
+    ) -> Result<Game<'a, G, N>, PlayError> {
+        Game::new(self, answer, guesser)
     }
 
-    pub fn matches(&self, word: &str) -> bool {
-        assert_eq!(self.word.len(), 5);
-        assert_eq!(word.len(), 5);
+    /// Resumes a game saved with [`Game::state`], replaying the previous guesses to the guesser.
+    ///
+    /// Fails if the saved answer is not one of the answers of this game, or if the saved guesses
+    /// were not played against it.
+    pub fn resume<G: Guesser<N>>(
+        &self,
+        state: GameState<N>,
+        guesser: G,
+    ) -> Result<Game<'_, G, N>, PlayError> {
+        Game::resume(self, state, guesser)
+    }
 
-        let mut used = [false; 5];
-        for (i, ((g, &m), w)) in self
-            .word
-            .chars()
-            .zip(&self.mask)
-            .zip(word.chars())
-            .enumerate()
+    /// Plays a game of Absurdle against the guesser: instead of picking the answer up front, the
+    /// host answers every guess with the pattern that keeps the most of the given candidates
+    /// alive, and only settles on an answer once a single candidate is left and gets guessed.
+    ///
+    /// When several patterns keep as many candidates, the one revealing the fewest green and then
+    /// yellow letters wins, and then the one first seen in the order of the candidates. The
+    /// answer of the transcript is the word the host was forced into, or the first candidate left
+    /// if the guesser gave up.
+    ///
+    /// Candidates that are not in the dictionary are ignored, since they could never be guessed.
+    /// Fails if no candidate is left, if a candidate is not `N` letters long, or if the guesser
+    /// plays an invalid word.
+    pub fn play_adversarial<'a, G: Guesser<N>>(
+        &self,
+        candidates: impl IntoIterator<Item = &'a str>,
+        mut guesser: G,
+    ) -> Result<Transcript<N>, PlayError> {
+        let mut remaining: Vec<&str> = candidates.into_iter().collect();
+        if let Some(candidate) = remaining
+            .iter()
+            .find(|candidate| candidate.chars().count() != N)
         {
-            if m == Correctness::Correct {
-                if g != w {
-                    return false;
-                } else {
-                    used[i] = true;
-                }
-            }
+            return Err(PlayError::InvalidAnswer(candidate.to_string()));
+        }
+        remaining.retain(|candidate| self.is_allowed(candidate));
+        if remaining.is_empty() {
+            return Err(PlayError::InvalidAnswer(String::new()));
         }
 
-        dbg!(used);
+        guesser.set_hard_mode(self.hard_mode);
 
-        for (i, (w, &m)) in word.chars().zip(&self.mask).enumerate() {
-            if m == Correctness::Correct {
-                // Must be correct, or we would have returned in the earlier loop.
-                continue;
-            }
+        let _span = tracing::info_span!("absurdle", candidates = remaining.len()).entered();
+        let mut history: History<N> = History::new();
+        while self.has_guesses_left(history.len()) {
+            let guess = self.check(guesser.guess(&history), &history)?;
 
-            let mut plausible = true;
-            if self
-                .word
-                .chars()
-                .zip(&self.mask)
-                .enumerate()
-                .any(|(j, (g, m))| {
-                    if g != w {
-                        return false;
-                    }
-                    if used[j] {
-                        return false;
-                    }
+            // Group the candidates by the pattern they would reveal, keeping the order in which
+            // the patterns are first seen so that ties are broken deterministically.
+            let mut buckets: Vec<([Correctness; N], Vec<&str>)> = Vec::new();
+            for candidate in remaining {
+                let mask = Correctness::compute(candidate, &guess);
+                match buckets.iter_mut().find(|(m, _)| *m == mask) {
+                    Some((_, bucket)) => bucket.push(candidate),
+                    None => buckets.push((mask, vec![candidate])),
+                }
+            }
 
-                    match m {
-                        Correctness::Correct => unreachable!("we shouldn't reach this point"),
-                        Correctness::Misplaced if j == i => {
-                            // `w` was misplaced the last time around, which
-                            // means that the word cannot be the answer.
-                            plausible = false;
-                            return false;
-                        }
-                        Correctness::Misplaced => {
-                            used[j] = true;
-                            return true;
-                        }
-                        Correctness::Wrong => {
-                            // TODO: early return
-                            plausible = false;
-                            return false;
-                        }
-                    }
+            let hints =
+                |mask: &[Correctness; N], color| mask.iter().filter(|&&c| c == color).count();
+            let (mask, bucket) = buckets
+                .into_iter()
+                .rev()
+                .max_by_key(|(mask, bucket)| {
+                    (
+                        bucket.len(),
+                        Reverse(hints(mask, Correctness::Correct)),
+                        Reverse(hints(mask, Correctness::Misplaced)),
+                    )
                 })
-                && plausible
-            {
-                // The character `w` was either guessed correctly, or was previously misplaced.
-            } else if !plausible {
-                return false;
-            } else {
-                // We have no information about the character `w`, so the word might still match.
+                .expect("there is always at least one remaining candidate");
+            remaining = bucket;
+            tracing::debug!(
+                turn = history.len() + 1,
+                guess = %guess,
+                mask = %mask.iter().map(|c| c.to_char()).collect::<String>(),
+                remaining = remaining.len(),
+                "dodged a guess"
+            );
+
+            let solved = mask == [Correctness::Correct; N];
+            history.push(Guess { word: guess, mask });
+
+            if solved {
+                return Ok(Transcript {
+                    answer: remaining[0].to_string(),
+                    score: Some(history.len()),
+                    guesses: history.into_guesses(),
+                });
             }
         }
 
-        true
+        Ok(Transcript {
+            answer: remaining[0].to_string(),
+            guesses: history.into_guesses(),
+            score: None,
+        })
+    }
+
+    /// Checks that the guesser is allowed to play `guess` after the given guesses.
+    fn check(
+        &self,
+        guess: Cow<'static, str>,
+        history: &History<N>,
+    ) -> Result<Cow<'static, str>, PlayError> {
+        self.validate_guess(&guess)?;
+        if self.hard_mode && !history.constraints().hard_mode_allows(&guess) {
+            // It doesn't use every hint revealed so far.
+            return Err(PlayError::HardModeViolation(guess.into_owned()));
+        }
+
+        Ok(guess)
     }
 }
 
-pub trait Guesser {
-    fn guess(&mut self, history: &[Guess]) -> String;
+#[cfg(feature = "solver")]
+impl Default for Wordle {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
-#[cfg(test)]
-macro_rules! guesser {
-    (|$history:ident| $impl:block) => {{
-        struct G;
-        impl $crate::Guesser for G {
-            fn guess(&mut self, $history: &[$crate::Guess]) -> String {
-                $impl
-            }
-        }
-        G
-    }};
+/// Record of a whole game.
+#[cfg(feature = "solver")]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Transcript<const N: usize = 5> {
+    pub answer: String,
+    /// Every guess played, including the last one if the answer was found.
+    pub guesses: Vec<Guess<N>>,
+    /// Number of guesses it took to find the answer, if it was found.
+    pub score: Option<usize>,
 }
 
-#[cfg(test)]
-macro_rules! mask {
-    (C) => { $crate::Correctness::Correct };
-    (M) => { $crate::Correctness::Misplaced };
-    (W) => { $crate::Correctness::Wrong };
-    ($($c:tt)+) => {
-        [$(mask!($c)),+]
+#[cfg(feature = "solver")]
+impl<const N: usize> Transcript<N> {
+    /// Returns whether the answer was found within the [`OFFICIAL_MAX_GUESSES`], which counts as
+    /// a win in the official game even if the game allowed more guesses.
+    pub fn won(&self) -> bool {
+        self.score
+            .is_some_and(|score| score <= OFFICIAL_MAX_GUESSES)
     }
 }
 
-#[cfg(test)]
-mod tests {
-    mod guess_matcher {
-        use crate::Guess;
+/// Record of a whole game along with how long it took, see [`Wordle::play_recorded`].
+///
+/// Serialized as the transcript, with the time in an extra `elapsed_ms` field.
+#[cfg(feature = "solver")]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GameResult<const N: usize = 5> {
+    #[serde(flatten)]
+    pub transcript: Transcript<N>,
+    /// How long the game took, mostly spent by the guesser picking its words.
+    #[serde(rename = "elapsed_ms", with = "millis")]
+    pub elapsed: Duration,
+}
 
-        macro_rules! check {
-            ($prev:literal + [$($mask:tt)+] allows $next:literal) => {
-                let g = Guess::new($prev.to_string(), mask![$($mask)+]);
-                assert!(g.matches($next))
-            };
-            ($prev:literal + [$($mask:tt)+] disallows $next:literal) => {
-                let g = Guess::new($prev.to_string(), mask![$($mask)+]);
-                assert!(!g.matches($next))
-            };
+#[cfg(feature = "solver")]
+mod millis {
+    use std::time::Duration;
+
+    use serde::de::Error;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(elapsed: &Duration, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_f64(elapsed.as_secs_f64() * 1000.0)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Duration, D::Error> {
+        let millis = f64::deserialize(deserializer)?;
+        Duration::try_from_secs_f64(millis / 1000.0).map_err(D::Error::custom)
+    }
+}
+
+/// Reasons why a game could not be played to completion.
+#[cfg(feature = "solver")]
+#[derive(Debug, Clone, Eq, PartialEq, thiserror::Error)]
+pub enum PlayError {
+    /// The answer does not have the number of letters the game is played with.
+    #[error("`{0}` has the wrong number of letters to be an answer")]
+    InvalidAnswer(String),
+    /// The guesser played a word that does not have the number of letters the game is played with.
+    #[error("guessed `{0}`, which has the wrong number of letters")]
+    WrongLength(String),
+    /// The guesser played a word that is not in the dictionary.
+    #[error("guessed `{0}`, which is not in the dictionary")]
+    NotInDictionary(String),
+    /// The guesser played a word that does not use every hint revealed so far in hard mode.
+    #[error("guessed `{0}`, which does not use every hint in hard mode")]
+    HardModeViolation(String),
+    /// A saved game could not be resumed.
+    #[error("cannot resume the game: {0}")]
+    InvalidState(String),
+}
+
+#[cfg(feature = "solver")]
+impl From<GuessError> for PlayError {
+    fn from(e: GuessError) -> Self {
+        match e {
+            GuessError::WrongLength { guess, .. } => PlayError::WrongLength(guess),
+            // Dictionaries only have words of lowercase letters.
+            GuessError::NotLetters(guess) | GuessError::NotInDictionary(guess) => {
+                PlayError::NotInDictionary(guess)
+            }
         }
+    }
+}
 
-        #[test]
-        fn matches() {
-            check!("abcde" + [C C C C C] allows "abcde");
-            check!("abcdf" + [C C C C C] disallows "abcde");
-            check!("abcde" + [W W W W W] allows "mnopq");
-            check!("abcde" + [M M M M M] allows "eabcd");
-            check!("baaaa" + [W C M W W] allows "aaccc");
-            check!("baaaa" + [W C M W W] disallows "caacc");
-            check!("aaabb" + [C M W W W] disallows "accaa");
-            check!("abcde" + [W W W W W] disallows "baedc");
+/// Why a word may not be guessed, see [`Wordle::validate_guess`].
+#[cfg(feature = "solver")]
+#[derive(Debug, Clone, Eq, PartialEq, thiserror::Error)]
+pub enum GuessError {
+    /// The word does not have the number of letters the game is played with.
+    #[error("`{guess}` does not have {expected} letters")]
+    WrongLength { guess: String, expected: usize },
+    /// The word has other characters than lowercase letters, e.g. digits, spaces or capitals.
+    #[error("`{0}` is not made of lowercase letters")]
+    NotLetters(String),
+    /// The word is not in the dictionary.
+    #[error("`{0}` is not in the dictionary")]
+    NotInDictionary(String),
+}
+
+/// Fails with the first of `words` that is not `N` letters long.
+#[cfg(feature = "solver")]
+fn check_lengths<'a, const N: usize>(mut words: impl Iterator<Item = &'a str>) -> Result<()> {
+    match words.find(|word| word.chars().count() != N) {
+        Some(word) => Err(BuildError::WrongLength {
+            word: word.to_string(),
+            expected: N,
         }
+        .into()),
+        None => Ok(()),
+    }
+}
+
+/// Checks that `word` has `N` letters, all of them lowercase, like every word of a dictionary.
+///
+/// Frontends without the dictionary at hand can check the words they are handed with this, and
+/// leave the rest to [`Wordle::validate_guess`].
+#[cfg(feature = "solver")]
+pub fn validate_word<const N: usize>(word: &str) -> Result<(), GuessError> {
+    if word.chars().count() != N {
+        return Err(GuessError::WrongLength {
+            guess: word.to_string(),
+            expected: N,
+        });
+    }
+    if !word.chars().all(char::is_lowercase) {
+        return Err(GuessError::NotLetters(word.to_string()));
+    }
+
+    Ok(())
+}
+
+/// A Wordle solving strategy for words of `N` letters.
+///
+/// Guessers can be used as trait objects, e.g. `Box<dyn Guesser>` to pick one at runtime, which
+/// are guessers themselves. See also [`Wordle::play_dyn`].
+#[cfg(feature = "solver")]
+pub trait Guesser<const N: usize = 5> {
+    /// Returns the next word to play given the previous guesses of this game.
+    ///
+    /// Most guessers pick words straight out of the static dictionary, so they can return a
+    /// [`Cow::Borrowed`] without allocating.
+    fn guess(&mut self, history: &History<N>) -> Cow<'static, str>;
+
+    /// Like [`Guesser::guess`], but also explains why the guess was picked, if the guesser can.
+    ///
+    /// Explaining a guess scores other guesses too, so it takes about as long again as picking it.
+    fn guess_with_explanation(
+        &mut self,
+        history: &History<N>,
+    ) -> (Cow<'static, str>, Option<Explanation<N>>) {
+        (self.guess(history), None)
+    }
+
+    /// Tells the guesser whether the upcoming game is played in hard mode, in which case every
+    /// guess must be consistent with the green and yellow letters revealed so far.
+    ///
+    /// Guessers that only ever guess words matching the whole history can ignore this.
+    fn set_hard_mode(&mut self, _hard_mode: bool) {}
+
+    /// Gives the guesser a token to check while it searches for a guess, so that it settles for
+    /// the best guess found so far once the token is cancelled.
+    ///
+    /// Guessers that answer quickly can ignore this.
+    fn set_cancel_token(&mut self, _token: CancelToken) {}
+
+    /// Returns the words the guesser still considers possible answers given the history of its
+    /// last guess, if it keeps track of them, e.g. to show them to a player using it as an
+    /// assistant.
+    ///
+    /// Guessers that play some guesses without asking the guesser they wrap, like
+    /// [`Opening`](crate::algorithms::Opening), report the candidates as of the last guess the
+    /// wrapped guesser picked.
+    fn remaining(&self) -> Option<Vec<&'static str>> {
+        None
+    }
+
+    /// Returns the `k` best words to play next given the previous guesses of this game, best
+    /// first, each with its score, the higher the better, e.g. to show a player more than the
+    /// single word [`Guesser::guess`] picks.
+    ///
+    /// By default, the candidates reported by [`Guesser::remaining`] that match the history are
+    /// ranked by the information (in bits) they are expected to reveal about each other, which
+    /// takes time quadratic in their number, and guessers that do not keep track of candidates
+    /// rank no words at all. Guessers that score their guesses rank them by their own score.
+    fn rank(&self, history: &History<N>, k: usize) -> Vec<(&'static str, f64)> {
+        let candidates: Vec<&'static str> = self
+            .remaining()
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|word| history.matches(word))
+            .collect();
+        let total = candidates.len() as f64;
+
+        let mut ranked: Vec<(&'static str, f64)> = candidates
+            .iter()
+            .map(|&guess| {
+                let mut buckets: HashMap<[Correctness; N], usize> = HashMap::new();
+                for &answer in &candidates {
+                    *buckets
+                        .entry(Correctness::compute(answer, guess))
+                        .or_default() += 1;
+                }
+                let entropy = -buckets
+                    .values()
+                    .map(|&size| size as f64 / total)
+                    .map(|p| p * p.log2())
+                    .sum::<f64>();
+                (guess, entropy)
+            })
+            .collect();
+        ranked.sort_by(|a, b| b.1.partial_cmp(&a.1).expect("entropy is never NaN"));
+        ranked.truncate(k);
+        ranked
+    }
+
+    /// Returns how the guesser expects the rest of the game to go given the previous guesses of
+    /// this game, if it can tell, e.g. to show a player what to expect before they play the
+    /// guess it suggests.
+    ///
+    /// By default, the game is estimated [at best](Estimate::at_best) from the candidates
+    /// reported by [`Guesser::remaining`] that match the history, and guessers that do not keep
+    /// track of candidates estimate nothing. Guessers that search the rest of the game estimate
+    /// it with their search.
+    fn estimate(&self, history: &History<N>) -> Option<Estimate> {
+        let remaining = self.remaining()?;
+        Estimate::at_best(
+            remaining
+                .into_iter()
+                .filter(|word| history.matches(word))
+                .count(),
+        )
+    }
+}
+
+#[cfg(feature = "solver")]
+impl<G: Guesser<N> + ?Sized, const N: usize> Guesser<N> for &mut G {
+    fn guess(&mut self, history: &History<N>) -> Cow<'static, str> {
+        (**self).guess(history)
+    }
+
+    fn guess_with_explanation(
+        &mut self,
+        history: &History<N>,
+    ) -> (Cow<'static, str>, Option<Explanation<N>>) {
+        (**self).guess_with_explanation(history)
+    }
+
+    fn set_hard_mode(&mut self, hard_mode: bool) {
+        (**self).set_hard_mode(hard_mode)
+    }
+
+    fn set_cancel_token(&mut self, token: CancelToken) {
+        (**self).set_cancel_token(token)
+    }
+
+    fn remaining(&self) -> Option<Vec<&'static str>> {
+        (**self).remaining()
     }
 
+    fn rank(&self, history: &History<N>, k: usize) -> Vec<(&'static str, f64)> {
+        (**self).rank(history, k)
+    }
+
+    fn estimate(&self, history: &History<N>) -> Option<Estimate> {
+        (**self).estimate(history)
+    }
+}
+
+#[cfg(feature = "solver")]
+impl<G: Guesser<N> + ?Sized, const N: usize> Guesser<N> for Box<G> {
+    fn guess(&mut self, history: &History<N>) -> Cow<'static, str> {
+        (**self).guess(history)
+    }
+
+    fn guess_with_explanation(
+        &mut self,
+        history: &History<N>,
+    ) -> (Cow<'static, str>, Option<Explanation<N>>) {
+        (**self).guess_with_explanation(history)
+    }
+
+    fn set_hard_mode(&mut self, hard_mode: bool) {
+        (**self).set_hard_mode(hard_mode)
+    }
+
+    fn set_cancel_token(&mut self, token: CancelToken) {
+        (**self).set_cancel_token(token)
+    }
+
+    fn remaining(&self) -> Option<Vec<&'static str>> {
+        (**self).remaining()
+    }
+
+    fn rank(&self, history: &History<N>, k: usize) -> Vec<(&'static str, f64)> {
+        (**self).rank(history, k)
+    }
+
+    fn estimate(&self, history: &History<N>) -> Option<Estimate> {
+        (**self).estimate(history)
+    }
+}
+
+#[cfg(test)]
+mod tests {
     mod play {
-        use crate::Wordle;
+        use crate::{
+            GameResult, Guess, GuessError, Guesser, PlayError, Transcript, Wordle,
+            OFFICIAL_MAX_GUESSES,
+        };
 
         #[test]
         fn genius() {
             let w = Wordle::new();
-            let guesser = guesser!(|_history| { "right".to_string() });
-            assert_eq!(w.play("right", guesser), Some(1));
+            let guesser = guesser!(|_history| { "right".into() });
+            assert_eq!(w.play("right", guesser), Ok(Some(1)));
         }
 
         #[test]
@@ -253,12 +938,12 @@ mod tests {
             let w = Wordle::new();
             let guesser = guesser!(|history| {
                 if history.len() == 1 {
-                    return "right".to_string();
+                    return "right".into();
                 }
 
-                return "wrong".to_string();
+                return "wrong".into();
             });
-            assert_eq!(w.play("right", guesser), Some(2));
+            assert_eq!(w.play("right", guesser), Ok(Some(2)));
         }
 
         #[test]
@@ -266,12 +951,12 @@ mod tests {
             let w = Wordle::new();
             let guesser = guesser!(|history| {
                 if history.len() == 2 {
-                    return "right".to_string();
+                    return "right".into();
                 }
 
-                return "wrong".to_string();
+                return "wrong".into();
             });
-            assert_eq!(w.play("right", guesser), Some(3));
+            assert_eq!(w.play("right", guesser), Ok(Some(3)));
         }
 
         #[test]
@@ -279,12 +964,12 @@ mod tests {
             let w = Wordle::new();
             let guesser = guesser!(|history| {
                 if history.len() == 3 {
-                    return "right".to_string();
+                    return "right".into();
                 }
 
-                return "wrong".to_string();
+                return "wrong".into();
             });
-            assert_eq!(w.play("right", guesser), Some(4));
+            assert_eq!(w.play("right", guesser), Ok(Some(4)));
         }
 
         #[test]
@@ -292,12 +977,12 @@ mod tests {
             let w = Wordle::new();
             let guesser = guesser!(|history| {
                 if history.len() == 4 {
-                    return "right".to_string();
+                    return "right".into();
                 }
 
-                return "wrong".to_string();
+                return "wrong".into();
             });
-            assert_eq!(w.play("right", guesser), Some(5));
+            assert_eq!(w.play("right", guesser), Ok(Some(5)));
         }
 
         #[test]
@@ -305,53 +990,386 @@ mod tests {
             let w = Wordle::new();
             let guesser = guesser!(|history| {
                 if history.len() == 5 {
-                    return "right".to_string();
+                    return "right".into();
                 }
 
-                return "wrong".to_string();
+                return "wrong".into();
             });
-            assert_eq!(w.play("right", guesser), Some(6));
+            assert_eq!(w.play("right", guesser), Ok(Some(6)));
         }
 
         #[test]
         fn oops() {
             let w = Wordle::new();
-            let guesser = guesser!(|_history| { "wrong".to_string() });
-            assert_eq!(w.play("right", guesser), None);
+            let guesser = guesser!(|_history| { "wrong".into() });
+            assert_eq!(w.play("right", guesser), Ok(None));
         }
-    }
 
-    mod compute {
-        use crate::Correctness;
+        #[test]
+        fn dynamic_dispatch() {
+            let w = Wordle::new();
+            let mut guessers: Vec<Box<dyn Guesser>> = vec![
+                Box::new(guesser!(|_history| { "right".into() })),
+                Box::new(guesser!(|history| {
+                    if history.is_empty() {
+                        return "wrong".into();
+                    }
+
+                    "right".into()
+                })),
+            ];
+
+            let scores: Vec<_> = guessers
+                .iter_mut()
+                .map(|guesser| w.play_dyn("right", guesser.as_mut()))
+                .collect();
+            assert_eq!(scores, vec![Ok(Some(1)), Ok(Some(2))]);
+            // Boxed guessers are guessers too.
+            assert_eq!(w.play("right", guessers.remove(0)), Ok(Some(1)));
+        }
 
         #[test]
-        fn all_green() {
-            assert_eq!(Correctness::compute("abcde", "abcde"), mask!(C C C C C));
+        fn unicode() {
+            let w = Wordle::with_dictionary(vec![("niños", 1), ("señor", 1)]);
+            let guesser = guesser!(|history| {
+                if history.is_empty() {
+                    return "señor".into();
+                }
+
+                "niños".into()
+            });
+            assert_eq!(w.play("niños", guesser), Ok(Some(2)));
+
+            // Five bytes, but only four letters.
+            assert_eq!(
+                w.play("años", guesser!(|_history| { "señor".into() })),
+                Err(PlayError::InvalidAnswer("años".to_string()))
+            );
         }
 
         #[test]
-        fn all_gray() {
-            assert_eq!(Correctness::compute("abcde", "fghij"), mask!(W W W W W));
+        fn max_guesses() {
+            let late = || {
+                guesser!(|history| {
+                    if history.len() == 9 {
+                        return "right".into();
+                    }
+
+                    return "wrong".into();
+                })
+            };
+
+            let w = Wordle::new().with_max_guesses(Some(OFFICIAL_MAX_GUESSES));
+            let transcript = w.play_transcript("right", late()).unwrap();
+            assert_eq!(transcript.score, None);
+            assert_eq!(transcript.guesses.len(), 6);
+
+            let w = Wordle::new().with_max_guesses(None);
+            let transcript = w.play_transcript("right", late()).unwrap();
+            assert_eq!(transcript.score, Some(10));
+            // Found, but not within the official limit.
+            assert!(!transcript.won());
+
+            let transcript = w.play_transcript("right", guesser!(|_history| { "right".into() }));
+            assert!(transcript.unwrap().won());
         }
 
         #[test]
-        fn all_yellow() {
-            assert_eq!(Correctness::compute("abcde", "baecd"), mask!(M M M M M));
+        fn other_lengths() {
+            struct G;
+            impl crate::Guesser<6> for G {
+                fn guess(&mut self, history: &crate::History<6>) -> std::borrow::Cow<'static, str> {
+                    if history.len() == 1 {
+                        return "bright".into();
+                    }
+
+                    "fright".into()
+                }
+            }
+
+            let w = Wordle::<6>::with_dictionary(vec![("bright", 1), ("fright", 1)]);
+            assert_eq!(w.play("bright", G), Ok(Some(2)));
         }
 
         #[test]
-        fn repeat_green() {
-            assert_eq!(Correctness::compute("aabbb", "aaccc"), mask!(C C W W W));
+        #[should_panic]
+        fn wrong_length_dictionary() {
+            Wordle::<4>::with_dictionary(vec![("four", 1), ("seven", 1)]);
         }
 
         #[test]
-        fn repeat_yellow() {
-            assert_eq!(Correctness::compute("aabbb", "ccaac"), mask!(W W M M W));
+        fn try_wrong_lengths() {
+            let wrong_length = |result: crate::Result<Wordle<4>>| match result {
+                Err(crate::Error::Build(crate::BuildError::WrongLength { word, .. })) => word,
+                _ => panic!("expected a word of the wrong length"),
+            };
+            assert_eq!(
+                wrong_length(Wordle::try_with_dictionary(vec![("four", 1), ("seven", 1)])),
+                "seven"
+            );
+            let wordle = Wordle::<4>::try_with_dictionary(vec![("four", 1), ("five", 1)]).unwrap();
+            assert_eq!(
+                wrong_length(wordle.try_with_answers(vec!["five", "six"])),
+                "six"
+            );
         }
 
         #[test]
-        fn some_green_and_yellow() {
-            assert_eq!(Correctness::compute("azzaz", "aaabb"), mask!(C M W W W));
+        fn transcript() {
+            let w = Wordle::new();
+            let guesser = guesser!(|history| {
+                if history.len() == 1 {
+                    return "right".into();
+                }
+
+                return "rebut".into();
+            });
+            let transcript = w.play_transcript("right", guesser).unwrap();
+            assert_eq!(transcript.score, Some(2));
+            assert_eq!(
+                transcript.guesses,
+                vec![
+                    Guess::new("rebut", mask!(C W W W C)),
+                    Guess::new("right", mask!(C C C C C)),
+                ]
+            );
+        }
+
+        #[test]
+        fn recorded() {
+            let w = Wordle::new();
+            let guesser = guesser!(|history| {
+                if history.is_empty() {
+                    return "rebut".into();
+                }
+
+                return "right".into();
+            });
+            let result = w.play_recorded("right", guesser).unwrap();
+            assert_eq!(result.transcript.score, Some(2));
+            assert_eq!(result.transcript.guesses[0].word, "rebut");
+
+            let json = serde_json::to_value(&result).unwrap();
+            assert_eq!(json["answer"], "right");
+            assert!(json["elapsed_ms"].as_f64().unwrap() >= 0.0);
+            let back: GameResult = serde_json::from_value(json).unwrap();
+            assert_eq!(back.transcript, result.transcript);
+        }
+
+        #[test]
+        fn streaming() {
+            let w = Wordle::new();
+            let guesser = guesser!(|history| {
+                if history.len() == 1 {
+                    return "right".into();
+                }
+
+                return "rebut".into();
+            });
+            let (tx, rx) = std::sync::mpsc::channel();
+            let transcript = w
+                .play_streaming("right", guesser, |event| tx.send(event).unwrap())
+                .unwrap();
+
+            let events: Vec<_> = rx.try_iter().collect();
+            assert_eq!(events.len(), 2);
+            assert_eq!(events[0].turn, 1);
+            assert_eq!(events[0].guess, transcript.guesses[0]);
+            assert!(events[0].remaining > 1);
+            assert_eq!(events[1].guess, transcript.guesses[1]);
+            assert_eq!(events[1].remaining, 1);
+            // Every answer but the last one was ruled out along the way.
+            let eliminated = events
+                .iter()
+                .map(|event| event.eliminated.len())
+                .sum::<usize>();
+            assert_eq!(eliminated, w.answers().len() - 1);
+            assert!(!events[0].eliminated.iter().any(|e| e.word == "right"));
+        }
+
+        #[test]
+        fn transcript_json() {
+            let w = Wordle::new();
+            let guesser = guesser!(|_history| { "right".into() });
+            let transcript = w.play_transcript("right", guesser).unwrap();
+            let json = serde_json::to_string(&transcript).unwrap();
+            assert_eq!(
+                json,
+                r#"{"answer":"right","guesses":[{"word":"right","mask":"GGGGG"}],"score":1}"#
+            );
+            assert_eq!(
+                serde_json::from_str::<Transcript>(&json).unwrap(),
+                transcript
+            );
+        }
+
+        #[test]
+        fn hard_mode() {
+            let w = Wordle::new().with_hard_mode(true);
+            let guesser = guesser!(|history| {
+                if history.len() == 1 {
+                    // Reuses the green "r" and "t" revealed by "rebut".
+                    return "right".into();
+                }
+
+                return "rebut".into();
+            });
+            assert_eq!(w.play("right", guesser), Ok(Some(2)));
+        }
+
+        #[test]
+        fn hard_mode_violation() {
+            let w = Wordle::new().with_hard_mode(true);
+            let guesser = guesser!(|history| {
+                if history.len() == 1 {
+                    // Drops the green "r" and "t" revealed by "rebut".
+                    return "wrong".into();
+                }
+
+                return "rebut".into();
+            });
+            assert_eq!(
+                w.play("right", guesser),
+                Err(PlayError::HardModeViolation("wrong".to_string()))
+            );
+        }
+
+        #[test]
+        fn not_in_dictionary() {
+            let w = Wordle::new();
+            let guesser = guesser!(|_history| { "zzzzz".into() });
+            assert_eq!(
+                w.play("right", guesser),
+                Err(PlayError::NotInDictionary("zzzzz".to_string()))
+            );
+        }
+
+        #[test]
+        fn wrong_length() {
+            let w = Wordle::new();
+            let guesser = guesser!(|_history| { "rights".into() });
+            assert_eq!(
+                w.play("right", guesser),
+                Err(PlayError::WrongLength("rights".to_string()))
+            );
+        }
+
+        #[test]
+        fn validate_guess() {
+            let w = Wordle::new();
+            assert_eq!(w.validate_guess("right"), Ok(()));
+            assert_eq!(
+                w.validate_guess("rights"),
+                Err(GuessError::WrongLength {
+                    guess: "rights".to_string(),
+                    expected: 5
+                })
+            );
+            assert_eq!(
+                w.validate_guess("r1ght"),
+                Err(GuessError::NotLetters("r1ght".to_string()))
+            );
+            assert_eq!(
+                w.validate_guess("RIGHT"),
+                Err(GuessError::NotLetters("RIGHT".to_string()))
+            );
+            assert_eq!(
+                w.validate_guess("zzzzz"),
+                Err(GuessError::NotInDictionary("zzzzz".to_string()))
+            );
+        }
+
+        #[test]
+        fn invalid_answer() {
+            let w = Wordle::new();
+            let guesser = guesser!(|_history| { "right".into() });
+            assert_eq!(
+                w.play("rights", guesser),
+                Err(PlayError::InvalidAnswer("rights".to_string()))
+            );
+        }
+
+        #[test]
+        fn adversarial() {
+            let w = Wordle::new();
+            let guesser = guesser!(|history| {
+                match history.len() {
+                    0 => "right",
+                    1 => "fight",
+                    2 => "light",
+                    _ => "might",
+                }
+                .into()
+            });
+            let transcript = w
+                .play_adversarial(vec!["right", "fight", "light", "might"], guesser)
+                .unwrap();
+            assert_eq!(transcript.answer, "might");
+            assert_eq!(transcript.score, Some(4));
+            assert_eq!(
+                transcript.guesses,
+                vec![
+                    // Keeps "fight", "light" and "might" alive.
+                    Guess::new("right", mask!(W C C C C)),
+                    Guess::new("fight", mask!(W C C C C)),
+                    // Both patterns keep a single candidate, so the one without a new green wins.
+                    Guess::new("light", mask!(W C C C C)),
+                    Guess::new("might", mask!(C C C C C)),
+                ]
+            );
+        }
+
+        #[test]
+        fn adversarial_invalid_answer() {
+            let w = Wordle::new();
+            let guesser = guesser!(|_history| { "right".into() });
+            assert_eq!(
+                w.play_adversarial(vec!["right", "rights"], guesser),
+                Err(PlayError::InvalidAnswer("rights".to_string()))
+            );
+        }
+    }
+
+    mod dictionary {
+        use std::io::Write;
+
+        #[test]
+        fn builtin() {
+            assert!(crate::builtin_dictionary().any(|(word, _)| word == "crane"));
+            assert!(crate::builtin_dictionary().all(|(word, _)| word.len() == 5));
+        }
+
+        #[test]
+        fn answers_and_allowed_guesses() {
+            let w = crate::Wordle::new();
+            assert!(w.answers().len() < crate::builtin_dictionary().count());
+            assert!(w.answers().iter().all(|answer| w.is_allowed(answer)));
+            assert!(w.answers().binary_search(&"cigar").is_ok());
+            // Allowed, but never the answer.
+            assert!(w.is_allowed("aahed"));
+            assert!(w.answers().binary_search(&"aahed").is_err());
+        }
+
+        #[test]
+        fn load() {
+            let path = std::env::temp_dir().join("crane-load-dictionary-test.txt");
+            let mut f = std::fs::File::create(&path).unwrap();
+            writeln!(f, "right 10\nwrong 20\n").unwrap();
+            drop(f);
+
+            let words = crate::load_dictionary(&path).unwrap();
+            assert_eq!(words, vec![("right", 10), ("wrong", 20)]);
+        }
+
+        #[test]
+        fn load_malformed() {
+            let path = std::env::temp_dir().join("crane-load-malformed-dictionary-test.txt");
+            std::fs::write(&path, "right 10\nwrong\n").unwrap();
+
+            match crate::load_dictionary(&path).unwrap_err() {
+                crate::Error::Dictionary(invalid) => assert_eq!(invalid.diagnostics[0].line, 2),
+                e => panic!("unexpected error: {}", e),
+            }
         }
     }
 }
@@ -1,11 +1,2046 @@
-use crane::Wordle;
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{self, BufRead, BufWriter, Write};
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use std::{fs, process};
 
-const GAMES: &'static str = include_str!("../answers.txt");
+use chrono::NaiveDate;
+use clap::builder::{PossibleValue, PossibleValuesParser};
+use clap::parser::ValueSource;
+use clap::{ArgMatches, Args, CommandFactory, FromArgMatches, Parser, Subcommand, ValueEnum};
+use crane::algorithms::opening::fingerprint;
+use crane::algorithms::{
+    lookup, registry, Anytime, Booked, Candidates, Crossover, DecisionTree, Endgame, Factory,
+    FixedOpening, GuessPool, OpenerRule, OpenerRules, Opening, OpeningBook, OpeningCache,
+    PatternCache, Prior, ReducedPool, Settings, TieBreak, TreeGuesser, Weighting,
+};
+use crane::assistant::Assistant;
+use crane::batch::{Batch, CsvWriter, TurnsCsvWriter};
+use crane::config::Config;
+use crane::keyboard::Keyboard;
+use crane::simulate::{sample_answers, Sampling, Streaks};
+use crane::stats::{Score, Stats, Worst};
+use crane::tournament::Tournament;
+use crane::{analysis, daily, render};
+use crane::{validate_word, CancelToken, Correctness, GameResult, Guess, Guesser, History, Wordle};
+use tracing_subscriber::EnvFilter;
+
+/// Play Wordle games using one of the available solvers.
+#[derive(Parser, Debug)]
+#[command(version, about, args_conflicts_with_subcommands = true)]
+struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    #[command(flatten)]
+    play: PlayArgs,
+
+    /// Read the defaults of the options from this file rather than from `crane.toml` in the
+    /// current directory, if there is one.
+    #[arg(long, global = true)]
+    config: Option<PathBuf>,
+
+    /// Score guesses on this many threads rather than on one per core, with the `parallel`
+    /// feature.
+    #[arg(long, global = true, value_parser = clap::value_parser!(u32).range(1..))]
+    threads: Option<u32>,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Play games against the built-in answer list (the default).
+    Play(PlayArgs),
+    /// Suggest guesses for a puzzle being played elsewhere, e.g. on the official website.
+    Solve(SolveArgs),
+    /// Report statistics about the words of a dictionary, e.g. one being curated.
+    DictStats(DictStatsArgs),
+    /// Compute the best second guess after every mask of a first guess, to be played with `--book`.
+    BuildBook(BuildBookArgs),
+    /// Play several algorithms over the same answers and compare how they did.
+    Compare(CompareArgs),
+    /// Solve the official puzzle of the day, or of another day.
+    Today(TodayArgs),
+    /// Estimate how hard an answer is to find, out of a game against it and of the answers that
+    /// look like it.
+    Difficulty(DifficultyArgs),
+    /// Compute what to guess after every mask for every answer, to be played with `--tree`.
+    BuildTree(BuildTreeArgs),
+    /// Pick the few words whose best guess reveals nearly as much as the best guess out of the
+    /// whole dictionary, to be played with `--reduced-pool`.
+    BuildPool(BuildPoolArgs),
+    /// Compute the mask of every guess against every answer once, to be looked up with `--matrix`.
+    PrecomputeMatrix(PrecomputeMatrixArgs),
+    /// Grade the guesses of a game played elsewhere against the best guess at every turn.
+    Grade(GradeArgs),
+    /// Suggest and grade guesses over HTTP, with the `serve` feature.
+    Serve(ServeArgs),
+    /// Play the daily puzzles of random days in a row, and report the streaks like the official
+    /// game does.
+    Simulate(SimulateArgs),
+    /// Get suggestions for a game played elsewhere in a terminal UI, with the `tui` feature.
+    Tui(TuiArgs),
+    /// Compare the runs recorded with `play --record`, and list their best and worst games, with
+    /// the `db` feature.
+    History(HistoryArgs),
+}
+
+#[derive(Args, Debug)]
+struct PlayArgs {
+    #[command(flatten)]
+    guesser: GuesserArgs,
+
+    /// Play at most this many games.
+    #[arg(short, long)]
+    max_games: Option<usize>,
+
+    /// Only play the game with the given answer.
+    #[arg(short, long)]
+    answer: Option<String>,
+
+    /// Play the answers listed in this file, separated by whitespace like the built-in list, or
+    /// read them from the standard input with `-`, instead of the official answers. Every answer
+    /// must be in the dictionary.
+    #[arg(long, value_name = "PATH", conflicts_with = "answer")]
+    answers: Option<PathBuf>,
+
+    /// Play in hard mode, where every guess must use all the hints revealed so far.
+    #[arg(long, overrides_with = "no_hard")]
+    hard: bool,
+
+    /// Play in normal mode, even if the configuration file sets `hard`.
+    #[arg(long, overrides_with = "hard")]
+    no_hard: bool,
+
+    /// Give up on a game after this many guesses, e.g. 6 like the official game, or 0 to keep
+    /// guessing until the answer is found.
+    #[arg(long, default_value_t = crane::DEFAULT_MAX_GUESSES)]
+    max_guesses: usize,
+
+    /// Play a single game of Absurdle, where the host keeps dodging the guesses among the answers
+    /// for as long as possible instead of picking one up front.
+    #[arg(long)]
+    adversarial: bool,
+
+    /// Print the outcome of every game.
+    #[arg(short, long)]
+    verbose: bool,
+
+    /// Print the board of every game once it is over, with text output.
+    #[arg(long)]
+    show: bool,
+
+    /// List the answers that took the most guesses, failures first, with their guesses, with text
+    /// output.
+    #[arg(long, value_name = "N", num_args = 0..=1, default_missing_value = "20")]
+    worst: Option<usize>,
+
+    /// Show a progress bar with the average score so far and the time left.
+    #[arg(long)]
+    progress: bool,
+
+    /// Play this many games at once, each on its own thread.
+    #[arg(short, long, default_value_t = 1, value_parser = clap::value_parser!(u32).range(1..))]
+    jobs: u32,
+
+    /// How to report the games played.
+    #[arg(short, long, value_enum, default_value_t = Output::Text)]
+    output: Output,
+
+    /// Write the report to this file instead of the standard output.
+    #[arg(long)]
+    out_file: Option<PathBuf>,
+
+    /// Also record every game in this SQLite database, to be queried with `crane history`, with
+    /// the `db` feature.
+    #[arg(long, value_name = "PATH")]
+    record: Option<PathBuf>,
+
+    /// Record the games under this label, e.g. a commit hash, instead of the version of crane.
+    #[arg(long, requires = "record")]
+    label: Option<String>,
+}
+
+#[derive(Args, Debug)]
+struct SolveArgs {
+    #[command(flatten)]
+    guesser: GuesserArgs,
+
+    /// Explain every suggestion: the information it is expected to reveal, the best alternatives
+    /// and the masks it may reveal.
+    #[arg(long)]
+    explain: bool,
+
+    /// Show the keyboard colored by the hints so far after every guess, like the official game.
+    #[arg(long)]
+    keyboard: bool,
+}
+
+/// Options selecting and configuring the guesser, shared by the commands that guess.
+#[derive(Args, Debug, Clone)]
+struct GuesserArgs {
+    /// The guessing algorithm to use.
+    #[arg(short, long, default_value = "naive", value_parser = implementations())]
+    implementation: String,
+
+    /// Read the dictionary from this file instead of using the built-in one.
+    #[arg(short, long)]
+    dictionary: Option<PathBuf>,
+
+    /// Only consider the words of the built-in answer list as possible answers, like the official
+    /// game, rather than every word of the dictionary.
+    #[arg(long)]
+    known_answers: bool,
+
+    /// How likely each word is considered to be the answer, for the naive implementation.
+    #[arg(long, value_enum, default_value_t = PriorArg::Counts)]
+    prior: PriorArg,
+
+    /// Multiply how likely each word is considered to be the answer by its value in this CSV
+    /// file, e.g. of historical solve data, for the naive implementation. See `--weight-column`.
+    #[arg(long, value_name = "PATH")]
+    weights: Option<PathBuf>,
+
+    /// The column of the `--weights` file holding the factor of every word, next to its `word`
+    /// column.
+    #[arg(long, default_value = "weight", requires = "weights")]
+    weight_column: String,
+
+    /// Which words the naive and positional implementations consider guessing.
+    #[arg(long, value_enum, default_value_t = PoolArg::Remaining)]
+    pool: PoolArg,
+
+    /// Only consider the words of this reduced pool among those that were ruled out, with the
+    /// naive implementation and `--pool dictionary` or `hybrid`, see the `build-pool` command.
+    #[arg(long, value_name = "PATH")]
+    reduced_pool: Option<PathBuf>,
+
+    /// Rules the first guess of the naive and positional implementations must follow, e.g.
+    /// `+q,-crane,s@1` for a word with a `q`, none of the letters of `crane`, and an `s` first.
+    #[arg(long, value_delimiter = ',', value_name = "RULES", conflicts_with_all = ["book", "tree", "openers"])]
+    opener_rules: Vec<OpenerRule>,
+
+    /// How to pick among guesses that score the same.
+    #[arg(long, value_enum, default_value_t = TieBreakArg::PossibleAnswer)]
+    tie_break: TieBreakArg,
+
+    /// Seed of the random number generator, for the random implementation, the shuffled tie break
+    /// and the answers drawn by `simulate`.
+    #[arg(long, default_value_t = 0)]
+    seed: u64,
+
+    /// How many guesses the ensemble implementation always picks like the naive one.
+    #[arg(long, value_name = "TURNS", default_value_t = Crossover::default().turns)]
+    crossover_turns: usize,
+
+    /// How few candidates must remain for the ensemble implementation to pick like the positional
+    /// one, once past `--crossover-turns`.
+    #[arg(long, value_name = "CANDIDATES", default_value_t = Crossover::default().candidates)]
+    crossover_candidates: usize,
+
+    /// Remember the first guess of every algorithm and dictionary in this file, so that it is only
+    /// ever computed once.
+    #[arg(long)]
+    openings: Option<PathBuf>,
+
+    /// Play the first two guesses out of this opening book, see the `build-book` command.
+    #[arg(long, conflicts_with = "openings")]
+    book: Option<PathBuf>,
+
+    /// Play every guess out of this decision tree, see the `build-tree` command.
+    #[arg(long, conflicts_with_all = ["openings", "book"])]
+    tree: Option<PathBuf>,
+
+    /// Always open with these guesses, in order, e.g. `crane,moist`, before the algorithm takes
+    /// over.
+    #[arg(long, value_delimiter = ',', value_name = "WORDS", conflicts_with_all = ["openings", "book", "tree"])]
+    openers: Vec<String>,
+
+    /// Look the masks of every guess up in this pattern matrix rather than computing them, see the
+    /// `precompute-matrix` command.
+    #[arg(long)]
+    matrix: Option<PathBuf>,
+
+    /// Settle for the best guess found after this many seconds of thinking at every turn, e.g.
+    /// 0.5. The first guess is computed in full once up front.
+    #[arg(long, value_name = "SECONDS", value_parser = seconds)]
+    time_limit: Option<Duration>,
+
+    /// Once at most this many candidates remain, e.g. 20, play the guess that finds them in the
+    /// fewest guesses on average, out of an exhaustive search.
+    #[arg(long, value_name = "CANDIDATES")]
+    endgame: Option<usize>,
+}
+
+#[derive(Args, Debug)]
+struct DictStatsArgs {
+    /// Read the dictionary from this file instead of using the built-in one.
+    #[arg(short, long)]
+    dictionary: Option<PathBuf>,
+
+    /// How many of the most common bigrams and best openers to report.
+    #[arg(short = 'n', long, default_value_t = 10)]
+    top: usize,
+}
+
+#[derive(Args, Debug)]
+struct CompareArgs {
+    #[command(flatten)]
+    guesser: GuesserArgs,
+
+    /// The algorithms to compare, which are otherwise configured the same way.
+    #[arg(
+        long,
+        value_delimiter = ',',
+        required = true,
+        value_parser = implementations()
+    )]
+    impls: Vec<String>,
+
+    /// Play at most this many games with every algorithm.
+    #[arg(short, long)]
+    max_games: Option<usize>,
+
+    /// Play the answers listed in this file, separated by whitespace like the built-in list, or
+    /// read them from the standard input with `-`, instead of the official answers. Every answer
+    /// must be in the dictionary.
+    #[arg(long, value_name = "PATH")]
+    answers: Option<PathBuf>,
+
+    /// Play in hard mode.
+    #[arg(long, overrides_with = "no_hard")]
+    hard: bool,
+
+    /// Play in normal mode, even if the configuration file sets `hard`.
+    #[arg(long, overrides_with = "hard")]
+    no_hard: bool,
+
+    /// Show a progress bar for every algorithm.
+    #[arg(long)]
+    progress: bool,
+
+    /// Play this many games of every algorithm at once, each on its own thread.
+    #[arg(short, long, default_value_t = 1, value_parser = clap::value_parser!(u32).range(1..))]
+    jobs: u32,
+
+    /// Also list the answers every algorithm took the most guesses for, failures first, with
+    /// their guesses.
+    #[arg(long, value_name = "N", num_args = 0..=1, default_missing_value = "20")]
+    worst: Option<usize>,
+
+    /// What to compare the algorithms by: `guesses` for the mean score, `win-loss` for the
+    /// fraction of games lost, or `penalty:COST` for the mean score where a lost game costs
+    /// COST guesses.
+    #[arg(long, default_value = "guesses", value_parser = score)]
+    score: Score,
+
+    /// Also write the answers left and their entropy at every turn of every game of every
+    /// algorithm to this file, as CSV with one row per turn, to plot the information gained per
+    /// turn.
+    #[arg(long, value_name = "PATH")]
+    turns: Option<PathBuf>,
+}
+
+#[derive(Args, Debug)]
+struct TodayArgs {
+    #[command(flatten)]
+    guesser: GuesserArgs,
+
+    /// Solve the puzzle of this day, e.g. 2022-01-31, instead of today's.
+    #[arg(long)]
+    date: Option<NaiveDate>,
+
+    /// Play in hard mode.
+    #[arg(long, overrides_with = "no_hard")]
+    hard: bool,
+
+    /// Play in normal mode, even if the configuration file sets `hard`.
+    #[arg(long, overrides_with = "hard")]
+    no_hard: bool,
+
+    /// Also print the guesses on their colors, which gives the answer away.
+    #[arg(long)]
+    show: bool,
+
+    /// Fetch the answer from the official game rather than reading it from the built-in answer
+    /// list, which requires the `fetch` feature. The official game only followed the list until
+    /// February 14, 2022.
+    #[arg(long)]
+    fetch: bool,
+}
+
+#[derive(Args, Debug)]
+struct DifficultyArgs {
+    #[command(flatten)]
+    guesser: GuesserArgs,
+
+    /// Play in hard mode.
+    #[arg(long, overrides_with = "no_hard")]
+    hard: bool,
+
+    /// Play in normal mode, even if the configuration file sets `hard`.
+    #[arg(long, overrides_with = "hard")]
+    no_hard: bool,
+
+    /// The answer to estimate.
+    answer: String,
+}
+
+#[derive(Args, Debug)]
+struct BuildBookArgs {
+    #[command(flatten)]
+    guesser: GuesserArgs,
+
+    /// The first guess to compute the responses to, instead of the algorithm's own first guess.
+    #[arg(long)]
+    opener: Option<String>,
+
+    /// Compute the responses for hard mode.
+    #[arg(long, overrides_with = "no_hard")]
+    hard: bool,
+
+    /// Play in normal mode, even if the configuration file sets `hard`.
+    #[arg(long, overrides_with = "hard")]
+    no_hard: bool,
+
+    /// Where to write the book.
+    output: PathBuf,
+}
+
+#[derive(Args, Debug)]
+struct BuildTreeArgs {
+    /// Read the dictionary from this file instead of using the built-in one.
+    #[arg(short, long)]
+    dictionary: Option<PathBuf>,
+
+    /// Only build the tree for the words of the built-in answer list rather than every word of
+    /// the dictionary.
+    #[arg(long)]
+    known_answers: bool,
+
+    /// Only guess words allowed in hard mode.
+    #[arg(long, overrides_with = "no_hard")]
+    hard: bool,
+
+    /// Play in normal mode, even if the configuration file sets `hard`.
+    #[arg(long, overrides_with = "hard")]
+    no_hard: bool,
+
+    /// How many of the most promising guesses to try at every node, or 0 to try them all, which
+    /// is only feasible for small dictionaries.
+    #[arg(long, default_value_t = 2)]
+    beam: usize,
+
+    /// Only try a single guess at every node left after this many seconds, to bound how long
+    /// building takes.
+    #[arg(long, value_name = "SECONDS", value_parser = seconds)]
+    time_limit: Option<Duration>,
+
+    /// Where to write the tree.
+    output: PathBuf,
+}
+
+#[derive(Args, Debug)]
+struct BuildPoolArgs {
+    /// Read the dictionary from this file instead of using the built-in one.
+    #[arg(short, long)]
+    dictionary: Option<PathBuf>,
+
+    /// Only profile the words of the built-in answer list as possible answers rather than every
+    /// word of the dictionary.
+    #[arg(long)]
+    known_answers: bool,
+
+    /// How likely each word is considered to be the answer while profiling.
+    #[arg(long, value_enum, default_value_t = PriorArg::Counts)]
+    prior: PriorArg,
+
+    /// Pick at most this many words.
+    #[arg(long, default_value_t = 1000)]
+    size: usize,
+
+    /// How many bits of information the words may miss out on compared to the best guess.
+    #[arg(long, default_value_t = 0.01)]
+    epsilon: f64,
+
+    /// Where to write the pool.
+    output: PathBuf,
+}
+
+#[derive(Args, Debug)]
+struct PrecomputeMatrixArgs {
+    /// Read the dictionary from this file instead of using the built-in one.
+    #[arg(short, long)]
+    dictionary: Option<PathBuf>,
+
+    /// Compute the matrix on the GPU, which requires the `gpu` feature, or on the CPU if the
+    /// machine has no GPU.
+    #[arg(long)]
+    gpu: bool,
+
+    /// Where to write the matrix.
+    output: PathBuf,
+}
+
+#[derive(Args, Debug)]
+struct GradeArgs {
+    /// Read the dictionary from this file instead of using the built-in one.
+    #[arg(short, long)]
+    dictionary: Option<PathBuf>,
+
+    /// Only consider the words of the built-in answer list as possible answers rather than every
+    /// word of the dictionary.
+    #[arg(long)]
+    known_answers: bool,
+
+    /// Also replay the game with another word at one of its turns, counted from 1, e.g.
+    /// `2=slate`, and let the naive implementation play the rest of the game.
+    #[arg(long, value_name = "TURN=WORD", value_parser = what_if)]
+    what_if: Option<(usize, String)>,
+
+    /// The guesses of the game, one per line followed by the colors the game showed, e.g. `crane
+    /// GYXXX`, read from the standard input if left out.
+    transcript: Option<PathBuf>,
+}
+
+#[derive(Args, Debug)]
+struct ServeArgs {
+    #[command(flatten)]
+    guesser: GuesserArgs,
+
+    /// The address to listen on.
+    #[arg(long, default_value = "127.0.0.1:8080")]
+    addr: String,
+}
+
+#[derive(Args, Debug)]
+struct TuiArgs {
+    #[command(flatten)]
+    guesser: GuesserArgs,
+}
+
+#[derive(Args, Debug)]
+struct HistoryArgs {
+    /// The database the games were recorded in.
+    database: PathBuf,
+
+    /// Only consider the games played by this algorithm.
+    #[arg(short, long)]
+    algorithm: Option<String>,
+
+    /// Also list the N games found in the fewest guesses.
+    #[arg(long, value_name = "N")]
+    best: Option<usize>,
+
+    /// Also list the N games that took the most guesses, failures first.
+    #[arg(long, value_name = "N")]
+    worst: Option<usize>,
+}
+
+#[derive(Args, Debug)]
+struct SimulateArgs {
+    #[command(flatten)]
+    guesser: GuesserArgs,
+
+    /// How many days to play.
+    #[arg(long, default_value_t = 365)]
+    days: usize,
+
+    /// How the answer of every day is drawn from the built-in answer list.
+    #[arg(long, value_enum, default_value_t = SamplingArg::Uniform)]
+    sampling: SamplingArg,
+
+    /// Play in hard mode.
+    #[arg(long, overrides_with = "no_hard")]
+    hard: bool,
+
+    /// Play in normal mode, even if the configuration file sets `hard`.
+    #[arg(long, overrides_with = "hard")]
+    no_hard: bool,
+
+    /// Play this many games at once, each on its own thread.
+    #[arg(short, long, default_value_t = 1, value_parser = clap::value_parser!(u32).range(1..))]
+    jobs: u32,
+
+    /// Show a progress bar.
+    #[arg(long)]
+    progress: bool,
+}
+
+#[derive(ValueEnum, Debug, Copy, Clone, PartialEq, Eq)]
+enum Output {
+    /// Statistics over all games, for humans.
+    Text,
+    /// The transcript of every game as a JSON object, one per line.
+    Json,
+    /// One row per game with the answer, score, guesses and time taken, for spreadsheets.
+    Csv,
+    /// One row per turn of every game with the answers left and their entropy, to plot the
+    /// information gained per turn.
+    Turns,
+}
+
+#[derive(ValueEnum, Debug, Copy, Clone)]
+enum PriorArg {
+    /// Every word is equally likely.
+    Uniform,
+    /// Words are as likely as their frequency count.
+    Counts,
+    /// Common words are all likely, rare words are unlikely.
+    Sigmoid,
+}
+
+impl From<PriorArg> for Prior {
+    fn from(prior: PriorArg) -> Self {
+        match prior {
+            PriorArg::Uniform => Prior::Uniform,
+            PriorArg::Counts => Prior::Counts,
+            PriorArg::Sigmoid => Prior::sigmoid(),
+        }
+    }
+}
+
+#[derive(ValueEnum, Debug, Copy, Clone)]
+enum SamplingArg {
+    /// Every answer is as likely as any other.
+    Uniform,
+    /// Answers are as likely as their frequency count in the dictionary.
+    Frequency,
+}
+
+impl From<SamplingArg> for Sampling {
+    fn from(sampling: SamplingArg) -> Self {
+        match sampling {
+            SamplingArg::Uniform => Sampling::Uniform,
+            SamplingArg::Frequency => Sampling::Frequency,
+        }
+    }
+}
+
+#[derive(ValueEnum, Debug, Copy, Clone)]
+enum PoolArg {
+    /// Only words that could still be the answer.
+    Remaining,
+    /// Every word of the dictionary, even those already ruled out.
+    Dictionary,
+    /// Every word of the dictionary until only a few candidates remain.
+    Hybrid,
+}
+
+impl From<PoolArg> for GuessPool {
+    fn from(pool: PoolArg) -> Self {
+        match pool {
+            PoolArg::Remaining => GuessPool::Remaining,
+            PoolArg::Dictionary => GuessPool::Dictionary,
+            PoolArg::Hybrid => GuessPool::hybrid(),
+        }
+    }
+}
+
+#[derive(ValueEnum, Debug, Copy, Clone)]
+enum TieBreakArg {
+    /// The word that could still be the answer.
+    PossibleAnswer,
+    /// The most frequent word.
+    Frequency,
+    /// The word that comes first alphabetically.
+    Alphabetical,
+    /// The word that comes first in an order shuffled by `--seed`.
+    Shuffled,
+}
+
+impl TieBreakArg {
+    fn with_seed(self, seed: u64) -> TieBreak {
+        match self {
+            TieBreakArg::PossibleAnswer => TieBreak::PossibleAnswer,
+            TieBreakArg::Frequency => TieBreak::Frequency,
+            TieBreakArg::Alphabetical => TieBreak::Alphabetical,
+            TieBreakArg::Shuffled => TieBreak::Shuffled(seed),
+        }
+    }
+}
+
+/// A dictionary loaded at runtime, see [`crane::load_dictionary`].
+type Dictionary = Vec<(&'static str, usize)>;
 
 fn main() {
-    let wordle = Wordle::new();
-    for answer in GAMES.split_whitespace() {
-        let guesser = crane::algorithms::Naive::new();
-        wordle.play(answer, guesser);
+    let matches = Cli::command().get_matches();
+    let mut cli = Cli::from_arg_matches(&matches).unwrap_or_else(|e| e.exit());
+
+    // Logs are off unless enabled through `RUST_LOG`, e.g. `RUST_LOG=crane=debug` to see why every
+    // guess was picked.
+    tracing_subscriber::fmt()
+        .with_env_filter(EnvFilter::from_default_env())
+        .with_writer(io::stderr)
+        .init();
+
+    if let Err(e) = configure(&mut cli, &matches) {
+        eprintln!("error: {}", e);
+        process::exit(1);
+    }
+
+    let result = match cli.command {
+        None => play_with(&cli.play),
+        Some(Command::Play(args)) => play_with(&args),
+        Some(Command::Solve(args)) => solve_with(&args),
+        Some(Command::DictStats(args)) => dict_stats(&args),
+        Some(Command::BuildBook(args)) => build_book(&args),
+        Some(Command::Compare(args)) => compare(&args),
+        Some(Command::Today(args)) => today(&args),
+        Some(Command::Difficulty(args)) => difficulty(&args),
+        Some(Command::BuildTree(args)) => build_tree(&args),
+        Some(Command::BuildPool(args)) => build_pool(&args),
+        Some(Command::PrecomputeMatrix(args)) => precompute_matrix(&args),
+        Some(Command::Grade(args)) => grade(&args),
+        Some(Command::Serve(args)) => serve(&args),
+        Some(Command::Simulate(args)) => simulate(&args),
+        Some(Command::Tui(args)) => tui(&args),
+        Some(Command::History(args)) => history(&args),
+    };
+
+    if let Err(e) = result {
+        eprintln!("error: {}", e);
+        process::exit(1);
+    }
+}
+
+/// Fills the options left out of the command line in from the configuration file.
+fn configure(cli: &mut Cli, matches: &ArgMatches) -> io::Result<()> {
+    let config = match &cli.config {
+        Some(path) => Config::load(path)?,
+        None => Config::find()?.unwrap_or_default(),
+    };
+    let matches = matches.subcommand().map_or(matches, |(_, matches)| matches);
+    let hard = |hard: &mut bool, no_hard| configure_hard(hard, no_hard, &config, matches);
+
+    match &mut cli.command {
+        None => cli.play.configure(&config, matches)?,
+        Some(Command::Play(args)) => args.configure(&config, matches)?,
+        Some(Command::Solve(args)) => args.guesser.configure(&config, matches),
+        Some(Command::DictStats(args)) => {
+            args.dictionary = args.dictionary.take().or_else(|| config.dictionary.clone())
+        }
+        Some(Command::BuildBook(args)) => {
+            args.guesser.configure(&config, matches);
+            hard(&mut args.hard, args.no_hard);
+        }
+        Some(Command::Compare(args)) => {
+            args.guesser.configure(&config, matches);
+            hard(&mut args.hard, args.no_hard);
+        }
+        Some(Command::Today(args)) => {
+            args.guesser.configure(&config, matches);
+            hard(&mut args.hard, args.no_hard);
+        }
+        Some(Command::Difficulty(args)) => {
+            args.guesser.configure(&config, matches);
+            hard(&mut args.hard, args.no_hard);
+        }
+        Some(Command::BuildTree(args)) => {
+            args.dictionary = args.dictionary.take().or_else(|| config.dictionary.clone());
+            hard(&mut args.hard, args.no_hard);
+        }
+        Some(Command::BuildPool(args)) => {
+            args.dictionary = args.dictionary.take().or_else(|| config.dictionary.clone())
+        }
+        Some(Command::PrecomputeMatrix(args)) => {
+            args.dictionary = args.dictionary.take().or_else(|| config.dictionary.clone())
+        }
+        Some(Command::Grade(args)) => {
+            args.dictionary = args.dictionary.take().or_else(|| config.dictionary.clone())
+        }
+        Some(Command::Serve(args)) => args.guesser.configure(&config, matches),
+        Some(Command::Tui(args)) => args.guesser.configure(&config, matches),
+        Some(Command::History(_)) => {}
+        Some(Command::Simulate(args)) => {
+            args.guesser.configure(&config, matches);
+            hard(&mut args.hard, args.no_hard);
+        }
+    }
+
+    if let Some(threads) = cli
+        .threads
+        .map(|threads| threads as usize)
+        .or(config.threads)
+    {
+        set_threads(threads)?;
+    }
+    Ok(())
+}
+
+#[cfg(feature = "parallel")]
+fn set_threads(threads: usize) -> io::Result<()> {
+    rayon::ThreadPoolBuilder::new()
+        .num_threads(threads)
+        .build_global()
+        .map_err(io::Error::other)
+}
+
+/// Without the `parallel` feature, guesses are scored on the current thread anyway.
+#[cfg(not(feature = "parallel"))]
+fn set_threads(_threads: usize) -> io::Result<()> {
+    Ok(())
+}
+
+/// Returns whether the option `id` was given on the command line rather than left to its default.
+fn given(matches: &ArgMatches, id: &str) -> bool {
+    matches.value_source(id) == Some(ValueSource::CommandLine)
+}
+
+/// Takes hard mode from the configuration file unless `--hard` or `--no-hard` was given.
+fn configure_hard(hard: &mut bool, no_hard: bool, config: &Config, matches: &ArgMatches) {
+    if !no_hard && !given(matches, "hard") {
+        *hard = config.hard.unwrap_or(false);
+    }
+}
+
+impl PlayArgs {
+    fn configure(&mut self, config: &Config, matches: &ArgMatches) -> io::Result<()> {
+        self.guesser.configure(config, matches);
+        configure_hard(&mut self.hard, self.no_hard, config, matches);
+        if let Some(output) = config
+            .output
+            .as_deref()
+            .filter(|_| !given(matches, "output"))
+        {
+            self.output = Output::from_str(output, true).map_err(|_| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("unknown output format `{}` in the configuration", output),
+                )
+            })?;
+        }
+        Ok(())
+    }
+}
+
+impl GuesserArgs {
+    fn configure(&mut self, config: &Config, matches: &ArgMatches) {
+        if let Some(algorithm) = config
+            .algorithm
+            .as_ref()
+            .filter(|_| !given(matches, "implementation"))
+        {
+            self.implementation = algorithm.clone();
+        }
+        if self.dictionary.is_none() {
+            self.dictionary = config.dictionary.clone();
+        }
+        if self.openers.is_empty() && self.book.is_none() && self.tree.is_none() {
+            self.openers = config.openers.clone().unwrap_or_default();
+        }
+    }
+}
+
+fn play_with(args: &PlayArgs) -> io::Result<()> {
+    let dictionary = load(&args.guesser.dictionary)?;
+    let mut wordle = match &dictionary {
+        Some(dictionary) => Wordle::with_dictionary(dictionary.iter().copied()),
+        None => Wordle::new(),
+    }
+    .with_hard_mode(args.hard)
+    .with_max_guesses(Some(args.max_guesses).filter(|&max| max > 0));
+    if args.guesser.known_answers {
+        wordle = wordle.with_answers(crane::builtin_answers());
+    }
+
+    let make = prepare(&args.guesser, dictionary)?;
+    play(&wordle, make, args)
+}
+
+fn solve_with(args: &SolveArgs) -> io::Result<()> {
+    let dictionary = load(&args.guesser.dictionary)?;
+    // Only to catch the words that are not in the dictionary, e.g. typos.
+    let wordle = match &dictionary {
+        Some(dictionary) => Wordle::with_dictionary(dictionary.iter().copied()),
+        None => Wordle::new(),
+    };
+
+    let make = prepare(&args.guesser, dictionary)?;
+    solve(make(), wordle, args)
+}
+
+fn dict_stats(args: &DictStatsArgs) -> io::Result<()> {
+    let dictionary =
+        load(&args.dictionary)?.unwrap_or_else(|| crane::builtin_dictionary().collect());
+    if dictionary.is_empty() {
+        // Every statistic is a share of the words.
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "the dictionary is empty",
+        ));
+    }
+    let words = || dictionary.iter().map(|&(word, _)| word);
+    println!("words: {}", dictionary.len());
+
+    println!();
+    println!("letter frequency by position:");
+    let frequencies: [[usize; 26]; 5] = analysis::letter_frequencies(words());
+    println!(
+        "    {}",
+        (1..=5)
+            .map(|position| format!("{:>7}", position))
+            .collect::<String>()
+    );
+    for (letter, i) in ('a'..='z').zip(0..) {
+        let row: String = frequencies
+            .iter()
+            .map(|position| {
+                format!(
+                    "{:>6.1}%",
+                    100.0 * position[i] as f64 / dictionary.len() as f64
+                )
+            })
+            .collect();
+        println!("  {} {}", letter, row);
+    }
+
+    println!();
+    println!("most common bigrams:");
+    for (bigram, count) in analysis::bigrams(words()).into_iter().take(args.top) {
+        println!("  {} {:>6}", bigram, count);
+    }
+
+    println!();
+    println!("best openers:");
+    for (word, information) in analysis::best_openers(&dictionary, args.top) {
+        println!("  {} {:.3} bits", word, information);
+    }
+
+    Ok(())
+}
+
+fn implementations() -> PossibleValuesParser {
+    PossibleValuesParser::new(registry().iter().map(|algorithm| {
+        PossibleValue::new(algorithm.name)
+            .aliases(algorithm.aliases.iter().copied())
+            .help(algorithm.description)
+    }))
+}
+
+fn compare(args: &CompareArgs) -> io::Result<()> {
+    let dictionary = load(&args.guesser.dictionary)?;
+    let mut wordle = match &dictionary {
+        Some(dictionary) => Wordle::with_dictionary(dictionary.iter().copied()),
+        None => Wordle::new(),
+    }
+    .with_hard_mode(args.hard);
+    if args.guesser.known_answers {
+        wordle = wordle.with_answers(crane::builtin_answers());
+    }
+
+    let mut tournament = Tournament::new(&wordle)
+        .with_progress(args.progress)
+        .with_jobs(args.jobs as usize)
+        .with_worst(args.worst.unwrap_or(0))
+        .with_score(args.score);
+    for implementation in &args.impls {
+        let guesser = GuesserArgs {
+            implementation: implementation.clone(),
+            ..args.guesser.clone()
+        };
+        tournament = tournament.contender(
+            implementation.as_str(),
+            prepare(&guesser, dictionary.clone())?,
+        );
+    }
+
+    let mut answers = read_answers(&args.answers, &wordle)?;
+    answers.truncate(args.max_games.unwrap_or(usize::MAX));
+    let standings = match &args.turns {
+        Some(path) => {
+            let out = BufWriter::new(File::create(path)?);
+            let mut csv = TurnsCsvWriter::new(out, wordle.answers())?;
+            let mut written = Ok(());
+            let standings = tournament.run_with(&answers, |name, game| {
+                if written.is_ok() {
+                    written = csv.write(name, &game.transcript);
+                }
+            });
+            written?;
+            csv.into_inner().flush()?;
+            standings
+        }
+        None => tournament.run(&answers),
+    };
+    print!("{}", standings);
+    if let Some(best) = standings.best() {
+        println!("best by {}: {}", args.score, best.name);
+    }
+    if args.worst.is_some() {
+        for standing in &standings.results {
+            print!("\n{}: {}", standing.name, standing.worst);
+        }
+    }
+
+    Ok(())
+}
+
+fn today(args: &TodayArgs) -> io::Result<()> {
+    let date = args.date.unwrap_or_else(daily::today);
+    let number = daily::puzzle_number(date).ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("there was no puzzle yet on {}", date),
+        )
+    })?;
+    let answer = daily_answer(args, date)?;
+
+    let dictionary = load(&args.guesser.dictionary)?;
+    let mut wordle = match &dictionary {
+        Some(dictionary) => Wordle::with_dictionary(dictionary.iter().copied()),
+        None => Wordle::new(),
+    }
+    .with_hard_mode(args.hard);
+    if args.guesser.known_answers {
+        wordle = wordle.with_answers(crane::builtin_answers());
+    }
+
+    let make = prepare(&args.guesser, dictionary)?;
+    let transcript = wordle
+        .play_transcript(&answer, make())
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+    print!("Wordle {} {}", number, render::share(&transcript));
+    if args.show {
+        println!();
+        print!("{}", render::ansi(&transcript.guesses));
+    }
+
+    Ok(())
+}
+
+fn difficulty(args: &DifficultyArgs) -> io::Result<()> {
+    let dictionary = load(&args.guesser.dictionary)?;
+    let mut wordle = match &dictionary {
+        Some(dictionary) => Wordle::with_dictionary(dictionary.iter().copied()),
+        None => Wordle::new(),
+    }
+    .with_hard_mode(args.hard);
+    if args.guesser.known_answers {
+        wordle = wordle.with_answers(crane::builtin_answers());
+    }
+
+    let make = prepare(&args.guesser, dictionary)?;
+    let difficulty = wordle
+        .difficulty(&args.answer, make())
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+    let transcript = &difficulty.transcript;
+    match transcript.score {
+        Some(score) => println!("Found in {} guesses", score),
+        None => println!("Not found in {} guesses", transcript.guesses.len()),
+    }
+    for (guess, remaining) in transcript.guesses.iter().zip(&difficulty.remaining) {
+        println!("  {}: {} left", guess.word, remaining);
+    }
+    if difficulty.family.is_empty() {
+        println!("No other answer differs at a single position");
+    } else {
+        println!(
+            "{} other answers differ at a single position: {}",
+            difficulty.family.len(),
+            difficulty.family.join(", ")
+        );
+    }
+    if difficulty.trap > 1 {
+        println!(
+            "Up to {} answers differ at the same position, which may take a guess each",
+            difficulty.trap
+        );
+    }
+
+    Ok(())
+}
+
+/// Returns the answer of the puzzle of `date`, fetched from the official game if asked to.
+fn daily_answer(args: &TodayArgs, date: NaiveDate) -> io::Result<String> {
+    let builtin = daily::answer(date);
+
+    if args.fetch {
+        #[cfg(feature = "fetch")]
+        {
+            let fetched = daily::fetch(date)?;
+            if builtin.is_some_and(|builtin| builtin != fetched) {
+                eprintln!(
+                    "warning: the official answer of {} differs from the built-in answer list",
+                    date
+                );
+            }
+            return Ok(fetched);
+        }
+        #[cfg(not(feature = "fetch"))]
+        return Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "fetching answers requires the `fetch` feature",
+        ));
+    }
+
+    builtin.map(str::to_string).ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::NotFound,
+            format!(
+                "the built-in answer list is only followed until {}, use --fetch for {}",
+                daily::last_day(),
+                date
+            ),
+        )
+    })
+}
+
+fn build_book(args: &BuildBookArgs) -> io::Result<()> {
+    let dictionary = load(&args.guesser.dictionary)?;
+    let answers: Vec<&'static str> = match &dictionary {
+        _ if args.guesser.known_answers => crane::builtin_answers().collect(),
+        Some(dictionary) => dictionary.iter().map(|&(word, _)| word).collect(),
+        None => crane::builtin_dictionary().map(|(word, _)| word).collect(),
+    };
+
+    let key = opening_key(&args.guesser, &dictionary);
+    let make = factory(&args.guesser, dictionary)?;
+    let opener = match &args.opener {
+        Some(opener) => {
+            validate_word::<5>(opener)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+            Cow::Owned(opener.clone())
+        }
+        None => first_guess(&args.guesser, &key, &make)?,
+    };
+
+    let book = OpeningBook::build(&opener, answers, args.hard, make);
+    book.save(&args.output)?;
+    println!(
+        "wrote {} responses to {} to {}",
+        book.len(),
+        opener,
+        args.output.display()
+    );
+
+    Ok(())
+}
+
+fn build_tree(args: &BuildTreeArgs) -> io::Result<()> {
+    let dictionary = load(&args.dictionary)?;
+    let cache = match &dictionary {
+        Some(dictionary) => Arc::new(PatternCache::new(dictionary.iter().map(|&(word, _)| word))),
+        None => PatternCache::global(),
+    };
+    let answers: Vec<&str> = if args.known_answers {
+        crane::builtin_answers().collect()
+    } else {
+        (0..cache.len()).map(|i| cache.word(i)).collect()
+    };
+
+    let beam = Some(args.beam).filter(|&beam| beam > 0);
+    let mut cancel = CancelToken::new();
+    if let Some(time_limit) = args.time_limit {
+        cancel = cancel.with_deadline(Instant::now() + time_limit);
+    }
+    let tree =
+        DecisionTree::build_until(&cache, answers, args.hard, beam, &cancel).ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "none of the answers is in the dictionary",
+            )
+        })?;
+    tree.save(&args.output)?;
+    println!(
+        "wrote a tree finding {} answers in {:.3} guesses on average and {} at most to {}",
+        tree.len(),
+        tree.average_guesses(),
+        tree.max_guesses(),
+        args.output.display()
+    );
+
+    Ok(())
+}
+
+fn build_pool(args: &BuildPoolArgs) -> io::Result<()> {
+    let dictionary = load(&args.dictionary)?;
+    let cache = match dictionary {
+        Some(dictionary) => {
+            let dictionary = crane::Dictionary::with_counts(dictionary);
+            Arc::new(PatternCache::for_dictionary(Arc::new(dictionary)))
+        }
+        None => PatternCache::global(),
+    };
+    let weights = Prior::from(args.prior).weights(&cache.dictionary().counts());
+    let answers: Vec<usize> = if args.known_answers {
+        crane::builtin_answers()
+            .filter_map(|answer| cache.index(answer))
+            .collect()
+    } else {
+        (0..cache.len()).collect()
+    };
+    if answers.is_empty() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "none of the answers is in the dictionary",
+        ));
+    }
+
+    let candidates: Vec<(usize, f64)> = answers.iter().map(|&i| (i, weights[i])).collect();
+    let pool = ReducedPool::build(&cache, &candidates, args.size, args.epsilon);
+    pool.save(&args.output)?;
+    let (covered, turns) = pool.covered();
+    println!(
+        "wrote {} words, within {} bits of the best guess at {} of {} turns, to {}",
+        pool.len(),
+        pool.epsilon(),
+        covered,
+        turns,
+        args.output.display()
+    );
+
+    Ok(())
+}
+
+fn precompute_matrix(args: &PrecomputeMatrixArgs) -> io::Result<()> {
+    let dictionary = load(&args.dictionary)?;
+    let cache = match &dictionary {
+        Some(dictionary) => PatternCache::new(dictionary.iter().map(|&(word, _)| word)),
+        None => PatternCache::new(crane::builtin_dictionary().map(|(word, _)| word)),
+    };
+    if args.gpu {
+        #[cfg(feature = "gpu")]
+        if let Err(e) = crane::gpu::Gpu::new().and_then(|gpu| cache.compute_on_gpu(&gpu)) {
+            eprintln!("warning: {}, computing the matrix on the CPU instead", e);
+        }
+        #[cfg(not(feature = "gpu"))]
+        return Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "computing the matrix on the GPU requires the `gpu` feature",
+        ));
+    }
+    cache.save(&args.output)?;
+    println!(
+        "wrote the {0}x{0} pattern matrix to {1}",
+        cache.len(),
+        args.output.display()
+    );
+
+    Ok(())
+}
+
+fn grade(args: &GradeArgs) -> io::Result<()> {
+    let invalid = |message: String| io::Error::new(io::ErrorKind::InvalidData, message);
+    let input: Box<dyn BufRead> = match &args.transcript {
+        Some(path) => Box::new(io::BufReader::new(File::open(path)?)),
+        None => Box::new(io::stdin().lock()),
+    };
+    let mut guesses = Vec::new();
+    for (i, line) in input.lines().enumerate() {
+        let line = line?;
+        let (word, mask) = match line.split_whitespace().collect::<Vec<_>>().as_slice() {
+            [] => continue,
+            &[word, mask] => (word.to_lowercase(), mask),
+            _ => {
+                return Err(invalid(format!(
+                    "line {}: expected a word and its colors, e.g. `crane GYXXX`",
+                    i + 1
+                )))
+            }
+        };
+        if let Err(e) = validate_word::<5>(&word) {
+            return Err(invalid(format!("line {}: {}", i + 1, e)));
+        }
+        let mask = Correctness::parse_mask(mask).ok_or_else(|| {
+            invalid(format!(
+                "line {}: `{}` is not five colors out of G, Y and X",
+                i + 1,
+                mask
+            ))
+        })?;
+        guesses.push(Guess::new(word, mask));
+    }
+
+    let dictionary = load(&args.dictionary)?;
+    let (cache, answers) = answers(&dictionary, args.known_answers);
+
+    let grades = crane::grade::grade(&cache, &answers, &guesses);
+    for (turn, grade) in grades.iter().enumerate() {
+        println!(
+            "{}. {}  {:.2} bits, rank {}, {:.2} bits lost to {}",
+            turn + 1,
+            grade.word,
+            grade.entropy,
+            grade.rank,
+            grade.entropy_lost(),
+            grade.best
+        );
+        println!(
+            "   {} -> {} candidates, {:.1} expected vs. {:.1} with {}",
+            grade.candidates,
+            grade.remaining,
+            grade.expected_remaining,
+            grade.best_expected_remaining,
+            grade.best
+        );
+    }
+    if let Some(grade) = grades.last().filter(|grade| grade.remaining == 0) {
+        return Err(invalid(format!(
+            "no answer agrees with the colors of `{}`",
+            grade.word
+        )));
+    }
+
+    if let Some((turn, word)) = &args.what_if {
+        let mut wordle = match &dictionary {
+            Some(dictionary) => Wordle::with_dictionary(dictionary.iter().copied()),
+            None => Wordle::new(),
+        };
+        if args.known_answers {
+            wordle = wordle.with_answers(crane::builtin_answers());
+        }
+        let make = lookup("naive")
+            .expect("the naive implementation is registered")
+            .factory(Settings {
+                dictionary,
+                cache: Some(cache),
+                answers: args
+                    .known_answers
+                    .then(|| crane::builtin_answers().collect()),
+                ..Settings::default()
+            })?;
+
+        let counterfactual = wordle
+            .counterfactual(&guesses, turn - 1, word, make)
+            .map_err(|e| invalid(e.to_string()))?;
+        println!();
+        println!(
+            "had {} been played instead of {}:",
+            word, counterfactual.actual.word
+        );
+        println!(
+            "   {:.1} candidates left on average instead of {}",
+            counterfactual.expected_remaining(),
+            counterfactual.actual_remaining.len()
+        );
+        match counterfactual.expected_score() {
+            Some(score) => println!("   solved in {:.2} guesses on average", score),
+            None => println!("   not always solved"),
+        }
+    }
+
+    Ok(())
+}
+
+fn what_if(s: &str) -> Result<(usize, String), String> {
+    let (turn, word) = s
+        .split_once('=')
+        .ok_or_else(|| "expected a turn and a word, e.g. `2=slate`".to_string())?;
+    let turn: usize = turn.parse().map_err(|e| format!("{}", e))?;
+    if turn == 0 {
+        return Err("turns are counted from 1".to_string());
+    }
+    Ok((turn, word.to_lowercase()))
+}
+
+fn score(s: &str) -> Result<Score, String> {
+    match s.split_once(':') {
+        None if s == "guesses" => Ok(Score::Guesses),
+        None if s == "win-loss" => Ok(Score::WinLoss),
+        Some(("penalty", cost)) => {
+            let fail_cost: f64 = cost.parse().map_err(|e| format!("{}", e))?;
+            if !fail_cost.is_finite() || fail_cost < 0.0 {
+                return Err("the cost of a lost game must be a non-negative number".to_string());
+            }
+            Ok(Score::Penalty { fail_cost })
+        }
+        _ => Err("expected `guesses`, `win-loss` or `penalty:COST`".to_string()),
+    }
+}
+
+fn serve(args: &ServeArgs) -> io::Result<()> {
+    #[cfg(feature = "serve")]
+    {
+        let dictionary = load(&args.guesser.dictionary)?;
+        let (cache, answers) = answers(&dictionary, args.guesser.known_answers);
+        let server = crane::serve::Server::new(prepare(&args.guesser, dictionary)?, cache, answers);
+        eprintln!("listening on http://{}", args.addr);
+        server.run(args.addr.as_str())
+    }
+    #[cfg(not(feature = "serve"))]
+    {
+        let _ = args;
+        Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "serving over HTTP requires the `serve` feature",
+        ))
+    }
+}
+
+fn tui(args: &TuiArgs) -> io::Result<()> {
+    #[cfg(feature = "tui")]
+    {
+        let dictionary = load(&args.guesser.dictionary)?;
+        let make = prepare(&args.guesser, dictionary)?;
+        crane::tui::run(make())
+    }
+    #[cfg(not(feature = "tui"))]
+    {
+        let _ = args;
+        Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "the terminal UI requires the `tui` feature",
+        ))
+    }
+}
+
+fn history(args: &HistoryArgs) -> io::Result<()> {
+    #[cfg(feature = "db")]
+    {
+        let db = crane::db::Database::open(&args.database)?;
+        let algorithm = args.algorithm.as_deref();
+        for summary in db.summaries()? {
+            if algorithm.is_some_and(|algorithm| algorithm != summary.algorithm) {
+                continue;
+            }
+            let average = summary
+                .average
+                .map_or_else(|| "-".to_string(), |average| format!("{:.3}", average));
+            println!(
+                "{} {}: {} games, average {}, {} failed, {:.1}s",
+                summary.version,
+                summary.algorithm,
+                summary.games,
+                average,
+                summary.failures,
+                summary.elapsed.as_secs_f64()
+            );
+        }
+
+        let lists = [("best", args.best, true), ("worst", args.worst, false)];
+        for (title, limit, best) in lists {
+            let Some(limit) = limit else { continue };
+            let games = if best {
+                db.best(algorithm, limit)?
+            } else {
+                db.worst(algorithm, limit)?
+            };
+            println!("\n{}:", title);
+            for game in games {
+                let score = game
+                    .score
+                    .map_or_else(|| "not found".to_string(), |score| score.to_string());
+                let guesses: Vec<&str> = game.guesses.iter().map(|guess| &*guess.word).collect();
+                println!(
+                    "{}: {} ({}) by {} {} in {:?}",
+                    game.answer,
+                    score,
+                    guesses.join(" "),
+                    game.version,
+                    game.algorithm,
+                    game.elapsed
+                );
+            }
+        }
+        Ok(())
+    }
+    #[cfg(not(feature = "db"))]
+    {
+        let _ = args;
+        Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "the game history requires the `db` feature",
+        ))
+    }
+}
+
+fn simulate(args: &SimulateArgs) -> io::Result<()> {
+    let dictionary = load(&args.guesser.dictionary)?;
+    let words = dictionary
+        .clone()
+        .unwrap_or_else(|| crane::builtin_dictionary().collect());
+    let counts: HashMap<&str, usize> = words.iter().copied().collect();
+    let answers: Vec<(&'static str, usize)> = crane::builtin_answers()
+        .filter_map(|answer| Some((answer, *counts.get(answer)?)))
+        .collect();
+    if answers.is_empty() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "none of the answers is in the dictionary",
+        ));
+    }
+
+    let wordle = Wordle::with_dictionary(words)
+        .with_answers(answers.iter().map(|&(answer, _)| answer))
+        .with_hard_mode(args.hard)
+        .with_max_guesses(Some(crane::OFFICIAL_MAX_GUESSES));
+    let days = sample_answers(&answers, args.days, args.guesser.seed, args.sampling.into());
+
+    let make = prepare(&args.guesser, dictionary)?;
+    let mut streaks = Streaks::new();
+    Batch::new(&wordle)
+        .with_progress(args.progress)
+        .with_jobs(args.jobs as usize)
+        .run(&days, make, |answer, result| match result {
+            Ok(game) => streaks.record(game.transcript.score),
+            Err(e) => {
+                eprintln!("{}: {}", answer, e);
+                streaks.record(None);
+            }
+        });
+    print!("{}", streaks);
+
+    Ok(())
+}
+
+/// Returns the patterns between the words of the dictionary, and which of them may be the answer.
+fn answers(
+    dictionary: &Option<Dictionary>,
+    known_answers: bool,
+) -> (Arc<PatternCache>, Candidates) {
+    let cache = match dictionary {
+        Some(dictionary) => Arc::new(PatternCache::new(dictionary.iter().map(|&(word, _)| word))),
+        None => PatternCache::global(),
+    };
+    let answers = if known_answers {
+        Candidates::from_indices(
+            cache.len(),
+            crane::builtin_answers().filter_map(|answer| cache.index(answer)),
+        )
+    } else {
+        Candidates::all(cache.len())
+    };
+    (cache, answers)
+}
+
+/// Builds the guessers of the selected algorithm, which play their guesses out of the decision
+/// tree, their first guesses out of the opening book, or the given openers first if any.
+///
+/// Otherwise, the first guess is computed once up front, or read from the openings file if it was
+/// computed by an earlier run, so that it is not recomputed for every game.
+fn prepare(args: &GuesserArgs, dictionary: Option<Dictionary>) -> io::Result<Factory> {
+    if let Some(path) = &args.tree {
+        let tree = Arc::new(DecisionTree::load(path)?);
+        let make = budgeted(factory(args, dictionary)?, args.time_limit);
+        return Ok(Box::new(move || {
+            Box::new(TreeGuesser::new(make(), tree.clone()))
+        }));
+    }
+
+    if let Some(path) = &args.book {
+        let book = Arc::new(OpeningBook::load(path)?);
+        let make = budgeted(factory(args, dictionary)?, args.time_limit);
+        return Ok(Box::new(move || {
+            Box::new(Booked::new(make(), book.clone()))
+        }));
+    }
+
+    if !args.openers.is_empty() {
+        let words = dictionary
+            .clone()
+            .unwrap_or_else(|| crane::builtin_dictionary().collect());
+        let openers: Vec<&'static str> = args
+            .openers
+            .iter()
+            .map(|opener| {
+                words
+                    .iter()
+                    .map(|&(word, _)| word)
+                    .find(|&word| word == opener)
+                    .ok_or_else(|| {
+                        io::Error::new(
+                            io::ErrorKind::InvalidInput,
+                            format!("the opener `{}` is not in the dictionary", opener),
+                        )
+                    })
+            })
+            .collect::<io::Result<_>>()?;
+        let make = budgeted(factory(args, dictionary)?, args.time_limit);
+        return Ok(Box::new(move || {
+            Box::new(FixedOpening::new(make(), openers.iter().copied()))
+        }));
+    }
+
+    let key = opening_key(args, &dictionary);
+    let make = factory(args, dictionary)?;
+    let first = first_guess(args, &key, &make)?;
+    let make = budgeted(make, args.time_limit);
+    Ok(Box::new(move || {
+        Box::new(Opening::new(make(), first.clone()))
+    }))
+}
+
+/// Gives the guessers of `make` this much time to think at every turn, if any.
+fn budgeted(make: Factory, time_limit: Option<Duration>) -> Factory {
+    match time_limit {
+        Some(budget) => Box::new(move || Box::new(Anytime::new(make(), budget))),
+        None => make,
+    }
+}
+
+fn factory(args: &GuesserArgs, dictionary: Option<Dictionary>) -> io::Result<Factory> {
+    let algorithm = lookup(&args.implementation).expect("only registered algorithms are accepted");
+    let opener = OpenerRules::new(args.opener_rules.iter().cloned());
+    let followed = match &dictionary {
+        Some(dictionary) => dictionary.iter().any(|&(word, _)| opener.allows(word)),
+        None => crane::builtin_dictionary().any(|(word, _)| opener.allows(word)),
+    };
+    if !followed {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("no word of the dictionary follows the rules `{}`", opener),
+        ));
+    }
+    let cache = match &args.matrix {
+        Some(path) => {
+            let cache = match &dictionary {
+                Some(dictionary) => {
+                    PatternCache::load(dictionary.iter().map(|&(word, _)| word), path)?
+                }
+                None => {
+                    PatternCache::load(crane::builtin_dictionary().map(|(word, _)| word), path)?
+                }
+            };
+            Some(Arc::new(cache))
+        }
+        // The endgame search shares the patterns of the guessers it takes over from.
+        None if args.endgame.is_some() => Some(match &dictionary {
+            Some(dictionary) => {
+                Arc::new(PatternCache::new(dictionary.iter().map(|&(word, _)| word)))
+            }
+            None => PatternCache::global(),
+        }),
+        None => None,
+    };
+    let reduced_pool = reduced_pool(args, &dictionary)?.map(Arc::new);
+    let answers: Option<Vec<&'static str>> = args
+        .known_answers
+        .then(|| crane::builtin_answers().collect());
+    let make = algorithm.factory(Settings {
+        dictionary,
+        answers: answers.clone(),
+        prior: args.prior.into(),
+        weighting: weighting(args)?.map(Arc::new),
+        pool: args.pool.into(),
+        reduced_pool,
+        opener,
+        tie_break: args.tie_break.with_seed(args.seed),
+        seed: args.seed,
+        crossover: crossover(args),
+        cache: cache.clone(),
+    })?;
+
+    let (Some(threshold), Some(cache)) = (args.endgame, cache) else {
+        return Ok(make);
+    };
+    Ok(Box::new(move || {
+        let mut endgame = Endgame::new(make(), cache.clone()).with_threshold(threshold);
+        if let Some(answers) = &answers {
+            endgame = endgame.with_answers(answers.iter().copied());
+        }
+        Box::new(endgame)
+    }))
+}
+
+fn crossover(args: &GuesserArgs) -> Crossover {
+    Crossover {
+        turns: args.crossover_turns,
+        candidates: args.crossover_candidates,
+    }
+}
+
+/// Reads the pool given with `--reduced-pool`, if any, which must have been built for the
+/// `dictionary`.
+fn reduced_pool(
+    args: &GuesserArgs,
+    dictionary: &Option<Dictionary>,
+) -> io::Result<Option<ReducedPool>> {
+    let Some(path) = &args.reduced_pool else {
+        return Ok(None);
+    };
+    let pool = match dictionary {
+        Some(dictionary) => {
+            let words = crane::Dictionary::new(dictionary.iter().map(|&(word, _)| word));
+            ReducedPool::load(path, &words)?
+        }
+        None => ReducedPool::load(path, &crane::Dictionary::builtin())?,
+    };
+    Ok(Some(pool))
+}
+
+/// Reads the factors given with `--weights`, if any.
+fn weighting(args: &GuesserArgs) -> io::Result<Option<Weighting>> {
+    args.weights
+        .as_ref()
+        .map(|path| Weighting::load(path, &args.weight_column))
+        .transpose()
+}
+
+/// Computes the first guess of the guessers from `make`, unless the openings file already has it.
+fn first_guess(args: &GuesserArgs, key: &str, make: &Factory) -> io::Result<Cow<'static, str>> {
+    match &args.openings {
+        Some(path) => {
+            let mut cache = OpeningCache::load(path)?;
+            let first = cache.get_or_compute(key, make());
+            cache.save()?;
+            Ok(first)
+        }
+        None => Ok(make().guess(&History::new())),
+    }
+}
+
+/// Identifies the algorithm and dictionary a first guess was computed for in the openings file.
+fn opening_key(args: &GuesserArgs, dictionary: &Option<Dictionary>) -> String {
+    fn name(value: &impl ValueEnum) -> String {
+        let value = value.to_possible_value().expect("no value is skipped");
+        value.get_name().to_string()
+    }
+
+    let algorithm = lookup(&args.implementation).expect("only registered algorithms are accepted");
+    let fingerprint = match dictionary {
+        Some(dictionary) => fingerprint(dictionary.iter().copied()),
+        None => fingerprint(crane::builtin_dictionary()),
+    };
+
+    // A weighting that cannot be read fails when the guessers are built anyway.
+    let weighting = match weighting(args) {
+        Ok(Some(weighting)) => format!("-weighted:{:016x}", weighting.fingerprint()),
+        _ => String::new(),
+    };
+
+    // So does a reduced pool that cannot be read, or was built for another dictionary.
+    let reduced_pool = match reduced_pool(args, dictionary) {
+        Ok(Some(pool)) => format!("-reduced:{:016x}", pool.fingerprint()),
+        _ => String::new(),
+    };
+
+    let opener = match OpenerRules::new(args.opener_rules.iter().cloned()) {
+        rules if rules.is_empty() => String::new(),
+        rules => format!("-opener:{}", rules),
+    };
+
+    let endgame = match args.endgame {
+        Some(threshold) => format!("-endgame:{}", threshold),
+        None => String::new(),
+    };
+
+    // Only the ensemble hands over to another algorithm.
+    let crossover = match algorithm.name {
+        "ensemble" => {
+            let crossover = crossover(args);
+            format!("-crossover:{}:{}", crossover.turns, crossover.candidates)
+        }
+        _ => String::new(),
+    };
+
+    format!(
+        "{}-{}-{}-{}-{}{}{}{}{}{}{}:{:016x}",
+        algorithm.name,
+        name(&args.prior),
+        name(&args.pool),
+        name(&args.tie_break),
+        args.seed,
+        if args.known_answers { "-known" } else { "" },
+        weighting,
+        reduced_pool,
+        opener,
+        crossover,
+        endgame,
+        fingerprint
+    )
+}
+
+/// Parses a duration given in seconds on the command line.
+fn seconds(s: &str) -> Result<Duration, String> {
+    let seconds: f64 = s.parse().map_err(|e| format!("{}", e))?;
+    Duration::try_from_secs_f64(seconds).map_err(|e| format!("{}", e))
+}
+
+fn load(path: &Option<PathBuf>) -> io::Result<Option<Dictionary>> {
+    Ok(path.as_ref().map(crane::load_dictionary).transpose()?)
+}
+
+/// Reads the answers given with `--answers`, from the standard input if the path is `-`, or
+/// returns the official ones.
+///
+/// Fails if some of the answers may not be guessed in `wordle`, since guessers could never find
+/// them. The contents are kept alive for the rest of the program, like those of dictionaries.
+fn read_answers(path: &Option<PathBuf>, wordle: &Wordle) -> io::Result<Vec<&'static str>> {
+    let contents = match path {
+        None => return Ok(crane::builtin_answers().collect()),
+        Some(path) if path.as_os_str() == "-" => io::read_to_string(io::stdin())?,
+        Some(path) => fs::read_to_string(path)?,
+    };
+    let contents: &'static str = Box::leak(contents.into_boxed_str());
+    let answers: Vec<&str> = contents.split_whitespace().collect();
+
+    let unknown: Vec<&str> = answers
+        .iter()
+        .copied()
+        .filter(|answer| !wordle.is_allowed(answer))
+        .collect();
+    if !unknown.is_empty() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("answers not in the dictionary: {}", unknown.join(", ")),
+        ));
+    }
+    Ok(answers)
+}
+
+fn play<G: Guesser>(wordle: &Wordle, mk: impl Fn() -> G + Sync, args: &PlayArgs) -> io::Result<()> {
+    let answers: Vec<&str> = match &args.answer {
+        Some(answer) => {
+            wordle
+                .validate_guess(answer)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+            vec![answer.as_str()]
+        }
+        None => read_answers(&args.answers, wordle)?,
+    };
+
+    let out: Box<dyn Write> = match &args.out_file {
+        Some(path) => Box::new(BufWriter::new(File::create(path)?)),
+        None => Box::new(io::stdout()),
+    };
+    let mut reporter = Reporter::new(out, args, wordle)?;
+    let recorder = Recorder::open(args)?;
+    let record = |game: &GameResult| match &recorder {
+        Some(recorder) => recorder.record(game),
+        None => Ok(()),
+    };
+
+    let stats = if args.adversarial {
+        // The host only settles on an answer at the end, so there is a single game to play.
+        let mut stats = Stats::new();
+        let start = Instant::now();
+        match wordle.play_adversarial(answers, (mk)()) {
+            Ok(transcript) => {
+                let game = GameResult {
+                    transcript,
+                    elapsed: start.elapsed(),
+                };
+                reporter.report(&game)?;
+                record(&game)?;
+                stats.record(game.transcript.score);
+            }
+            Err(e) => {
+                eprintln!("{}", e);
+                stats.record(None);
+            }
+        }
+        stats
+    } else {
+        let answers = &answers[..answers.len().min(args.max_games.unwrap_or(usize::MAX))];
+        let mut written = Ok(());
+        let stats = Batch::new(wordle)
+            .with_progress(args.progress)
+            .with_jobs(args.jobs as usize)
+            .run(answers, mk, |answer, result| match result {
+                Ok(game) if written.is_ok() => {
+                    written = reporter.report(&game).and_then(|()| record(&game));
+                }
+                Ok(_) => {}
+                Err(e) => eprintln!("{}: {}", answer, e),
+            });
+        written?;
+        stats
+    };
+
+    reporter.finish(&stats)
+}
+
+/// Writes the outcome of every game in the format selected with `--output`.
+enum Reporter {
+    Text {
+        out: Box<dyn Write>,
+        verbose: bool,
+        show: bool,
+        worst: Option<Worst>,
+    },
+    Json(Box<dyn Write>),
+    Csv(CsvWriter<Box<dyn Write>>),
+    Turns {
+        csv: TurnsCsvWriter<Box<dyn Write>>,
+        algorithm: String,
+    },
+}
+
+impl Reporter {
+    fn new(out: Box<dyn Write>, args: &PlayArgs, wordle: &Wordle) -> io::Result<Self> {
+        Ok(match args.output {
+            Output::Text => Reporter::Text {
+                out,
+                verbose: args.verbose,
+                show: args.show,
+                worst: args.worst.map(Worst::new),
+            },
+            Output::Json => Reporter::Json(out),
+            Output::Csv => Reporter::Csv(CsvWriter::new(out)?),
+            Output::Turns => Reporter::Turns {
+                csv: TurnsCsvWriter::new(out, wordle.answers())?,
+                algorithm: args.guesser.implementation.clone(),
+            },
+        })
+    }
+
+    fn report(&mut self, game: &GameResult) -> io::Result<()> {
+        let transcript = &game.transcript;
+        match self {
+            Reporter::Text {
+                out,
+                verbose,
+                show,
+                worst,
+            } => {
+                if *verbose {
+                    match transcript.score {
+                        Some(score) => {
+                            writeln!(out, "{}: guessed in {}", transcript.answer, score)?
+                        }
+                        None => writeln!(out, "{}: not found", transcript.answer)?,
+                    }
+                }
+                if *show {
+                    writeln!(out, "{}", render::ansi(&transcript.guesses))?;
+                }
+                if let Some(worst) = worst {
+                    worst.record(transcript);
+                }
+                Ok(())
+            }
+            Reporter::Json(out) => writeln!(
+                out,
+                "{}",
+                serde_json::to_string(game).expect("game results are always serializable")
+            ),
+            Reporter::Csv(csv) => csv.write(game),
+            Reporter::Turns { csv, algorithm } => csv.write(algorithm, transcript),
+        }
+    }
+
+    /// Writes the statistics over all games for humans, and flushes the output.
+    fn finish(self, stats: &Stats) -> io::Result<()> {
+        let mut out = match self {
+            Reporter::Text { mut out, worst, .. } => {
+                write!(out, "{}", stats)?;
+                if let Some(worst) = worst {
+                    write!(out, "\n{}", worst)?;
+                }
+                out
+            }
+            Reporter::Json(out) => out,
+            Reporter::Csv(csv) => csv.into_inner(),
+            Reporter::Turns { csv, .. } => csv.into_inner(),
+        };
+        out.flush()
+    }
+}
+
+/// Records every game in the database given with `--record`.
+#[cfg(feature = "db")]
+struct Recorder {
+    db: crane::db::Database,
+    algorithm: String,
+}
+
+/// Without the `db` feature, there is nowhere to record games.
+#[cfg(not(feature = "db"))]
+enum Recorder {}
+
+impl Recorder {
+    /// Opens the database given with `--record`, if any.
+    fn open(args: &PlayArgs) -> io::Result<Option<Self>> {
+        let Some(path) = &args.record else {
+            return Ok(None);
+        };
+
+        #[cfg(feature = "db")]
+        {
+            let mut db = crane::db::Database::open(path)?;
+            if let Some(label) = &args.label {
+                db = db.with_version(label.clone());
+            }
+            Ok(Some(Recorder {
+                db,
+                algorithm: args.guesser.implementation.clone(),
+            }))
+        }
+        #[cfg(not(feature = "db"))]
+        {
+            let _ = path;
+            Err(io::Error::new(
+                io::ErrorKind::Unsupported,
+                "recording games requires the `db` feature",
+            ))
+        }
+    }
+
+    fn record(&self, game: &GameResult) -> io::Result<()> {
+        #[cfg(feature = "db")]
+        {
+            self.db.record(&self.algorithm, game)
+        }
+        #[cfg(not(feature = "db"))]
+        {
+            let _ = game;
+            match *self {}
+        }
+    }
+}
+
+/// Most candidates listed after every guess of an interactive session.
+const LISTED_CANDIDATES: usize = 10;
+
+/// Tells how many candidates are left, and which ones if there are few enough to read through.
+fn print_remaining(remaining: &[&str]) {
+    match remaining.len() {
+        1 => println!("1 candidate left: {}", remaining[0]),
+        n if n <= LISTED_CANDIDATES => println!("{} candidates left: {}", n, remaining.join(", ")),
+        n => println!("{} candidates left", n),
+    }
+}
+
+/// Runs an interactive session where the user reports the outcome of every guess they play, and
+/// the guesser suggests the next one.
+fn solve<G: Guesser>(guesser: G, wordle: Wordle, args: &SolveArgs) -> io::Result<()> {
+    println!("After each guess, enter the word you played followed by the colors the game showed,");
+    println!("using G for green, Y for yellow and X for gray (e.g. `crane GYXXX`).");
+    println!("Enter only the colors if you played the suggested word.");
+
+    let stdin = io::stdin();
+    let mut lines = stdin.lock().lines();
+    let mut assistant = Assistant::new(guesser)
+        .with_wordle(wordle)
+        .with_explanations(args.explain);
+    loop {
+        let first = assistant.history().is_empty();
+        if args.keyboard && !first {
+            let keyboard = Keyboard::from_history(assistant.history());
+            print!("{}", render::ansi_keyboard(&keyboard));
+        }
+        let suggestion = assistant.suggestion();
+        if let (false, Some(remaining)) = (first, &suggestion.remaining) {
+            print_remaining(remaining);
+        }
+        println!("suggestion: {}", suggestion.guess);
+        if let Some(estimate) = &suggestion.estimate {
+            println!("{}", estimate);
+        }
+        if let Some(explanation) = &suggestion.explanation {
+            print!("{}", explanation);
+        }
+
+        loop {
+            print!("> ");
+            io::stdout().flush()?;
+
+            let line = match lines.next() {
+                Some(line) => line?,
+                None => return Ok(()),
+            };
+            match assistant.enter(&line) {
+                Ok(()) => break,
+                Err(e) => println!("{}", e),
+            }
+        }
+
+        if assistant.is_solved() {
+            println!("solved in {}!", assistant.history().len());
+            return Ok(());
+        }
     }
 }
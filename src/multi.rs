@@ -0,0 +1,643 @@
+//! Variants such as Quordle and Octordle, where several boards are solved at once and every guess
+//! is played on all the boards that are not solved yet.
+
+use std::borrow::Cow;
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+
+use crate::algorithms::{best_by, Candidates, PatternCache};
+use crate::{
+    builtin_dictionary, CancelToken, Correctness, Dictionary, Guess, Guesser, History, PlayError,
+    Wordle,
+};
+
+/// A game of Wordle played on several boards at once, each with its own answer.
+pub struct MultiWordle<const N: usize = 5> {
+    wordle: Wordle<N>,
+}
+
+impl MultiWordle {
+    pub fn new() -> Self {
+        Self::with_dictionary(builtin_dictionary())
+    }
+}
+
+impl<const N: usize> MultiWordle<N> {
+    /// Creates a game whose valid guesses are the words of the given dictionary. Every word must
+    /// be `N` letters long.
+    pub fn with_dictionary(words: impl IntoIterator<Item = (&'static str, usize)>) -> Self {
+        Self {
+            wordle: Wordle::with_dictionary(words),
+        }
+    }
+
+    /// Sets how many guesses the guesser gets to solve every board, e.g. 9 for Quordle or 13 for
+    /// Octordle, see [`Wordle::with_max_guesses`].
+    pub fn with_max_guesses(mut self, max_guesses: Option<usize>) -> Self {
+        self.wordle = self.wordle.with_max_guesses(max_guesses);
+        self
+    }
+
+    /// Enables or disables hard mode, where every guess must use all the hints revealed so far on
+    /// every board that is not solved yet.
+    pub fn with_hard_mode(mut self, hard_mode: bool) -> Self {
+        self.wordle = self.wordle.with_hard_mode(hard_mode);
+        self
+    }
+
+    /// Plays a game with one board per answer, until every board is solved or the guesser runs
+    /// out of guesses.
+    ///
+    /// Fails if an answer is not `N` letters long, or if the guesser plays an invalid word.
+    pub fn play<G: MultiGuesser<N>>(
+        &self,
+        answers: &[&str],
+        mut guesser: G,
+    ) -> Result<MultiTranscript<N>, PlayError> {
+        if let Some(answer) = answers.iter().find(|answer| answer.chars().count() != N) {
+            return Err(PlayError::InvalidAnswer(answer.to_string()));
+        }
+
+        guesser.set_hard_mode(self.wordle.hard_mode);
+
+        let mut boards: Vec<History<N>> = vec![History::new(); answers.len()];
+        let mut scores = vec![None; answers.len()];
+        let mut turn = 0;
+        while !scores.iter().all(Option::is_some) && self.wordle.has_guesses_left(turn) {
+            turn += 1;
+            let mut guess = guesser.guess(&boards);
+            for (history, score) in boards.iter().zip(&scores) {
+                if score.is_none() {
+                    guess = self.wordle.check(guess, history)?;
+                }
+            }
+            for ((answer, history), score) in answers.iter().zip(&mut boards).zip(&mut scores) {
+                if score.is_some() {
+                    continue;
+                }
+
+                history.push(Guess::new(
+                    guess.clone(),
+                    Correctness::compute(answer, &guess),
+                ));
+                if guess == *answer {
+                    *score = Some(turn);
+                }
+            }
+        }
+
+        Ok(MultiTranscript {
+            answers: answers.iter().map(|answer| answer.to_string()).collect(),
+            boards: boards.into_iter().map(History::into_guesses).collect(),
+            scores,
+        })
+    }
+}
+
+impl Default for MultiWordle {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Record of a whole game played on several boards.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MultiTranscript<const N: usize = 5> {
+    pub answers: Vec<String>,
+    /// The guesses played on every board, up to and including the one that solved it.
+    pub boards: Vec<Vec<Guess<N>>>,
+    /// Number of guesses it took to solve every board, if it was solved.
+    pub scores: Vec<Option<usize>>,
+}
+
+impl<const N: usize> MultiTranscript<N> {
+    /// Number of guesses it took to solve every board, or `None` if one of them was not solved.
+    pub fn score(&self) -> Option<usize> {
+        self.scores
+            .iter()
+            .try_fold(0, |total, &score| Some(total.max(score?)))
+    }
+}
+
+/// A strategy for solving several boards at once with words of `N` letters.
+pub trait MultiGuesser<const N: usize = 5> {
+    /// Returns the next word to play given the previous guesses on every board.
+    ///
+    /// Boards that are already solved are passed along too, and end with the guess that solved
+    /// them (see [`is_solved`]). They are not played on anymore.
+    fn guess(&mut self, boards: &[History<N>]) -> Cow<'static, str>;
+
+    /// Tells the guesser whether the upcoming game is played in hard mode, in which case every
+    /// guess must be consistent with the green and yellow letters revealed so far on every board
+    /// that is not solved yet, see [`hard_mode_allows`].
+    fn set_hard_mode(&mut self, _hard_mode: bool) {}
+}
+
+impl<G: MultiGuesser<N> + ?Sized, const N: usize> MultiGuesser<N> for &mut G {
+    fn guess(&mut self, boards: &[History<N>]) -> Cow<'static, str> {
+        (**self).guess(boards)
+    }
+
+    fn set_hard_mode(&mut self, hard_mode: bool) {
+        (**self).set_hard_mode(hard_mode)
+    }
+}
+
+/// Returns whether the board with the given history is solved.
+pub fn is_solved<const N: usize>(history: &[Guess<N>]) -> bool {
+    history
+        .last()
+        .is_some_and(|guess| guess.mask == [Correctness::Correct; N])
+}
+
+/// Returns whether `word` may be played in hard mode, i.e. whether it uses all the hints revealed
+/// so far on every board that is not solved yet.
+///
+/// The last guess always may, since every guess before it had to use the hints it built on.
+pub fn hard_mode_allows<const N: usize>(boards: &[History<N>], word: &str) -> bool {
+    boards
+        .iter()
+        .filter(|history| !is_solved(history))
+        .all(|history| history.constraints().hard_mode_allows(word))
+}
+
+/// Returns the last guess played on the boards that are not solved yet, which hard mode always
+/// allows, see [`hard_mode_allows`].
+fn last_guess<const N: usize>(boards: &[History<N>]) -> Cow<'static, str> {
+    boards
+        .iter()
+        .find(|history| !is_solved(history))
+        .and_then(|history| history.last())
+        .expect("hard mode only rules out words once a guess was played")
+        .word
+        .clone()
+}
+
+/// Solves the boards one after the other, with a separate [`Guesser`] for every board.
+///
+/// Every guesser still sees each guess played on its board, so the next board usually only has a
+/// handful of candidates left by the time its turn comes.
+///
+/// In hard mode, a guesser only follows the hints of its own board, so the first suggestion every
+/// board allows is played, or else the first word of the dictionary that every board allows and was
+/// not played yet, preferably one that could be the answer of a board, or else the last guess
+/// again.
+#[derive(Clone)]
+pub struct Sequential<G> {
+    prototype: G,
+    guessers: Vec<G>,
+    hard_mode: bool,
+    dictionary: Arc<Dictionary>,
+}
+
+impl<G: Clone> Sequential<G> {
+    /// Creates a guesser that clones `prototype` for every board, and falls back on the words of
+    /// the built-in dictionary in hard mode.
+    pub fn new(prototype: G) -> Self {
+        Self {
+            prototype,
+            guessers: Vec::new(),
+            hard_mode: false,
+            dictionary: Dictionary::builtin(),
+        }
+    }
+
+    /// Falls back on the words of `dictionary` in hard mode, which should be the dictionary of the
+    /// game.
+    pub fn with_dictionary(mut self, dictionary: Arc<Dictionary>) -> Self {
+        self.dictionary = dictionary;
+        self
+    }
+}
+
+impl<G: Guesser<N> + Clone, const N: usize> MultiGuesser<N> for Sequential<G> {
+    fn guess(&mut self, boards: &[History<N>]) -> Cow<'static, str> {
+        let prototype = &self.prototype;
+        self.guessers
+            .resize_with(boards.len(), || prototype.clone());
+
+        // Every guesser is asked on every turn until its board is solved, even if its suggestion
+        // is not played, since guessers narrow down their candidates one guess at a time.
+        let suggestions: Vec<_> = boards
+            .iter()
+            .zip(&mut self.guessers)
+            .filter(|(history, _)| !is_solved(history))
+            .map(|(history, guesser)| guesser.guess(history))
+            .collect();
+        if !self.hard_mode {
+            return suggestions
+                .into_iter()
+                .next()
+                .expect("the game is over once every board is solved");
+        }
+
+        if let Some(suggestion) = suggestions
+            .into_iter()
+            .find(|suggestion| hard_mode_allows(boards, suggestion))
+        {
+            return suggestion;
+        }
+        let words = self.dictionary.words();
+        let played = |word: &str| {
+            boards
+                .iter()
+                .any(|history| history.iter().any(|guess| guess.word == word))
+        };
+        let allowed = |word: &&str| !played(word) && hard_mode_allows(boards, word);
+        let word = words
+            .iter()
+            .copied()
+            .filter(allowed)
+            .find(|word| boards.iter().any(|history| history.matches(word)))
+            .or_else(|| words.iter().copied().find(allowed));
+        match word {
+            Some(word) => Cow::Borrowed(word),
+            None => {
+                tracing::warn!("no word of the dictionary is allowed on every board");
+                last_guess(boards)
+            }
+        }
+    }
+
+    fn set_hard_mode(&mut self, hard_mode: bool) {
+        self.hard_mode = hard_mode;
+        self.prototype.set_hard_mode(hard_mode);
+        for guesser in &mut self.guessers {
+            guesser.set_hard_mode(hard_mode);
+        }
+    }
+}
+
+/// Solves the two boards of Dordle together, playing the word expected to reveal the most
+/// information about both answers at once rather than about one board at a time.
+///
+/// The two answers are different words, so a guess is scored on how it partitions the pairs of
+/// distinct candidates of both boards by the pair of patterns it reveals.
+#[derive(Clone)]
+pub struct Dordle {
+    boards: [Candidates; 2],
+    /// Number of guesses of the history of every board its candidates were narrowed down with.
+    seen: [usize; 2],
+    hard_mode: bool,
+    cancel: CancelToken,
+    cache: Arc<PatternCache>,
+}
+
+impl Dordle {
+    /// Creates a guesser that picks its words from the built-in dictionary.
+    pub fn new() -> Self {
+        Self::with_cache(PatternCache::global())
+    }
+
+    /// Creates a guesser that picks its words from the given dictionary, with a new
+    /// [`PatternCache`] for it.
+    pub fn with_dictionary(words: impl IntoIterator<Item = (&'static str, usize)>) -> Self {
+        Self::with_cache(Arc::new(PatternCache::new(
+            words.into_iter().map(|(word, _)| word),
+        )))
+    }
+
+    /// Creates a guesser that picks its words from the words of `cache`.
+    pub fn with_cache(cache: Arc<PatternCache>) -> Self {
+        Dordle {
+            boards: [Candidates::all(cache.len()), Candidates::all(cache.len())],
+            seen: [0; 2],
+            hard_mode: false,
+            cancel: CancelToken::new(),
+            cache,
+        }
+    }
+
+    /// Only considers the given words as possible answers on both boards, see
+    /// [`Candidates::retain_words`].
+    pub fn with_answers<'a>(mut self, answers: impl IntoIterator<Item = &'a str> + Clone) -> Self {
+        for board in &mut self.boards {
+            board.retain_words(&self.cache, answers.clone());
+        }
+        self
+    }
+
+    /// Bounds how long the guesser searches, see [`Guesser::set_cancel_token`].
+    pub fn set_cancel_token(&mut self, cancel: CancelToken) {
+        self.cancel = cancel;
+    }
+}
+
+impl Default for Dordle {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MultiGuesser for Dordle {
+    /// # Panics
+    ///
+    /// Panics if there are not exactly two boards.
+    fn guess(&mut self, boards: &[History]) -> Cow<'static, str> {
+        assert_eq!(boards.len(), 2, "Dordle is played on two boards");
+        let cache = &self.cache;
+        for ((candidates, history), seen) in self.boards.iter_mut().zip(boards).zip(&mut self.seen)
+        {
+            candidates.catch_up(cache, history, seen);
+        }
+
+        // A solved board has a single outcome left, which reveals nothing.
+        let open: Vec<Option<&Candidates>> = self
+            .boards
+            .iter()
+            .zip(boards)
+            .map(|(candidates, history)| (!is_solved(history)).then_some(candidates))
+            .collect();
+        let [a, b] = [open[0], open[1]].map(|board| {
+            board.map_or_else(Vec::new, |candidates| candidates.iter().collect::<Vec<_>>())
+        });
+        let both: Vec<usize> = match open[1] {
+            Some(second) => a
+                .iter()
+                .copied()
+                .filter(|&index| second.contains(index))
+                .collect(),
+            None => Vec::new(),
+        };
+
+        let guesses: Vec<usize> = (0..cache.len())
+            .filter(|&guess| !self.hard_mode || hard_mode_allows(boards, cache.word(guess)))
+            .collect();
+        if guesses.is_empty() {
+            tracing::warn!("no word of the dictionary is allowed on every board");
+            return last_guess(boards);
+        }
+        let (best, (information, _)) = best_by(&guesses, &self.cancel, |guess| {
+            let patterns = cache.row(guess);
+            let histogram = |candidates: &[usize]| {
+                let mut counts = [0usize; Correctness::PATTERNS];
+                for &candidate in candidates {
+                    counts[patterns[candidate] as usize] += 1;
+                }
+                counts
+            };
+            let information = match (a.is_empty(), b.is_empty()) {
+                (false, false) => joint_entropy(&histogram(&a), &histogram(&b), &histogram(&both)),
+                (false, true) => entropy(&histogram(&a)),
+                (true, _) => entropy(&histogram(&b)),
+            };
+            let possible = open
+                .iter()
+                .flatten()
+                .any(|candidates| candidates.contains(guess));
+            (information, possible)
+        })
+        .expect("the dictionary is not empty");
+        tracing::debug!(
+            guess = cache.word(best),
+            information,
+            candidates = ?[a.len(), b.len()],
+            "picked the guess revealing the most information about both boards"
+        );
+
+        Cow::Borrowed(cache.word(best))
+    }
+
+    fn set_hard_mode(&mut self, hard_mode: bool) {
+        self.hard_mode = hard_mode;
+    }
+}
+
+/// Returns the entropy in bits of the distribution of patterns counted in `counts`.
+fn entropy(counts: &[usize; Correctness::PATTERNS]) -> f64 {
+    let total: usize = counts.iter().sum();
+    log2(total as f64) - counts.iter().map(|&n| n_log2_n(n as f64)).sum::<f64>() / total as f64
+}
+
+/// Returns the entropy in bits of the distribution of the pairs of patterns revealed on two
+/// boards whose answers are different, given the patterns counted on the candidates of the first
+/// board in `a`, on those of the second board in `b`, and on the candidates of both in `both`.
+///
+/// There are `a[p] * b[q]` pairs of candidates revealing patterns `p` and `q`, except when the
+/// same word would be the answer of both boards, which only happens with `p == q`. The terms of
+/// the sum over all pairs of patterns factor into sums over the patterns of every board, so the
+/// joint partition is never built.
+fn joint_entropy(
+    a: &[usize; Correctness::PATTERNS],
+    b: &[usize; Correctness::PATTERNS],
+    both: &[usize; Correctness::PATTERNS],
+) -> f64 {
+    let sum = |counts: &[usize; Correctness::PATTERNS]| counts.iter().sum::<usize>() as f64;
+    let sum_n_log2_n = |counts: &[usize; Correctness::PATTERNS]| {
+        counts.iter().map(|&n| n_log2_n(n as f64)).sum::<f64>()
+    };
+    let (total_a, total_b) = (sum(a), sum(b));
+    let total = total_a * total_b - sum(both);
+
+    // The sum of n * log2(n) over every pair of patterns if the answers could be the same word,
+    // then corrected on the diagonal.
+    let mut pairs = total_b * sum_n_log2_n(a) + total_a * sum_n_log2_n(b);
+    for p in 0..Correctness::PATTERNS {
+        let independent = (a[p] * b[p]) as f64;
+        pairs += n_log2_n(independent - both[p] as f64) - n_log2_n(independent);
+    }
+    log2(total) - pairs / total
+}
+
+fn log2(n: f64) -> f64 {
+    if n > 0.0 {
+        n.log2()
+    } else {
+        0.0
+    }
+}
+
+fn n_log2_n(n: f64) -> f64 {
+    n * log2(n)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        is_solved, joint_entropy, Dordle, MultiGuesser, MultiTranscript, MultiWordle, Sequential,
+    };
+    use crate::algorithms::Naive;
+    use crate::{Correctness, Guess, History, PlayError};
+
+    #[test]
+    fn plays_every_unsolved_board() {
+        struct G;
+        impl MultiGuesser for G {
+            fn guess(&mut self, boards: &[History]) -> std::borrow::Cow<'static, str> {
+                if is_solved(&boards[0]) {
+                    "right".into()
+                } else {
+                    "wrong".into()
+                }
+            }
+        }
+
+        let w = MultiWordle::new();
+        let transcript = w.play(&["wrong", "right"], G).unwrap();
+        assert_eq!(transcript.scores, vec![Some(1), Some(2)]);
+        assert_eq!(transcript.score(), Some(2));
+        assert_eq!(
+            transcript.boards,
+            vec![
+                vec![Guess::new("wrong", mask!(C C C C C))],
+                vec![
+                    Guess::new("wrong", mask!(W M W W M)),
+                    Guess::new("right", mask!(C C C C C)),
+                ],
+            ]
+        );
+    }
+
+    #[test]
+    fn max_guesses() {
+        struct G;
+        impl MultiGuesser for G {
+            fn guess(&mut self, _boards: &[History]) -> std::borrow::Cow<'static, str> {
+                "wrong".into()
+            }
+        }
+
+        let w = MultiWordle::new().with_max_guesses(Some(3));
+        let transcript = w.play(&["wrong", "right"], G).unwrap();
+        assert_eq!(transcript.scores, vec![Some(1), None]);
+        assert_eq!(transcript.boards[1].len(), 3);
+    }
+
+    #[test]
+    fn hard_mode() {
+        struct G;
+        impl MultiGuesser for G {
+            fn guess(&mut self, boards: &[History]) -> std::borrow::Cow<'static, str> {
+                match boards[0].len() {
+                    0 => "right",
+                    _ => "wrong",
+                }
+                .into()
+            }
+        }
+
+        let w = MultiWordle::new().with_hard_mode(true);
+        assert_eq!(
+            w.play(&["fight", "wrong"], G),
+            Err(PlayError::HardModeViolation("wrong".to_string()))
+        );
+
+        // Only the boards that are not solved yet constrain the next guesses.
+        let transcript = w.play(&["right", "wrong"], G).unwrap();
+        assert_eq!(transcript.scores, vec![Some(1), Some(2)]);
+    }
+
+    #[test]
+    fn sequential_hard_mode() {
+        let w = MultiWordle::new().with_hard_mode(true);
+        let guesser = Sequential::new(Naive::new());
+        // The hints of both boards may leave no word to play but the last one, so these are not
+        // all won, but none of them breaks the rules.
+        for answers in [["chirt", "cigar"], ["cigar", "admen"], ["admen", "chirt"]] {
+            assert!(w.play(&answers, guesser.clone()).is_ok(), "{:?}", answers);
+        }
+        let transcript = w.play(&["humph", "blush"], guesser).unwrap();
+        assert!(transcript.score().is_some());
+    }
+
+    #[test]
+    fn unsolved() {
+        let transcript = MultiTranscript::<5> {
+            answers: vec!["wrong".to_string(), "right".to_string()],
+            boards: vec![Vec::new(), Vec::new()],
+            scores: vec![Some(3), None],
+        };
+        assert_eq!(transcript.score(), None);
+    }
+
+    #[test]
+    fn invalid_answer() {
+        let w = MultiWordle::new();
+        let guesser = Sequential::new(Naive::new());
+        assert!(matches!(
+            w.play(&["right", "rights"], guesser),
+            Err(PlayError::InvalidAnswer(answer)) if answer == "rights"
+        ));
+    }
+
+    #[test]
+    fn sequential() {
+        let words = ["cigar", "rebut", "sissy", "humph", "awake", "blush"];
+        let w: MultiWordle = MultiWordle::with_dictionary(words.iter().map(|&word| (word, 1)));
+        let guesser = Sequential::new(Naive::with_dictionary(words.iter().map(|&word| (word, 1))));
+        let transcript = w
+            .play(&["humph", "cigar", "blush", "rebut"], guesser)
+            .unwrap();
+        assert!(transcript.score().is_some());
+    }
+
+    const WORDS: [&str; 8] = [
+        "cigar", "rebut", "sissy", "humph", "awake", "blush", "focal", "evade",
+    ];
+
+    #[test]
+    fn joint_entropy_of_distinct_answers() {
+        let guess = "rebut";
+        let pattern =
+            |answer: &str| Correctness::pack(Correctness::compute(answer, guess)) as usize;
+        let (a, b) = (&WORDS[..6], &WORDS[2..]);
+
+        // Partition every pair of different answers by the patterns the guess reveals.
+        let mut pairs = std::collections::HashMap::new();
+        for x in a {
+            for y in b.iter().filter(|&y| y != x) {
+                *pairs.entry((pattern(x), pattern(y))).or_insert(0.0) += 1.0;
+            }
+        }
+        let total: f64 = pairs.values().sum();
+        let expected: f64 = -pairs
+            .values()
+            .map(|n| n / total * (n / total).log2())
+            .sum::<f64>();
+
+        let histogram = |words: &[&str]| {
+            let mut counts = [0; Correctness::PATTERNS];
+            for word in words {
+                counts[pattern(word)] += 1;
+            }
+            counts
+        };
+        let entropy = joint_entropy(&histogram(a), &histogram(b), &histogram(&WORDS[2..6]));
+        assert!(
+            (entropy - expected).abs() < 1e-9,
+            "{} != {}",
+            entropy,
+            expected
+        );
+    }
+
+    #[test]
+    fn dordle() {
+        let w = MultiWordle::with_dictionary(WORDS.iter().map(|&word| (word, 1)));
+        let guesser = Dordle::with_dictionary(WORDS.iter().map(|&word| (word, 1)));
+        for first in WORDS {
+            for second in WORDS.iter().filter(|&&second| second != first) {
+                let transcript = w.play(&[first, second], guesser.clone()).unwrap();
+                assert!(transcript.score().is_some(), "{} {}", first, second);
+            }
+        }
+    }
+
+    #[test]
+    fn dordle_hard_mode() {
+        let w =
+            MultiWordle::with_dictionary(WORDS.iter().map(|&word| (word, 1))).with_hard_mode(true);
+        let guesser = Dordle::with_dictionary(WORDS.iter().map(|&word| (word, 1)));
+        for first in WORDS {
+            for second in WORDS.iter().filter(|&&second| second != first) {
+                assert!(
+                    w.play(&[first, second], guesser.clone()).is_ok(),
+                    "{} {}",
+                    first,
+                    second
+                );
+            }
+        }
+    }
+}
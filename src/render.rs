@@ -0,0 +1,158 @@
+//! Drawing games the way the official game shows them, either as the emoji grid people share or
+//! as colored letters in a terminal.
+
+use std::fmt::Write;
+
+use crate::keyboard::{KeyState, Keyboard, LAYOUT};
+use crate::{Correctness, Guess, Transcript, OFFICIAL_MAX_GUESSES};
+
+/// Returns one row of colored squares per guess, without the letters, like the grid the official
+/// game lets players share.
+pub fn emoji_grid<const N: usize>(guesses: &[Guess<N>]) -> String {
+    let mut grid = String::new();
+    for guess in guesses {
+        grid.extend(guess.mask.iter().map(|&c| emoji(c)));
+        grid.push('\n');
+    }
+    grid
+}
+
+/// Returns the text the official game shares for a finished game: the score out of
+/// [`OFFICIAL_MAX_GUESSES`], or `X` if the game was lost, followed by the [`emoji_grid`].
+pub fn share<const N: usize>(transcript: &Transcript<N>) -> String {
+    let score = match transcript.score {
+        Some(score) if transcript.won() => score.to_string(),
+        _ => "X".to_string(),
+    };
+    format!(
+        "{}/{}\n\n{}",
+        score,
+        OFFICIAL_MAX_GUESSES,
+        emoji_grid(&transcript.guesses)
+    )
+}
+
+/// Returns one row per guess with every letter in uppercase on its color, using ANSI escape
+/// codes, for printing to a terminal.
+pub fn ansi<const N: usize>(guesses: &[Guess<N>]) -> String {
+    let mut board = String::new();
+    for guess in guesses {
+        for (letter, &c) in guess.word.chars().zip(&guess.mask) {
+            let background = match c {
+                Correctness::Correct => 42,
+                Correctness::Misplaced => 43,
+                Correctness::Wrong => 100,
+            };
+            let _ = write!(
+                board,
+                "\x1b[1;97;{}m {} \x1b[0m",
+                background,
+                letter.to_uppercase()
+            );
+        }
+        board.push('\n');
+    }
+    board
+}
+
+/// Returns the rows of the keyboard with every key colored by its state, using ANSI escape codes,
+/// for printing to a terminal. Guessed letters that are not on the keyboard get a row of their
+/// own.
+pub fn ansi_keyboard(keyboard: &Keyboard) -> String {
+    let mut rows: Vec<String> = LAYOUT.iter().map(|row| row.to_string()).collect();
+    let others: String = keyboard
+        .keys()
+        .map(|(letter, _)| letter)
+        .filter(|&letter| !LAYOUT.iter().any(|row| row.contains(letter)))
+        .collect();
+    if !others.is_empty() {
+        rows.push(others);
+    }
+
+    let width = LAYOUT[0].len();
+    let mut board = String::new();
+    for row in rows {
+        // Every key is three columns wide, and the shorter rows are centered.
+        let indent = 3 * width.saturating_sub(row.chars().count()) / 2;
+        board.extend(std::iter::repeat_n(' ', indent));
+        for letter in row.chars() {
+            let colors = match keyboard.state(letter) {
+                KeyState::Unused => "30;47",
+                KeyState::Absent => "97;100",
+                KeyState::Present => "97;43",
+                KeyState::Correct => "97;42",
+            };
+            let _ = write!(
+                board,
+                "\x1b[1;{}m {} \x1b[0m",
+                colors,
+                letter.to_uppercase()
+            );
+        }
+        board.push('\n');
+    }
+    board
+}
+
+fn emoji(c: Correctness) -> char {
+    match c {
+        Correctness::Correct => '🟩',
+        Correctness::Misplaced => '🟨',
+        Correctness::Wrong => '⬛',
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ansi, ansi_keyboard, emoji_grid, share};
+    use crate::keyboard::Keyboard;
+    use crate::{Guess, Transcript};
+
+    fn transcript(score: Option<usize>) -> Transcript {
+        Transcript {
+            answer: "right".to_string(),
+            guesses: vec![
+                Guess::new("wrong", mask!(W M W W M)),
+                Guess::new("right", mask!(C C C C C)),
+            ],
+            score,
+        }
+    }
+
+    #[test]
+    fn grid() {
+        assert_eq!(
+            emoji_grid(&transcript(Some(2)).guesses),
+            "⬛🟨⬛⬛🟨\n🟩🟩🟩🟩🟩\n"
+        );
+    }
+
+    #[test]
+    fn share_text() {
+        assert_eq!(
+            share(&transcript(Some(2))),
+            "2/6\n\n⬛🟨⬛⬛🟨\n🟩🟩🟩🟩🟩\n"
+        );
+        assert!(share(&transcript(Some(9))).starts_with("X/6\n"));
+        assert!(share(&transcript(None)).starts_with("X/6\n"));
+    }
+
+    #[test]
+    fn ansi_colors() {
+        let board = ansi(&[Guess::new("ñandu", mask!(C M W W W))]);
+        assert!(board.starts_with("\x1b[1;97;42m Ñ \x1b[0m\x1b[1;97;43m A \x1b[0m"));
+        assert!(board.ends_with("\x1b[1;97;100m U \x1b[0m\n"));
+    }
+
+    #[test]
+    fn ansi_keys() {
+        let keyboard = Keyboard::from_history(&[Guess::new("ñandu", mask!(C M W W W))]);
+        let board = ansi_keyboard(&keyboard);
+        let rows: Vec<&str> = board.lines().collect();
+        assert_eq!(rows.len(), 4);
+        assert!(rows[0].starts_with("\x1b[1;30;47m Q \x1b[0m"));
+        assert!(rows[1].starts_with(" \x1b[1;97;43m A \x1b[0m"));
+        assert!(rows[2].contains("\x1b[1;97;100m N \x1b[0m"));
+        assert_eq!(rows[3], "             \x1b[1;97;42m Ñ \x1b[0m");
+    }
+}
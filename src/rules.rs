@@ -0,0 +1,765 @@
+//! The rules of the game: the mask a guess reveals against an answer, packed masks, and whether
+//! a word still matches the guesses played so far.
+//!
+//! Along with [`constraints`](crate::constraints) and [`word`](crate::word), this only needs
+//! `alloc`, so it builds without the `std` feature for targets without the standard library.
+
+use alloc::borrow::Cow;
+use alloc::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::constraints::{Constraints, LetterCount};
+use crate::Word;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum Correctness {
+    /// Green
+    Correct,
+    /// Yellow
+    Misplaced,
+    /// Gray
+    Wrong,
+}
+
+impl Correctness {
+    /// Returns the mask the game reveals when `guess` is played against `answer`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either word is not `N` letters long, see [`Correctness::try_compute`] for words
+    /// that were not checked.
+    pub fn compute<const N: usize>(answer: &str, guess: &str) -> [Self; N] {
+        Self::try_compute(answer, guess)
+            .unwrap_or_else(|| panic!("`{}` and `{}` are not {} letters long", answer, guess, N))
+    }
+
+    /// Like [`Correctness::compute`], but returns `None` if either word is not `N` letters long.
+    pub fn try_compute<const N: usize>(answer: &str, guess: &str) -> Option<[Self; N]> {
+        if answer.chars().count() != N || guess.chars().count() != N {
+            return None;
+        }
+
+        Some(match (Word::new(answer), Word::new(guess)) {
+            (Some(answer), Some(guess)) => answer.mask(&guess),
+            _ => Self::compute_chars(answer, guess),
+        })
+    }
+
+    /// Computes the mask revealed by `guess` if the answer is `answer`, for words made of
+    /// lowercase ASCII letters only.
+    ///
+    /// This only does a couple of passes over the letters and a lookup in a table of letter
+    /// counts, so it is much faster than going through strings. Algorithms that compare many words
+    /// should convert them to byte arrays once and call this in their hot loops.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a byte is not a lowercase ASCII letter.
+    pub fn compute_bytes<const N: usize>(answer: &[u8; N], guess: &[u8; N]) -> [Self; N] {
+        let mut c = [Correctness::Wrong; N];
+        // How many times every letter of the answer appears outside of the green positions.
+        let mut unmatched = [0u8; 26];
+        for i in 0..N {
+            if answer[i] == guess[i] {
+                c[i] = Correctness::Correct;
+            } else {
+                unmatched[answer[i].wrapping_sub(b'a') as usize] += 1;
+            }
+        }
+
+        // Mark letters yellow, as long as the answer has copies of them left.
+        for i in 0..N {
+            if c[i] == Correctness::Correct {
+                continue;
+            }
+
+            let count = &mut unmatched[guess[i].wrapping_sub(b'a') as usize];
+            if *count > 0 {
+                *count -= 1;
+                c[i] = Correctness::Misplaced;
+            }
+        }
+
+        c
+    }
+
+    /// Like [`Correctness::compute_bytes`], but for words with any letters, e.g. `ñ` or `ü`,
+    /// which take more than one byte.
+    fn compute_chars<const N: usize>(answer: &str, guess: &str) -> [Self; N] {
+        let mut c = [Correctness::Wrong; N];
+        // Mark letters green.
+        for (i, (a, g)) in answer.chars().zip(guess.chars()).enumerate() {
+            if a == g {
+                c[i] = Correctness::Correct;
+            }
+        }
+
+        let mut used = [false; N];
+        for (i, &c) in c.iter().enumerate() {
+            if c == Correctness::Correct {
+                used[i] = true;
+            }
+        }
+
+        // Mark letters yellow.
+        for (i, g) in guess.chars().enumerate() {
+            if c[i] == Correctness::Correct {
+                continue;
+            }
+
+            if answer.chars().enumerate().any(|(i, a)| {
+                if a == g && !used[i] {
+                    used[i] = true;
+                    return true;
+                }
+
+                false
+            }) {
+                c[i] = Correctness::Misplaced;
+            }
+        }
+
+        c
+    }
+
+    /// Parses a mask written as one letter per tile, the way the game colors them: `G` for green,
+    /// `Y` for yellow and `X` for gray (case-insensitive), e.g. `GYXXX`.
+    pub fn parse_mask<const N: usize>(mask: &str) -> Option<[Self; N]> {
+        let mut c = [Correctness::Wrong; N];
+        let mut tiles = mask.chars();
+        for c in &mut c {
+            *c = match tiles.next()?.to_ascii_uppercase() {
+                'G' => Correctness::Correct,
+                'Y' => Correctness::Misplaced,
+                'X' => Correctness::Wrong,
+                _ => return None,
+            };
+        }
+
+        if tiles.next().is_some() {
+            return None;
+        }
+
+        Some(c)
+    }
+
+    /// Returns the letter for this color used by [`Correctness::parse_mask`].
+    pub fn to_char(self) -> char {
+        match self {
+            Correctness::Correct => 'G',
+            Correctness::Misplaced => 'Y',
+            Correctness::Wrong => 'X',
+        }
+    }
+
+    /// Number of distinct masks of five letters, and therefore of packed masks.
+    pub const PATTERNS: usize = 243;
+
+    /// Packs a mask into a single byte by reading it as a base-3 number.
+    ///
+    /// Since there are only 3^5 = 243 possible masks, the result always fits in a `u8`, which
+    /// allows per-pattern data to be stored in arrays indexed by the packed mask.
+    pub fn pack(mask: [Self; 5]) -> u8 {
+        mask.iter().fold(0, |packed, c| {
+            packed * 3
+                + match c {
+                    Correctness::Correct => 0,
+                    Correctness::Misplaced => 1,
+                    Correctness::Wrong => 2,
+                }
+        })
+    }
+
+    /// Reverses [`Correctness::pack`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `packed` is not smaller than [`Correctness::PATTERNS`].
+    pub fn unpack(mut packed: u8) -> [Self; 5] {
+        assert!((packed as usize) < Self::PATTERNS, "not a packed mask");
+
+        let mut c = [Correctness::Wrong; 5];
+        for c in c.iter_mut().rev() {
+            *c = match packed % 3 {
+                0 => Correctness::Correct,
+                1 => Correctness::Misplaced,
+                _ => Correctness::Wrong,
+            };
+            packed /= 3;
+        }
+
+        c
+    }
+
+    /// Iterates over every mask of five letters, in packed order, including the
+    /// [impossible](Correctness::is_possible) ones.
+    pub fn patterns() -> impl Iterator<Item = [Self; 5]> {
+        (0..Self::PATTERNS as u8).map(Self::unpack)
+    }
+
+    /// Iterates over the masks of five letters that some answer may reveal, in packed order.
+    pub fn possible_patterns() -> impl Iterator<Item = [Self; 5]> {
+        Self::patterns().filter(|&mask| Self::is_possible(mask))
+    }
+
+    /// Returns whether some guess and answer reveal `mask`.
+    ///
+    /// Only a mask with a single tile that is not green cannot be revealed when that tile is
+    /// yellow, e.g. `GGGGY`: the answer would need another copy of the letter elsewhere, but
+    /// every other letter of the answer is already matched by a green one.
+    pub fn is_possible<const N: usize>(mask: [Self; N]) -> bool {
+        let mut others = mask.iter().filter(|&&c| c != Correctness::Correct);
+        !matches!(
+            (others.next(), others.next()),
+            (Some(Correctness::Misplaced), None)
+        )
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Guess<const N: usize = 5> {
+    pub word: Cow<'static, str>,
+    /// Serialized in the format of [`Correctness::parse_mask`], e.g. `"GYXXX"`.
+    #[serde(with = "mask_string")]
+    pub mask: [Correctness; N],
+}
+
+mod mask_string {
+    use alloc::format;
+    use alloc::string::String;
+
+    use serde::de::Error;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    use crate::Correctness;
+
+    pub fn serialize<S: Serializer, const N: usize>(
+        mask: &[Correctness; N],
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        let mask: String = mask.iter().map(|c| c.to_char()).collect();
+        serializer.serialize_str(&mask)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>, const N: usize>(
+        deserializer: D,
+    ) -> Result<[Correctness; N], D::Error> {
+        let mask = String::deserialize(deserializer)?;
+        Correctness::parse_mask(&mask).ok_or_else(|| {
+            D::Error::custom(format!("`{}` is not {} colors out of G, Y and X", mask, N))
+        })
+    }
+}
+
+impl<const N: usize> Guess<N> {
+    pub fn new(word: impl Into<Cow<'static, str>>, mask: [Correctness; N]) -> Self {
+        Self {
+            word: word.into(),
+            mask,
+        }
+    }
+
+    /// Returns whether `word` could still be the answer given this guess, i.e. whether playing
+    /// this guess against `word` would have revealed the same mask.
+    ///
+    /// Words that do not have `N` letters never match, nor does any word if the guess itself does
+    /// not have `N` letters, e.g. because it was read from a file.
+    pub fn matches(&self, word: &str) -> bool {
+        Correctness::try_compute(word, &self.word) == Some(self.mask)
+    }
+
+    /// Returns how many times every letter of the guess appears in the answer, as far as the mask
+    /// tells, see [`Constraints::letter_counts`](crate::constraints::Constraints::letter_counts).
+    pub fn letter_counts(&self) -> BTreeMap<char, LetterCount> {
+        Constraints::from_history(core::slice::from_ref(self)).letter_counts()
+    }
+
+    /// Returns which tiles of the mask lied if `word` is the answer, i.e. differ from the mask
+    /// playing this guess against `word` would have revealed. None of them did if it
+    /// [matches](Guess::matches) the guess.
+    pub fn lies(&self, word: &str) -> [bool; N] {
+        let truth: [Correctness; N] = Correctness::compute(word, &self.word);
+        core::array::from_fn(|i| truth[i] != self.mask[i])
+    }
+
+    /// Returns whether `word` may be played after this guess in hard mode, i.e. whether it keeps
+    /// every green letter in place and contains every yellow letter.
+    ///
+    /// Words that do not have `N` letters are never allowed, nor is any word if the guess itself
+    /// does not have `N` letters.
+    pub fn hard_mode_allows(&self, word: &str) -> bool {
+        if self.word.chars().count() != N || word.chars().count() != N {
+            return false;
+        }
+
+        let mut used = [false; N];
+        for (i, ((g, &m), w)) in self
+            .word
+            .chars()
+            .zip(&self.mask)
+            .zip(word.chars())
+            .enumerate()
+        {
+            if m == Correctness::Correct {
+                if g != w {
+                    return false;
+                }
+                used[i] = true;
+            }
+        }
+
+        // Every yellow letter must appear in one of the positions not already taken by a green.
+        for (g, &m) in self.word.chars().zip(&self.mask) {
+            if m != Correctness::Misplaced {
+                continue;
+            }
+
+            let position = word
+                .chars()
+                .enumerate()
+                .position(|(i, w)| w == g && !used[i]);
+            match position {
+                Some(i) => used[i] = true,
+                None => return false,
+            }
+        }
+
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Correctness;
+
+    /// Computes the mask the way the rules spell it out, one letter at a time, to check the
+    /// optimized implementations against.
+    fn reference(answer: &str, guess: &str) -> Vec<Correctness> {
+        let answer: Vec<char> = answer.chars().collect();
+        let guess: Vec<char> = guess.chars().collect();
+        assert_eq!(answer.len(), guess.len());
+
+        // Letters in the right place are green.
+        let mut mask: Vec<Correctness> = answer
+            .iter()
+            .zip(&guess)
+            .map(|(a, g)| {
+                if a == g {
+                    Correctness::Correct
+                } else {
+                    Correctness::Wrong
+                }
+            })
+            .collect();
+
+        // Other letters are yellow, from left to right, as long as the answer has more copies
+        // of them than there are green and earlier yellow copies in the guess.
+        for i in 0..guess.len() {
+            if mask[i] == Correctness::Correct {
+                continue;
+            }
+            let copies = |letter: char, color: Correctness, upto: usize| {
+                (0..upto)
+                    .filter(|&j| guess[j] == letter && mask[j] == color)
+                    .count()
+            };
+            let in_answer = answer.iter().filter(|&&a| a == guess[i]).count();
+            let green = copies(guess[i], Correctness::Correct, guess.len());
+            let yellow = copies(guess[i], Correctness::Misplaced, i);
+            if green + yellow < in_answer {
+                mask[i] = Correctness::Misplaced;
+            }
+        }
+        mask
+    }
+
+    mod guess_matcher {
+        use std::convert::TryInto;
+
+        use super::reference;
+        use crate::{Correctness, Guess};
+        use proptest::prelude::*;
+
+        /// The matcher used before it was expressed in terms of [`Correctness::compute`], kept
+        /// around to document how the two differ.
+        fn legacy_matches<const N: usize>(guess: &Guess<N>, word: &str) -> bool {
+            assert_eq!(guess.word.len(), N);
+            assert_eq!(word.len(), N);
+
+            let mut used = [false; N];
+            for (i, ((g, &m), w)) in guess
+                .word
+                .chars()
+                .zip(&guess.mask)
+                .zip(word.chars())
+                .enumerate()
+            {
+                if m == Correctness::Correct {
+                    if g != w {
+                        return false;
+                    } else {
+                        used[i] = true;
+                    }
+                }
+            }
+
+            for (i, (w, &m)) in word.chars().zip(&guess.mask).enumerate() {
+                if m == Correctness::Correct {
+                    // Must be correct, or we would have returned in the earlier loop.
+                    continue;
+                }
+
+                let mut plausible = true;
+                if guess
+                    .word
+                    .chars()
+                    .zip(&guess.mask)
+                    .enumerate()
+                    .any(|(j, (g, m))| {
+                        if g != w {
+                            return false;
+                        }
+                        if used[j] {
+                            return false;
+                        }
+
+                        match m {
+                            Correctness::Correct => unreachable!("we shouldn't reach this point"),
+                            Correctness::Misplaced if j == i => {
+                                // `w` was misplaced the last time around, which
+                                // means that the word cannot be the answer.
+                                plausible = false;
+                                false
+                            }
+                            Correctness::Misplaced => {
+                                used[j] = true;
+                                true
+                            }
+                            Correctness::Wrong => {
+                                // TODO: early return
+                                plausible = false;
+                                false
+                            }
+                        }
+                    })
+                    && plausible
+                {
+                    // The character `w` was either guessed correctly, or was previously misplaced.
+                } else if !plausible {
+                    return false;
+                } else {
+                    // We have no information about the character `w`, so the word might still match.
+                }
+            }
+
+            true
+        }
+
+        /// Five-letter words over a small alphabet, so that repeated letters are common.
+        fn word() -> impl Strategy<Value = String> {
+            "[abc]{5}"
+        }
+
+        #[test]
+        fn legacy_ignores_missing_yellows() {
+            // "royal" lacks the yellow "c", which the legacy matcher never checked for.
+            let g = Guess::new("trace", mask!(W M M M W));
+            assert!(!g.matches("royal"));
+            assert!(legacy_matches(&g, "royal"));
+        }
+
+        proptest! {
+            #[test]
+            fn answer_always_matches(answer in word(), guess in word()) {
+                let g: Guess = Guess::new(guess.clone(), Correctness::compute(&answer, &guess));
+                prop_assert!(g.matches(&answer));
+            }
+
+            #[test]
+            fn matches_iff_same_mask(
+                answer in word(),
+                guess in word(),
+                candidate in word(),
+            ) {
+                // The masks come from the reference implementation, so that the matcher is not
+                // checked against itself.
+                let mask = reference(&answer, &guess);
+                let g: Guess = Guess::new(guess.clone(), mask.clone().try_into().unwrap());
+                prop_assert_eq!(g.matches(&candidate), reference(&candidate, &guess) == mask);
+
+                // Revealing the same mask is symmetric.
+                let h: Guess = Guess::new(guess.clone(), reference(&candidate, &guess).try_into().unwrap());
+                prop_assert_eq!(g.matches(&candidate), h.matches(&answer));
+            }
+
+            #[test]
+            fn accents_do_not_change_masks(answer in word(), guess in word()) {
+                // Letters outside of `a` to `z` take another path through `Correctness::compute`.
+                let accented = |word: &str| word.replace('a', "á").replace('c', "ç");
+                prop_assert_eq!(
+                    Correctness::compute::<5>(&accented(&answer), &accented(&guess)),
+                    Correctness::compute::<5>(&answer, &guess)
+                );
+            }
+
+            #[test]
+            fn agrees_with_legacy_when_allowed(
+                answer in word(),
+                guess in word(),
+                candidate in word(),
+            ) {
+                let g: Guess = Guess::new(guess.clone(), Correctness::compute(&answer, &guess));
+                if g.matches(&candidate) {
+                    prop_assert!(legacy_matches(&g, &candidate));
+                }
+            }
+        }
+
+        macro_rules! check {
+            ($prev:literal + [$($mask:tt)+] allows $next:literal) => {
+                let g = Guess::new($prev.to_string(), mask![$($mask)+]);
+                assert!(g.matches($next))
+            };
+            ($prev:literal + [$($mask:tt)+] disallows $next:literal) => {
+                let g = Guess::new($prev.to_string(), mask![$($mask)+]);
+                assert!(!g.matches($next))
+            };
+        }
+
+        #[test]
+        fn matches() {
+            check!("abcde" + [C C C C C] allows "abcde");
+            check!("abcdf" + [C C C C C] disallows "abcde");
+            check!("abcde" + [W W W W W] allows "mnopq");
+            check!("abcde" + [M M M M M] allows "eabcd");
+            check!("baaaa" + [W C M W W] allows "aaccc");
+            check!("baaaa" + [W C M W W] disallows "caacc");
+            check!("aaabb" + [C M W W W] disallows "accaa");
+            check!("abcde" + [W W W W W] disallows "baedc");
+            check!("abcdef" + [C C W W W M] allows "abfxyz");
+            check!("abcd" + [C C C W] disallows "abcd");
+            check!("abcde" + [C C C C C] disallows "abcd");
+            check!("abcd" + [C C C C C] disallows "abcde");
+        }
+
+        macro_rules! check_hard {
+            ($prev:literal + [$($mask:tt)+] allows $next:literal) => {
+                let g = Guess::new($prev.to_string(), mask![$($mask)+]);
+                assert!(g.hard_mode_allows($next))
+            };
+            ($prev:literal + [$($mask:tt)+] disallows $next:literal) => {
+                let g = Guess::new($prev.to_string(), mask![$($mask)+]);
+                assert!(!g.hard_mode_allows($next))
+            };
+        }
+
+        #[test]
+        fn hard_mode_allows() {
+            check_hard!("abcde" + [W W W W W] allows "abcde");
+            check_hard!("abcde" + [C W W W W] allows "afghi");
+            check_hard!("abcde" + [C W W W W] disallows "fghij");
+            check_hard!("abcde" + [W M W W W] allows "fghib");
+            check_hard!("abcde" + [W M W W W] allows "bfghi");
+            check_hard!("abcde" + [W M W W W] disallows "fghij");
+            check_hard!("aabcd" + [C M W W W] allows "afgha");
+            check_hard!("aabcd" + [C M W W W] disallows "afghi");
+            check_hard!("abcde" + [W W W W W] disallows "abcdef");
+            check_hard!("abcdef" + [W W W W W] disallows "abcde");
+        }
+    }
+
+    mod compute {
+        use std::convert::TryInto;
+
+        use super::reference;
+        use crate::Correctness;
+        use proptest::prelude::*;
+
+        #[test]
+        fn all_green() {
+            assert_eq!(Correctness::compute("abcde", "abcde"), mask!(C C C C C));
+        }
+
+        #[test]
+        fn all_gray() {
+            assert_eq!(Correctness::compute("abcde", "fghij"), mask!(W W W W W));
+        }
+
+        #[test]
+        fn all_yellow() {
+            assert_eq!(Correctness::compute("abcde", "baecd"), mask!(M M M M M));
+        }
+
+        #[test]
+        fn repeat_green() {
+            assert_eq!(Correctness::compute("aabbb", "aaccc"), mask!(C C W W W));
+        }
+
+        #[test]
+        fn repeat_yellow() {
+            assert_eq!(Correctness::compute("aabbb", "ccaac"), mask!(W W M M W));
+        }
+
+        #[test]
+        fn some_green_and_yellow() {
+            assert_eq!(Correctness::compute("azzaz", "aaabb"), mask!(C M W W W));
+        }
+
+        #[test]
+        fn unicode() {
+            assert_eq!(Correctness::compute("niños", "señor"), mask!(M W C C W));
+            assert_eq!(Correctness::compute("über", "rübe"), mask!(M M M M));
+        }
+
+        #[test]
+        fn not_lowercase() {
+            assert_eq!(Correctness::compute("AZZAZ", "AAABB"), mask!(C M W W W));
+            assert_eq!(Correctness::compute("Abcde", "abcdE"), mask!(W C C C W));
+        }
+
+        /// Pairs of `len` letters out of `alphabet`, which should be small so that repeated
+        /// letters are common, where the guess is often a shuffle of the answer.
+        fn pair(alphabet: &str, len: usize) -> impl Strategy<Value = (String, String)> {
+            let pattern = format!("[{}]{{{}}}", alphabet, len);
+            let word = || proptest::string::string_regex(&pattern).expect("the pattern is valid");
+            prop_oneof![
+                (word(), word()),
+                word().prop_flat_map(|answer| {
+                    let letters: Vec<char> = answer.chars().collect();
+                    let shuffled = Just(letters)
+                        .prop_shuffle()
+                        .prop_map(|letters| letters.into_iter().collect());
+                    (Just(answer), shuffled)
+                }),
+            ]
+        }
+
+        #[test]
+        fn reference_follows_the_rules() {
+            assert_eq!(reference("aabbb", "ccaac"), mask!(W W M M W));
+            assert_eq!(reference("azzaz", "aaabb"), mask!(C M W W W));
+            assert_eq!(reference("abbey", "babes"), mask!(M M C C W));
+        }
+
+        proptest! {
+            #[test]
+            fn agrees_with_reference((answer, guess) in pair("a-e", 5)) {
+                let expected = reference(&answer, &guess);
+                prop_assert_eq!(Correctness::compute::<5>(&answer, &guess).to_vec(), expected.clone());
+                let bytes: [Correctness; 5] = Correctness::compute_bytes(
+                    answer.as_bytes().try_into().unwrap(),
+                    guess.as_bytes().try_into().unwrap(),
+                );
+                prop_assert_eq!(bytes.to_vec(), expected);
+            }
+
+            #[test]
+            fn agrees_with_reference_on_any_letters((answer, guess) in pair("abáç", 5)) {
+                prop_assert_eq!(
+                    Correctness::compute::<5>(&answer, &guess).to_vec(),
+                    reference(&answer, &guess)
+                );
+            }
+
+            #[test]
+            fn agrees_with_reference_on_other_lengths((answer, guess) in pair("a-d", 7)) {
+                prop_assert_eq!(
+                    Correctness::compute::<7>(&answer, &guess).to_vec(),
+                    reference(&answer, &guess)
+                );
+            }
+
+            #[test]
+            fn bytes_agree_with_chars(answer in "[abc]{5}", guess in "[abc]{5}") {
+                let bytes: [Correctness; 5] = Correctness::compute_bytes(
+                    answer.as_bytes().try_into().unwrap(),
+                    guess.as_bytes().try_into().unwrap(),
+                );
+                prop_assert_eq!(bytes, Correctness::compute_chars(&answer, &guess));
+            }
+        }
+
+        #[test]
+        fn other_lengths() {
+            let c: [Correctness; 4] = Correctness::compute("abcd", "dbax");
+            assert_eq!(c, mask!(M C M W));
+            let c: [Correctness; 6] = Correctness::compute("aabbcc", "abcabc");
+            assert_eq!(c, mask!(C M M M M C));
+            assert_eq!(Correctness::try_compute::<5>("abcd", "dbax"), None);
+            assert_eq!(Correctness::try_compute::<5>("abcde", "abcdef"), None);
+        }
+
+        #[test]
+        fn pack() {
+            assert_eq!(Correctness::pack(mask!(C C C C C)), 0);
+            assert_eq!(Correctness::pack(mask!(C C C C M)), 1);
+            assert_eq!(Correctness::pack(mask!(W W W W W)), 242);
+            for packed in 0..Correctness::PATTERNS as u8 {
+                assert_eq!(Correctness::pack(Correctness::unpack(packed)), packed);
+            }
+        }
+
+        #[test]
+        #[should_panic]
+        fn unpack_out_of_range() {
+            Correctness::unpack(243);
+        }
+
+        #[test]
+        fn patterns() {
+            let patterns: std::collections::HashSet<_> = Correctness::patterns().collect();
+            assert_eq!(patterns.len(), Correctness::PATTERNS);
+            assert!(patterns.contains(&mask!(M W C M W)));
+        }
+
+        #[test]
+        fn possible_patterns() {
+            assert!(!Correctness::is_possible(mask!(C C M C C)));
+            assert!(Correctness::is_possible(mask!(C C M C M)));
+            assert!(Correctness::is_possible(mask!(C C W C C)));
+            assert!(!Correctness::is_possible([Correctness::Misplaced]));
+
+            // Every mask is revealed by some pair of words over four letters, unless impossible.
+            let words: Vec<[u8; 5]> = (0..4usize.pow(5))
+                .map(|mut i| {
+                    let mut word = [0; 5];
+                    for letter in word.iter_mut() {
+                        *letter = b'a' + (i % 4) as u8;
+                        i /= 4;
+                    }
+                    word
+                })
+                .collect();
+            let mut revealed = [false; Correctness::PATTERNS];
+            for answer in &words {
+                for guess in &words {
+                    revealed
+                        [Correctness::pack(Correctness::compute_bytes(answer, guess)) as usize] =
+                        true;
+                }
+            }
+            for mask in Correctness::patterns() {
+                assert_eq!(
+                    revealed[Correctness::pack(mask) as usize],
+                    Correctness::is_possible(mask),
+                    "{:?}",
+                    mask
+                );
+            }
+            assert_eq!(
+                Correctness::possible_patterns().count(),
+                Correctness::PATTERNS - 5
+            );
+        }
+
+        #[test]
+        fn parse_mask() {
+            assert_eq!(Correctness::parse_mask("GYXXX"), Some(mask!(C M W W W)));
+            assert_eq!(Correctness::parse_mask("gggyx"), Some(mask!(C C C M W)));
+            assert_eq!(Correctness::parse_mask::<5>("GYXX"), None);
+            assert_eq!(Correctness::parse_mask::<5>("GYXXXX"), None);
+            assert_eq!(Correctness::parse_mask::<5>("GYXXB"), None);
+        }
+    }
+}
@@ -0,0 +1,249 @@
+//! An HTTP API over the solver, so that bots and web pages can use it without bindings.
+//!
+//! Every endpoint takes the guesses played so far as a JSON object, e.g.
+//! `{"guesses": [{"word": "crane", "mask": "XYXXG"}]}`:
+//!
+//! - `POST /suggest` returns the word to play next and how many words could still be the
+//!   answer, e.g. `{"guess": "moist", "remaining": 12}`. With `"top": 3` in the request, it also
+//!   ranks the 3 best guesses along with their scores, see [`Guesser::rank`](crate::Guesser::rank),
+//!   e.g. `"ranking": [{"guess": "moist", "score": 2.5}, ...]`.
+//! - `POST /grade` returns the [`Grade`](crate::grade::Grade) of every guess, like the `grade`
+//!   command.
+//!
+//! Errors come back as `{"error": "..."}` with a 4xx status.
+
+use std::borrow::Cow;
+use std::io;
+use std::net::ToSocketAddrs;
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use tiny_http::{Header, Method, Request, Response};
+
+use crate::algorithms::{Candidates, Factory, PatternCache};
+use crate::grade::grade;
+use crate::{validate_word, Guess, History};
+
+/// Answers the requests of the API, see the [module documentation](self).
+pub struct Server {
+    factory: Factory,
+    cache: Arc<PatternCache>,
+    answers: Candidates,
+}
+
+/// The body of every request.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Game {
+    guesses: Vec<Guess>,
+    /// How many of the best guesses to rank, if any.
+    #[serde(default)]
+    top: usize,
+}
+
+#[derive(Debug, Serialize)]
+struct Suggestion {
+    guess: Cow<'static, str>,
+    remaining: usize,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    ranking: Vec<Ranked>,
+}
+
+#[derive(Debug, Serialize)]
+struct Ranked {
+    guess: &'static str,
+    score: f64,
+}
+
+#[derive(Debug, Serialize)]
+struct Failure {
+    error: String,
+}
+
+impl Server {
+    /// Creates a server suggesting the guesses of fresh guessers from `factory`, and telling
+    /// apart the `answers` among the words of `cache`.
+    ///
+    /// The guessers should consider the same answers, e.g. only the official answer list, so that
+    /// the number of remaining words matches their suggestions.
+    pub fn new(factory: Factory, cache: Arc<PatternCache>, answers: Candidates) -> Self {
+        Self {
+            factory,
+            cache,
+            answers,
+        }
+    }
+
+    /// Serves requests on `addr`, e.g. `127.0.0.1:8080`, one at a time until the process ends.
+    pub fn run(&self, addr: impl ToSocketAddrs) -> io::Result<()> {
+        let server = tiny_http::Server::http(addr).map_err(io::Error::other)?;
+        for request in server.incoming_requests() {
+            if let Err(e) = self.respond(request) {
+                tracing::warn!(error = %e, "could not respond to a request");
+            }
+        }
+        Ok(())
+    }
+
+    fn respond(&self, mut request: Request) -> io::Result<()> {
+        let mut body = String::new();
+        let (status, body) = match request.as_reader().read_to_string(&mut body) {
+            Ok(_) => {
+                let post = *request.method() == Method::Post;
+                self.handle(post, request.url(), &body)
+            }
+            Err(e) => failure(400, format!("could not read the body: {}", e)),
+        };
+        tracing::info!(url = request.url(), status, "answered a request");
+
+        let json = Header::from_bytes(&b"Content-Type"[..], &b"application/json"[..])
+            .expect("the header is valid");
+        request.respond(
+            Response::from_string(body)
+                .with_status_code(status)
+                .with_header(json),
+        )
+    }
+
+    /// Returns the status and the JSON body of the response to a request for `path`, with
+    /// `body`, which is only accepted with the `POST` method.
+    pub fn handle(&self, post: bool, path: &str, body: &str) -> (u16, String) {
+        if !matches!(path, "/suggest" | "/grade") {
+            return failure(404, format!("there is no `{}` endpoint", path));
+        }
+        if !post {
+            return failure(405, format!("`{}` only accepts POST requests", path));
+        }
+
+        let game: Game = match serde_json::from_str(body) {
+            Ok(game) => game,
+            Err(e) => return failure(400, e.to_string()),
+        };
+        if let Some(e) = game
+            .guesses
+            .iter()
+            .find_map(|guess| validate_word::<5>(&guess.word).err())
+        {
+            return failure(400, e.to_string());
+        }
+
+        match path {
+            "/suggest" => self.suggest(&game.guesses, game.top),
+            _ => respond(200, &grade(&self.cache, &self.answers, &game.guesses)),
+        }
+    }
+
+    fn suggest(&self, guesses: &[Guess], top: usize) -> (u16, String) {
+        let mut remaining = self.answers.clone();
+        for guess in guesses {
+            remaining.retain_matching(&self.cache, guess);
+            if remaining.is_empty() {
+                return failure(
+                    422,
+                    format!("no answer agrees with the colors of `{}`", guess.word),
+                );
+            }
+        }
+
+        let history = History::from(guesses);
+        let mut guesser = (self.factory)();
+        let guess = guesser.guess(&history);
+        let ranking = guesser
+            .rank(&history, top)
+            .into_iter()
+            .map(|(guess, score)| Ranked { guess, score })
+            .collect();
+        respond(
+            200,
+            &Suggestion {
+                guess,
+                remaining: remaining.len(),
+                ranking,
+            },
+        )
+    }
+}
+
+fn respond(status: u16, body: &impl Serialize) -> (u16, String) {
+    let body = serde_json::to_string(body).expect("responses are always serializable");
+    (status, body)
+}
+
+fn failure(status: u16, error: String) -> (u16, String) {
+    respond(status, &Failure { error })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use serde_json::{json, Value};
+
+    use super::Server;
+    use crate::algorithms::{lookup, Candidates, PatternCache, Settings};
+    use crate::WORDS;
+
+    fn server() -> Server {
+        let cache = Arc::new(PatternCache::new(WORDS.iter().map(|&(word, _)| word)));
+        let factory = lookup("minimax")
+            .unwrap()
+            .factory(Settings {
+                dictionary: Some(WORDS.to_vec()),
+                cache: Some(cache.clone()),
+                ..Settings::default()
+            })
+            .unwrap();
+        let answers = Candidates::all(cache.len());
+        Server::new(factory, cache, answers)
+    }
+
+    fn post(server: &Server, path: &str, body: Value) -> (u16, Value) {
+        let (status, body) = server.handle(true, path, &body.to_string());
+        (status, serde_json::from_str(&body).unwrap())
+    }
+
+    #[test]
+    fn suggests() {
+        let server = server();
+        let (status, body) = post(&server, "/suggest", json!({ "guesses": [] }));
+        assert_eq!(status, 200);
+        assert_eq!(body["remaining"], 12);
+
+        let guesses = json!({ "guesses": [{ "word": "blush", "mask": "XXYXG" }] });
+        let (status, body) = post(&server, "/suggest", guesses);
+        assert_eq!(status, 200);
+        assert_eq!(body, json!({ "guess": "humph", "remaining": 1 }));
+    }
+
+    #[test]
+    fn ranks_the_best_guesses() {
+        let server = server();
+        let (status, body) = post(&server, "/suggest", json!({ "guesses": [], "top": 3 }));
+        assert_eq!(status, 200);
+        let ranking = body["ranking"].as_array().unwrap();
+        assert_eq!(ranking.len(), 3);
+        assert_eq!(ranking[0]["guess"], body["guess"]);
+        assert!(ranking[0]["score"].as_f64() >= ranking[2]["score"].as_f64());
+    }
+
+    #[test]
+    fn grades() {
+        let guesses = json!({ "guesses": [{ "word": "blush", "mask": "XXYXG" }] });
+        let (status, body) = post(&server(), "/grade", guesses);
+        assert_eq!(status, 200);
+        assert_eq!(body[0]["word"], "blush");
+        assert_eq!(body[0]["remaining"], 1);
+    }
+
+    #[test]
+    fn rejects_invalid_requests() {
+        let server = server();
+        let inconsistent = json!({ "guesses": [{ "word": "blush", "mask": "GGGGX" }] });
+        assert_eq!(post(&server, "/suggest", inconsistent).0, 422);
+        let invalid = json!({ "guesses": [{ "word": "blush", "mask": "GGGG" }] });
+        assert_eq!(post(&server, "/suggest", invalid).0, 400);
+        assert_eq!(server.handle(true, "/grade", "{").0, 400);
+        assert_eq!(server.handle(true, "/", "").0, 404);
+        assert_eq!(server.handle(false, "/suggest", "").0, 405);
+    }
+}
@@ -0,0 +1,140 @@
+//! Computing the masks revealed by a guess against many answers at once, which is where scoring
+//! guesses spends most of its time.
+//!
+//! On x86_64, answers are processed [`LANES`] at a time with SSE2, which every x86_64 processor
+//! supports. Other targets fall back to
+//! [`Correctness::compute_bytes`](crate::Correctness::compute_bytes) for every answer.
+
+#[cfg(not(target_arch = "x86_64"))]
+use crate::Correctness;
+
+/// Number of answers processed at once.
+pub const LANES: usize = 16;
+
+/// Writes the packed mask revealed by `guess` against every one of `answers` to `patterns`, like
+/// [`Correctness::pack`](crate::Correctness::pack) of
+/// [`Correctness::compute_bytes`](crate::Correctness::compute_bytes).
+///
+/// # Panics
+///
+/// Panics if `patterns` is not as long as `answers`.
+pub fn packed_patterns(guess: &[u8; 5], answers: &[[u8; 5]], patterns: &mut [u8]) {
+    assert_eq!(
+        answers.len(),
+        patterns.len(),
+        "there is one pattern per answer"
+    );
+
+    for (answers, patterns) in answers.chunks(LANES).zip(patterns.chunks_mut(LANES)) {
+        // Lay the chunk out letter by letter, so that every letter position is a single vector.
+        let mut lanes = [[0; LANES]; 5];
+        for (lane, answer) in answers.iter().enumerate() {
+            for (position, &letter) in answer.iter().enumerate() {
+                lanes[position][lane] = letter;
+            }
+        }
+
+        let packed = chunk(guess, &lanes);
+        patterns.copy_from_slice(&packed[..patterns.len()]);
+    }
+}
+
+#[cfg(target_arch = "x86_64")]
+fn chunk(guess: &[u8; 5], lanes: &[[u8; LANES]; 5]) -> [u8; LANES] {
+    use std::arch::x86_64::*;
+
+    // SAFETY: SSE2 is part of the x86_64 baseline, and the loads and stores are unaligned ones of
+    // exactly `LANES` bytes.
+    unsafe {
+        let answer: [__m128i; 5] =
+            std::array::from_fn(|i| _mm_loadu_si128(lanes[i].as_ptr() as *const __m128i));
+        let splat = |letter: u8| _mm_set1_epi8(letter as i8);
+        let all = _mm_set1_epi8(-1);
+
+        // Every lane of a comparison is either all ones or all zeros, i.e. -1 or 0.
+        let green: [__m128i; 5] =
+            std::array::from_fn(|i| _mm_cmpeq_epi8(answer[i], splat(guess[i])));
+        let open: [__m128i; 5] = std::array::from_fn(|i| _mm_andnot_si128(green[i], all));
+
+        let mut packed = _mm_setzero_si128();
+        let mut weight = 81;
+        for i in 0..5 {
+            // A letter is yellow if the answer has more copies of it outside of the green
+            // positions than the guess used up to the left of it.
+            let mut available = _mm_setzero_si128();
+            for (&answer, &open) in answer.iter().zip(&open) {
+                let copy = _mm_and_si128(_mm_cmpeq_epi8(answer, splat(guess[i])), open);
+                available = _mm_sub_epi8(available, copy);
+            }
+            let mut used = _mm_setzero_si128();
+            for j in (0..i).filter(|&j| guess[j] == guess[i]) {
+                used = _mm_sub_epi8(used, open[j]);
+            }
+
+            let yellow = _mm_and_si128(open[i], _mm_cmpgt_epi8(available, used));
+            let gray = _mm_andnot_si128(_mm_or_si128(green[i], yellow), all);
+            // Green letters count for 0, yellow ones for 1 and gray ones for 2, like in
+            // `Correctness::pack`.
+            let digit = _mm_or_si128(
+                _mm_and_si128(yellow, splat(weight)),
+                _mm_and_si128(gray, splat(2 * weight)),
+            );
+            packed = _mm_add_epi8(packed, digit);
+            weight /= 3;
+        }
+
+        let mut out = [0; LANES];
+        _mm_storeu_si128(out.as_mut_ptr() as *mut __m128i, packed);
+        out
+    }
+}
+
+#[cfg(not(target_arch = "x86_64"))]
+fn chunk(guess: &[u8; 5], lanes: &[[u8; LANES]; 5]) -> [u8; LANES] {
+    std::array::from_fn(|lane| {
+        let answer = std::array::from_fn(|position| lanes[position][lane]);
+        Correctness::pack(Correctness::compute_bytes(&answer, guess))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::packed_patterns;
+    use crate::Correctness;
+    use proptest::prelude::*;
+
+    fn packed(answer: &[u8; 5], guess: &[u8; 5]) -> u8 {
+        Correctness::pack(Correctness::compute_bytes(answer, guess))
+    }
+
+    #[test]
+    fn repeated_letters() {
+        let answers = [*b"abbey", *b"kebab", *b"babes", *b"crane", *b"ebbed"];
+        for guess in [b"abbey", b"keeps", b"ebbed", b"bobby"] {
+            let mut patterns = [0; 5];
+            packed_patterns(guess, &answers, &mut patterns);
+            for (answer, &pattern) in answers.iter().zip(&patterns) {
+                assert_eq!(pattern, packed(answer, guess));
+            }
+        }
+    }
+
+    /// Five-letter words over a small alphabet, so that repeated letters are common.
+    fn word() -> impl Strategy<Value = [u8; 5]> {
+        prop::array::uniform5(b'a'..=b'd')
+    }
+
+    proptest! {
+        #[test]
+        fn agrees_with_compute_bytes(
+            guess in word(),
+            answers in prop::collection::vec(word(), 0..40),
+        ) {
+            let mut patterns = vec![0; answers.len()];
+            packed_patterns(&guess, &answers, &mut patterns);
+            for (answer, &pattern) in answers.iter().zip(&patterns) {
+                prop_assert_eq!(pattern, packed(answer, &guess));
+            }
+        }
+    }
+}
@@ -0,0 +1,170 @@
+//! Simulating the daily puzzles of the official game over a stretch of days, to see the streaks a
+//! player following a guesser would get, like the statistics panel of the official game.
+
+use std::fmt;
+
+use rand::distr::weighted::WeightedIndex;
+use rand::distr::Distribution;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+use crate::OFFICIAL_MAX_GUESSES;
+
+/// Widest bar (in characters) drawn by the guess distribution of the [`Display`](fmt::Display)
+/// output of [`Streaks`].
+const DISTRIBUTION_WIDTH: usize = 40;
+
+/// How the answer of every day is drawn.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub enum Sampling {
+    /// Every answer is as likely as any other.
+    #[default]
+    Uniform,
+    /// Answers are as likely as their frequency count, so common words come up more often.
+    Frequency,
+}
+
+/// Draws the answers of `days` puzzles out of `answers` and their frequency counts, reproducibly
+/// for a given `seed`. Answers may come up more than once.
+///
+/// # Panics
+///
+/// Panics if there are no answers to draw from, or if they all have a count of zero with
+/// [`Sampling::Frequency`].
+pub fn sample_answers(
+    answers: &[(&'static str, usize)],
+    days: usize,
+    seed: u64,
+    sampling: Sampling,
+) -> Vec<&'static str> {
+    assert!(!answers.is_empty(), "there must be answers to draw from");
+    let mut rng = ChaCha8Rng::seed_from_u64(seed);
+    match sampling {
+        Sampling::Uniform => (0..days)
+            .map(|_| answers[rng.random_range(0..answers.len())].0)
+            .collect(),
+        Sampling::Frequency => {
+            let weights = WeightedIndex::new(answers.iter().map(|&(_, count)| count))
+                .expect("at least one answer has a frequency count");
+            (0..days)
+                .map(|_| answers[weights.sample(&mut rng)].0)
+                .collect()
+        }
+    }
+}
+
+/// The statistics the official game shows for a run of daily puzzles, where a game is won if it
+/// is solved within the [`OFFICIAL_MAX_GUESSES`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Streaks {
+    played: usize,
+    current: usize,
+    max: usize,
+    /// `distribution[i]` is the number of games won in `i + 1` guesses.
+    distribution: [usize; OFFICIAL_MAX_GUESSES],
+}
+
+impl Streaks {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records the outcome of the game of the next day.
+    pub fn record(&mut self, score: Option<usize>) {
+        self.played += 1;
+        match score.filter(|&score| score <= OFFICIAL_MAX_GUESSES) {
+            Some(score) => {
+                self.distribution[score - 1] += 1;
+                self.current += 1;
+                self.max = self.max.max(self.current);
+            }
+            None => self.current = 0,
+        }
+    }
+
+    pub fn played(&self) -> usize {
+        self.played
+    }
+
+    pub fn won(&self) -> usize {
+        self.distribution.iter().sum()
+    }
+
+    /// Fraction of the games that were won, between 0 and 1.
+    pub fn win_rate(&self) -> f64 {
+        if self.played == 0 {
+            return 0.0;
+        }
+
+        self.won() as f64 / self.played as f64
+    }
+
+    /// Number of games won in a row up to the last day.
+    pub fn current_streak(&self) -> usize {
+        self.current
+    }
+
+    /// Longest run of games won in a row.
+    pub fn max_streak(&self) -> usize {
+        self.max
+    }
+
+    /// Number of games won per guess count: the element at index `i` counts the games won in
+    /// `i + 1` guesses.
+    pub fn distribution(&self) -> &[usize; OFFICIAL_MAX_GUESSES] {
+        &self.distribution
+    }
+}
+
+impl fmt::Display for Streaks {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "played:         {}", self.played)?;
+        writeln!(f, "win %:          {:.0}", self.win_rate() * 100.0)?;
+        writeln!(f, "current streak: {}", self.current)?;
+        writeln!(f, "max streak:     {}", self.max)?;
+        writeln!(f, "guess distribution:")?;
+
+        let max = self.distribution.iter().copied().max().unwrap_or(0);
+        for (i, &count) in self.distribution.iter().enumerate() {
+            let width = (count * DISTRIBUTION_WIDTH).checked_div(max).unwrap_or(0);
+            writeln!(f, "{:>3} | {:>5} | {}", i + 1, count, "#".repeat(width))?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{sample_answers, Sampling, Streaks};
+
+    #[test]
+    fn streaks() {
+        let mut streaks = Streaks::new();
+        for score in [Some(3), Some(4), None, Some(2), Some(7), Some(6), Some(1)] {
+            streaks.record(score);
+        }
+
+        assert_eq!(streaks.played(), 7);
+        assert_eq!(streaks.won(), 5);
+        // Solving in 7 guesses is a loss for the official game.
+        assert_eq!(streaks.max_streak(), 2);
+        assert_eq!(streaks.current_streak(), 2);
+        assert_eq!(streaks.distribution(), &[1, 1, 1, 1, 0, 1]);
+    }
+
+    #[test]
+    fn sampling() {
+        let answers = [("cigar", 1), ("rebut", 0), ("sissy", 3)];
+        for sampling in [Sampling::Uniform, Sampling::Frequency] {
+            let days = sample_answers(&answers, 100, 7, sampling);
+            assert_eq!(days.len(), 100);
+            assert_eq!(days, sample_answers(&answers, 100, 7, sampling));
+        }
+
+        let days = sample_answers(&answers, 100, 7, Sampling::Frequency);
+        assert!(!days.contains(&"rebut"));
+        let days = sample_answers(&answers, 100, 7, Sampling::Uniform);
+        assert!(days.contains(&"rebut"));
+    }
+}
@@ -0,0 +1,391 @@
+use std::fmt;
+
+use crate::{Transcript, OFFICIAL_MAX_GUESSES};
+
+/// Widest bar (in characters) drawn by the histogram in the [`Display`](fmt::Display) output.
+const HISTOGRAM_WIDTH: usize = 50;
+
+/// Number of games kept by [`Worst::default`].
+pub const WORST_GAMES: usize = 20;
+
+/// The objective games are scored by, to compare guessers on what matters to the player: how
+/// few guesses they take on average, or how rarely they lose the official game.
+///
+/// Every game costs something under the model, and lower costs are better.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum Score {
+    /// A game costs the number of guesses it took. Games that were not solved are left out, like
+    /// in [`Stats::mean`].
+    #[default]
+    Guesses,
+    /// A game costs 1 if it was lost, i.e. not solved within the [`OFFICIAL_MAX_GUESSES`], and
+    /// nothing otherwise, so that the average cost is the fraction of games lost.
+    WinLoss,
+    /// A game costs the number of guesses it took if it was won, and `fail_cost` if it was lost.
+    Penalty { fail_cost: f64 },
+}
+
+impl Score {
+    /// Returns the cost of a game that was solved in `score` guesses, or never solved if `score`
+    /// is `None`. Returns `None` for the games that do not count under this model.
+    pub fn cost(&self, score: Option<usize>) -> Option<f64> {
+        let won = score.filter(|&score| score <= OFFICIAL_MAX_GUESSES);
+        match *self {
+            Score::Guesses => score.map(|score| score as f64),
+            Score::WinLoss => Some(if won.is_some() { 0.0 } else { 1.0 }),
+            Score::Penalty { fail_cost } => Some(won.map_or(fail_cost, |score| score as f64)),
+        }
+    }
+}
+
+impl fmt::Display for Score {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Score::Guesses => write!(f, "guesses"),
+            Score::WinLoss => write!(f, "win-loss"),
+            Score::Penalty { fail_cost } => write!(f, "penalty:{}", fail_cost),
+        }
+    }
+}
+
+/// Aggregated results of a batch of games, as returned by [`Wordle::play`](crate::Wordle::play).
+#[derive(Debug, Default, Clone)]
+pub struct Stats {
+    /// `solved[i]` is the number of games that were solved in `i + 1` guesses.
+    solved: Vec<usize>,
+    failures: usize,
+}
+
+impl Stats {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records the outcome of a single game.
+    ///
+    /// A score of zero is ignored, since no game is solved without a guess.
+    pub fn record(&mut self, score: Option<usize>) {
+        match score {
+            Some(0) => {}
+            Some(score) => {
+                if self.solved.len() < score {
+                    self.solved.resize(score, 0);
+                }
+                self.solved[score - 1] += 1;
+            }
+            None => self.failures += 1,
+        }
+    }
+
+    /// Number of games recorded so far.
+    pub fn games(&self) -> usize {
+        self.solved() + self.failures
+    }
+
+    /// Number of games where the answer was found.
+    pub fn solved(&self) -> usize {
+        self.solved.iter().sum()
+    }
+
+    /// Number of games where the answer was never found.
+    pub fn failures(&self) -> usize {
+        self.failures
+    }
+
+    /// Fraction of the games that were not solved, between 0 and 1.
+    pub fn failure_rate(&self) -> f64 {
+        if self.games() == 0 {
+            return 0.0;
+        }
+
+        self.failures as f64 / self.games() as f64
+    }
+
+    /// Fraction of the games solved within the [`OFFICIAL_MAX_GUESSES`], which the official game
+    /// counts as wins, between 0 and 1.
+    pub fn win_rate(&self) -> f64 {
+        if self.games() == 0 {
+            return 0.0;
+        }
+
+        let won: usize = self.solved.iter().take(OFFICIAL_MAX_GUESSES).sum();
+        won as f64 / self.games() as f64
+    }
+
+    /// Number of solved games per guess count: the element at index `i` counts the games
+    /// solved in `i + 1` guesses.
+    pub fn histogram(&self) -> &[usize] {
+        &self.solved
+    }
+
+    /// Average number of guesses over the solved games.
+    pub fn mean(&self) -> Option<f64> {
+        let solved = self.solved();
+        if solved == 0 {
+            return None;
+        }
+
+        let total: usize = self
+            .solved
+            .iter()
+            .enumerate()
+            .map(|(i, &n)| (i + 1) * n)
+            .sum();
+        Some(total as f64 / solved as f64)
+    }
+
+    /// Median number of guesses over the solved games.
+    pub fn median(&self) -> Option<f64> {
+        let solved = self.solved();
+        if solved == 0 {
+            return None;
+        }
+
+        if solved % 2 == 1 {
+            Some(self.nth_score(solved / 2) as f64)
+        } else {
+            let (a, b) = (self.nth_score(solved / 2 - 1), self.nth_score(solved / 2));
+            Some((a + b) as f64 / 2.0)
+        }
+    }
+
+    /// Average cost of the games under the given scoring model, or `None` if no game counts
+    /// under it.
+    pub fn cost(&self, score: Score) -> Option<f64> {
+        let games = self
+            .solved
+            .iter()
+            .enumerate()
+            .map(|(i, &n)| (Some(i + 1), n))
+            .chain(std::iter::once((None, self.failures)))
+            .filter(|&(_, n)| n > 0);
+
+        let (mut total, mut counted) = (0.0, 0);
+        for (outcome, n) in games {
+            if let Some(cost) = score.cost(outcome) {
+                total += cost * n as f64;
+                counted += n;
+            }
+        }
+        (counted > 0).then(|| total / counted as f64)
+    }
+
+    /// Returns the score of the `n`-th (zero-based) solved game when sorted by score.
+    fn nth_score(&self, mut n: usize) -> usize {
+        for (i, &count) in self.solved.iter().enumerate() {
+            if n < count {
+                return i + 1;
+            }
+            n -= count;
+        }
+
+        unreachable!("`n` must be smaller than the number of solved games")
+    }
+}
+
+impl Extend<Option<usize>> for Stats {
+    fn extend<T: IntoIterator<Item = Option<usize>>>(&mut self, iter: T) {
+        for score in iter {
+            self.record(score);
+        }
+    }
+}
+
+impl std::iter::FromIterator<Option<usize>> for Stats {
+    fn from_iter<T: IntoIterator<Item = Option<usize>>>(iter: T) -> Self {
+        let mut stats = Self::new();
+        stats.extend(iter);
+        stats
+    }
+}
+
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "games played: {}", self.games())?;
+        writeln!(
+            f,
+            "failed:       {} ({:.2}%)",
+            self.failures,
+            self.failure_rate() * 100.0
+        )?;
+        if let (Some(mean), Some(median)) = (self.mean(), self.median()) {
+            writeln!(f, "mean score:   {:.4}", mean)?;
+            writeln!(f, "median score: {}", median)?;
+        }
+
+        let max = self.solved.iter().copied().max().unwrap_or(0);
+        for (i, &count) in self.solved.iter().enumerate() {
+            let width = (count * HISTOGRAM_WIDTH).checked_div(max).unwrap_or(0);
+            writeln!(f, "{:>3} | {:>6} | {}", i + 1, count, "#".repeat(width))?;
+        }
+
+        Ok(())
+    }
+}
+
+/// The games of a batch that took the most guesses, failures first, to see exactly which answers
+/// a guesser struggles with.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Worst<const N: usize = 5> {
+    limit: usize,
+    /// Sorted from the worst game.
+    games: Vec<Transcript<N>>,
+}
+
+impl<const N: usize> Worst<N> {
+    /// Keeps the `limit` worst games recorded.
+    pub fn new(limit: usize) -> Self {
+        Self {
+            limit,
+            games: Vec::new(),
+        }
+    }
+
+    /// Records the outcome of a single game, which is kept if it is one of the worst so far.
+    /// Among games that are just as bad, the ones recorded first are kept.
+    pub fn record(&mut self, transcript: &Transcript<N>) {
+        let key = badness(transcript);
+        let position = self.games.partition_point(|kept| badness(kept) >= key);
+        if position < self.limit {
+            self.games.insert(position, transcript.clone());
+            self.games.truncate(self.limit);
+        }
+    }
+
+    /// Returns the games kept, from the worst one.
+    pub fn games(&self) -> &[Transcript<N>] {
+        &self.games
+    }
+}
+
+impl<const N: usize> Default for Worst<N> {
+    fn default() -> Self {
+        Self::new(WORST_GAMES)
+    }
+}
+
+/// Failed games are worse than any solved game, and games are worse the more guesses they took.
+fn badness<const N: usize>(transcript: &Transcript<N>) -> (bool, usize) {
+    (transcript.score.is_none(), transcript.guesses.len())
+}
+
+impl<const N: usize> fmt::Display for Worst<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "worst {} answers:", self.games.len())?;
+        let width = self
+            .games
+            .iter()
+            .map(|game| game.answer.chars().count())
+            .max()
+            .unwrap_or(0);
+        for game in &self.games {
+            let score = match game.score {
+                Some(score) => score.to_string(),
+                None => "X".to_string(),
+            };
+            let guesses: Vec<&str> = game.guesses.iter().map(|guess| &*guess.word).collect();
+            writeln!(
+                f,
+                "  {:<width$}  {:>2}  {}",
+                game.answer,
+                score,
+                guesses.join(" "),
+                width = width
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Score, Stats, Worst};
+    use crate::{Guess, Transcript};
+
+    #[test]
+    fn empty() {
+        let stats = Stats::new();
+        assert_eq!(stats.games(), 0);
+        assert_eq!(stats.failure_rate(), 0.0);
+        assert_eq!(stats.mean(), None);
+        assert_eq!(stats.median(), None);
+    }
+
+    #[test]
+    fn histogram() {
+        let stats: Stats = vec![Some(3), Some(1), None, Some(3)].into_iter().collect();
+        assert_eq!(stats.histogram(), &[1, 0, 2]);
+        assert_eq!(stats.games(), 4);
+        assert_eq!(stats.solved(), 3);
+        assert_eq!(stats.failures(), 1);
+        assert_eq!(stats.failure_rate(), 0.25);
+        assert_eq!(stats.win_rate(), 0.75);
+
+        let zero: Stats = vec![Some(0), Some(1)].into_iter().collect();
+        assert_eq!(zero.histogram(), &[1]);
+        assert_eq!(zero.games(), 1);
+    }
+
+    #[test]
+    fn win_rate() {
+        let stats: Stats = vec![Some(6), Some(7), None, Some(2)].into_iter().collect();
+        assert_eq!(stats.win_rate(), 0.5);
+        assert_eq!(Stats::new().win_rate(), 0.0);
+    }
+
+    #[test]
+    fn mean_and_median() {
+        let stats: Stats = vec![Some(2), Some(4), Some(3), Some(6)]
+            .into_iter()
+            .collect();
+        assert_eq!(stats.mean(), Some(3.75));
+        assert_eq!(stats.median(), Some(3.5));
+
+        let stats: Stats = vec![Some(2), Some(4), Some(4), None].into_iter().collect();
+        assert_eq!(stats.median(), Some(4.0));
+    }
+
+    #[test]
+    fn cost() {
+        let stats: Stats = vec![Some(2), Some(7), None, Some(3)].into_iter().collect();
+        assert_eq!(stats.cost(Score::Guesses), stats.mean());
+        assert_eq!(stats.cost(Score::WinLoss), Some(0.5));
+        assert_eq!(
+            stats.cost(Score::Penalty { fail_cost: 10.0 }),
+            Some((2.0 + 10.0 + 10.0 + 3.0) / 4.0)
+        );
+        assert_eq!(Stats::new().cost(Score::WinLoss), None);
+        let failed: Stats = vec![None].into_iter().collect();
+        assert_eq!(failed.cost(Score::Guesses), None);
+        assert_eq!(failed.cost(Score::WinLoss), Some(1.0));
+    }
+
+    fn transcript(answer: &str, guesses: &[&str], score: Option<usize>) -> Transcript {
+        Transcript {
+            answer: answer.to_string(),
+            guesses: guesses
+                .iter()
+                .map(|&word| Guess::new(word.to_string(), mask!(W W W W W)))
+                .collect(),
+            score,
+        }
+    }
+
+    #[test]
+    fn worst() {
+        let mut worst = Worst::new(3);
+        worst.record(&transcript("cigar", &["crane", "cigar"], Some(2)));
+        worst.record(&transcript("watch", &["crane", "batch", "hatch"], None));
+        worst.record(&transcript("rebut", &["crane", "tribe", "rebut"], Some(3)));
+        worst.record(&transcript("sissy", &["crane", "sissy"], Some(2)));
+        worst.record(&transcript("humph", &["crane", "humph"], Some(2)));
+
+        let answers: Vec<_> = worst.games().iter().map(|g| g.answer.as_str()).collect();
+        assert_eq!(answers, vec!["watch", "rebut", "cigar"]);
+        assert_eq!(
+            worst.to_string(),
+            "worst 3 answers:\n  watch   X  crane batch hatch\n  rebut   3  crane tribe rebut\n  cigar   2  crane cigar\n"
+        );
+    }
+}
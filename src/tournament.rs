@@ -0,0 +1,312 @@
+//! Playing several algorithms over the same answers, to compare how well and how fast they play.
+
+use std::fmt;
+use std::time::{Duration, Instant};
+
+use crate::batch::Batch;
+use crate::stats::{Score, Stats, Worst, WORST_GAMES};
+use crate::{Estimate, GameResult, Guesser, History, Wordle, OFFICIAL_MAX_GUESSES};
+
+/// Creates a fresh guesser for every game of a contender, from any thread.
+type Contender<const N: usize> = Box<dyn Fn() -> Box<dyn Guesser<N>> + Send + Sync>;
+
+/// Plays every contender over the same answers, one after the other.
+pub struct Tournament<'w, const N: usize = 5> {
+    wordle: &'w Wordle<N>,
+    contenders: Vec<(String, Contender<N>)>,
+    progress: bool,
+    jobs: usize,
+    worst: usize,
+    score: Score,
+}
+
+impl<'w, const N: usize> Tournament<'w, N> {
+    pub fn new(wordle: &'w Wordle<N>) -> Self {
+        Self {
+            wordle,
+            contenders: Vec::new(),
+            progress: false,
+            jobs: 1,
+            worst: WORST_GAMES,
+            score: Score::default(),
+        }
+    }
+
+    /// Adds a contender, which plays every game with a fresh guesser from `factory`, e.g. a
+    /// [`Factory`](crate::algorithms::Factory) out of the registry.
+    pub fn contender(
+        mut self,
+        name: impl Into<String>,
+        factory: impl Fn() -> Box<dyn Guesser<N>> + Send + Sync + 'static,
+    ) -> Self {
+        self.contenders.push((name.into(), Box::new(factory)));
+        self
+    }
+
+    /// Whether to draw a progress bar for every contender, see [`Batch::with_progress`].
+    pub fn with_progress(mut self, progress: bool) -> Self {
+        self.progress = progress;
+        self
+    }
+
+    /// Sets how many games every contender plays at once, see [`Batch::with_jobs`].
+    pub fn with_jobs(mut self, jobs: usize) -> Self {
+        self.jobs = jobs;
+        self
+    }
+
+    /// Sets how many of the worst games of every contender to keep. Defaults to [`WORST_GAMES`].
+    pub fn with_worst(mut self, worst: usize) -> Self {
+        self.worst = worst;
+        self
+    }
+
+    /// Sets the objective the contenders are compared by, see [`Standings::best`]. Defaults to
+    /// the average number of guesses.
+    pub fn with_score(mut self, score: Score) -> Self {
+        self.score = score;
+        self
+    }
+
+    /// Plays a game for every answer with every contender. Games that cannot be played count as
+    /// failures.
+    pub fn run(&self, answers: &[&str]) -> Standings<N> {
+        self.run_with(answers, |_, _| {})
+    }
+
+    /// Like [`Tournament::run`], but also calls `on_game` with the name of the contender and every
+    /// game it played, in the order of the answers, e.g. to write them out.
+    pub fn run_with(
+        &self,
+        answers: &[&str],
+        mut on_game: impl FnMut(&str, &GameResult<N>),
+    ) -> Standings<N> {
+        let results = self
+            .contenders
+            .iter()
+            .map(|(name, factory)| {
+                let _span = tracing::info_span!("contender", name = name.as_str()).entered();
+                let estimate = factory().estimate(&History::new());
+                let start = Instant::now();
+                let mut worst = Worst::new(self.worst);
+                let stats = Batch::new(self.wordle)
+                    .with_progress(self.progress)
+                    .with_jobs(self.jobs)
+                    .run(answers, factory, |answer, result| match result {
+                        Ok(game) => {
+                            worst.record(&game.transcript);
+                            on_game(name, &game);
+                        }
+                        Err(e) => tracing::warn!(answer, error = %e, "could not play the game"),
+                    });
+                Standing {
+                    name: name.clone(),
+                    stats,
+                    worst,
+                    elapsed: start.elapsed(),
+                    estimate,
+                }
+            })
+            .collect();
+
+        Standings {
+            results,
+            score: self.score,
+        }
+    }
+}
+
+/// How a single contender of a [`Tournament`] did.
+#[derive(Debug, Clone)]
+pub struct Standing<const N: usize = 5> {
+    pub name: String,
+    pub stats: Stats,
+    /// The games the contender struggled with the most.
+    pub worst: Worst<N>,
+    /// Wall-clock time of all the games of the contender.
+    pub elapsed: Duration,
+    /// What the contender expected of its games before their first guess, if it can tell, see
+    /// [`Guesser::estimate`].
+    pub estimate: Option<Estimate>,
+}
+
+/// How every contender of a [`Tournament`] did, in the order they were added.
+///
+/// Displays as a table with a row per contender, with the number of guesses they expected to take
+/// before the first one, along with their average cost under the scoring model of the tournament
+/// unless it is the mean score.
+#[derive(Debug, Clone)]
+pub struct Standings<const N: usize = 5> {
+    pub results: Vec<Standing<N>>,
+    /// The objective the contenders are compared by.
+    pub score: Score,
+}
+
+impl<const N: usize> Standings<N> {
+    /// Returns the contender with the lowest average cost under the scoring model, the first one
+    /// on ties, or `None` if no game counts under it.
+    pub fn best(&self) -> Option<&Standing<N>> {
+        self.results
+            .iter()
+            .filter_map(|standing| Some((standing, standing.stats.cost(self.score)?)))
+            .fold(None, |best: Option<(&Standing<N>, f64)>, next| match best {
+                Some(best) if next.1 >= best.1 => Some(best),
+                _ => Some(next),
+            })
+            .map(|(standing, _)| standing)
+    }
+}
+
+impl<const N: usize> fmt::Display for Standings<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let width = self
+            .results
+            .iter()
+            .map(|standing| standing.name.len())
+            .chain(std::iter::once("algorithm".len()))
+            .max()
+            .unwrap_or(0);
+        write!(
+            f,
+            "{:<width$}  {:>10}  {:>12}  {:>10}  {:>10}",
+            "algorithm",
+            "mean score",
+            format!("wins in {}", OFFICIAL_MAX_GUESSES),
+            "time",
+            "expected",
+            width = width
+        )?;
+        let cost = self.score != Score::Guesses;
+        if cost {
+            write!(f, "  {:>10}", format!("{} cost", self.score))?;
+        }
+        writeln!(f)?;
+        for standing in &self.results {
+            let mean = match standing.stats.mean() {
+                Some(mean) => format!("{:.4}", mean),
+                None => "-".to_string(),
+            };
+            let expected = match standing.estimate {
+                Some(estimate) => format!("{:.4}", estimate.expected_turns),
+                None => "-".to_string(),
+            };
+            write!(
+                f,
+                "{:<width$}  {:>10}  {:>11.2}%  {:>10.2?}  {:>10}",
+                standing.name,
+                mean,
+                standing.stats.win_rate() * 100.0,
+                standing.elapsed,
+                expected,
+                width = width
+            )?;
+            if cost {
+                match standing.stats.cost(self.score) {
+                    Some(cost) => write!(f, "  {:>10.4}", cost)?,
+                    None => write!(f, "  {:>10}", "-")?,
+                }
+            }
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::borrow::Cow;
+
+    use super::Tournament;
+    use crate::stats::Score;
+    use crate::{Estimate, Guesser, History, Wordle};
+
+    #[test]
+    fn plays_every_contender() {
+        let wordle = Wordle::with_dictionary(vec![("right", 1), ("wrong", 1)]);
+        let standings = Tournament::new(&wordle)
+            .contender("stubborn", || {
+                Box::new(guesser!(|_history| { "right".into() }))
+            })
+            .contender("careful", || {
+                Box::new(guesser!(|history| {
+                    match history.last() {
+                        Some(last) if last.word == "right" => "wrong",
+                        _ => "right",
+                    }
+                    .into()
+                }))
+            })
+            .run(&["right", "wrong"]);
+
+        let names: Vec<_> = standings.results.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["stubborn", "careful"]);
+        assert_eq!(standings.results[0].stats.win_rate(), 0.5);
+        assert_eq!(standings.results[1].stats.mean(), Some(1.5));
+        let worst = standings.results[1].worst.games();
+        assert_eq!(worst[0].answer, "wrong");
+        assert_eq!(worst[1].answer, "right");
+
+        let table = standings.to_string();
+        assert!(table.starts_with("algorithm  mean score     wins in 6"));
+        assert!(table.contains("\nstubborn       1.0000        50.00%"));
+    }
+
+    #[test]
+    fn compares_by_the_score() {
+        let wordle = Wordle::with_dictionary(vec![("right", 1), ("wrong", 1)]);
+        // Finds "right" at once but never finds "wrong", unlike the slower but steady contender.
+        let tournament = Tournament::new(&wordle)
+            .contender("quick", || {
+                Box::new(guesser!(|_history| { "right".into() }))
+            })
+            .contender("steady", || {
+                Box::new(guesser!(|history| {
+                    match history.len() {
+                        0..=1 => "right",
+                        _ => "wrong",
+                    }
+                    .into()
+                }))
+            });
+
+        let standings = tournament.run(&["right", "wrong"]);
+        assert_eq!(standings.best().unwrap().name, "quick");
+
+        let standings = tournament
+            .with_score(Score::Penalty { fail_cost: 8.0 })
+            .run(&["right", "wrong"]);
+        assert_eq!(standings.best().unwrap().name, "steady");
+        assert!(standings.to_string().contains("penalty:8 cost"));
+    }
+
+    #[test]
+    fn shows_the_estimates() {
+        struct Confident;
+        impl Guesser for Confident {
+            fn guess(&mut self, _history: &History) -> Cow<'static, str> {
+                "right".into()
+            }
+
+            fn estimate(&self, history: &History) -> Option<Estimate> {
+                Estimate::at_best(2 - history.len())
+            }
+        }
+
+        let wordle = Wordle::with_dictionary(vec![("right", 1), ("wrong", 1)]);
+        let standings = Tournament::new(&wordle)
+            .contender("confident", || Box::new(Confident))
+            .contender("silent", || {
+                Box::new(guesser!(|_history| { "right".into() }))
+            })
+            .run(&["right"]);
+
+        let estimate = standings.results[0].estimate.unwrap();
+        assert_eq!((estimate.expected_turns, estimate.win_prob), (1.5, 0.5));
+        assert_eq!(standings.results[1].estimate, None);
+        let table = standings.to_string();
+        assert!(table.lines().next().unwrap().ends_with("  expected"));
+        assert!(table.lines().nth(1).unwrap().ends_with("      1.5000"));
+        assert!(table.lines().nth(2).unwrap().ends_with("           -"));
+    }
+}
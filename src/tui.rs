@@ -0,0 +1,249 @@
+//! A terminal UI over the [`Assistant`], showing the board, the keyboard colored by the hints so
+//! far, the best suggestions and the candidates left, all at once.
+//!
+//! Turns are typed the way the `solve` command reads them, e.g. `crane GYXXX`, or only the colors
+//! to play the suggestion. Tab fills the suggestion in, and Esc quits.
+
+use std::io;
+
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Paragraph, Wrap};
+use ratatui::{DefaultTerminal, Frame};
+
+use crate::assistant::Assistant;
+use crate::keyboard::{KeyState, Keyboard, LAYOUT};
+use crate::{Correctness, Guess, Guesser};
+
+/// Runs the UI in the terminal until the player quits.
+pub fn run<G: Guesser>(guesser: G) -> io::Result<()> {
+    let mut terminal = ratatui::init();
+    let result = Tui::new(guesser).run(&mut terminal);
+    ratatui::restore();
+    result
+}
+
+/// The state of the UI: the assistant, and the line being typed.
+pub struct Tui<G> {
+    assistant: Assistant<G>,
+    input: String,
+    /// What went wrong with the last line entered, or how the game ended.
+    message: Option<String>,
+}
+
+impl<G: Guesser> Tui<G> {
+    pub fn new(guesser: G) -> Self {
+        Self {
+            assistant: Assistant::new(guesser).with_explanations(true),
+            input: String::new(),
+            message: None,
+        }
+    }
+
+    fn run(&mut self, terminal: &mut DefaultTerminal) -> io::Result<()> {
+        loop {
+            terminal.draw(|frame| self.draw(frame))?;
+            if let Event::Key(key) = event::read()? {
+                let quit =
+                    key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c');
+                if quit || (key.kind == KeyEventKind::Press && self.handle(key.code)) {
+                    return Ok(());
+                }
+            }
+        }
+    }
+
+    /// Handles a key pressed by the player, returning whether they asked to quit.
+    pub fn handle(&mut self, key: KeyCode) -> bool {
+        if self.assistant.is_solved() {
+            return true;
+        }
+
+        match key {
+            KeyCode::Esc => return true,
+            KeyCode::Char(c) => self.input.push(c),
+            KeyCode::Backspace => {
+                self.input.pop();
+            }
+            KeyCode::Tab => self.input = format!("{} ", self.assistant.suggestion().guess),
+            KeyCode::Enter => {
+                self.message = match self.assistant.enter(&self.input) {
+                    Ok(()) if self.assistant.is_solved() => Some(format!(
+                        "solved in {}! press any key to quit",
+                        self.assistant.history().len()
+                    )),
+                    Ok(()) => None,
+                    Err(e) => Some(e.to_string()),
+                };
+                self.input.clear();
+            }
+            _ => {}
+        }
+        false
+    }
+
+    /// Draws the whole UI, asking the guesser for a suggestion if it was not asked yet this turn.
+    pub fn draw(&mut self, frame: &mut Frame) {
+        let [main, status] =
+            Layout::vertical([Constraint::Min(0), Constraint::Length(1)]).areas(frame.area());
+        let [left, right] =
+            Layout::horizontal([Constraint::Length(32), Constraint::Min(0)]).areas(main);
+        let [board, keyboard] =
+            Layout::vertical([Constraint::Min(0), Constraint::Length(5)]).areas(left);
+        let [suggestions, candidates] =
+            Layout::vertical([Constraint::Length(9), Constraint::Min(0)]).areas(right);
+
+        let history = self.assistant.history();
+        let mut rows: Vec<Line> = history.iter().map(board_row).collect();
+        rows.push(Line::from(format!("> {}", self.input)));
+        frame.render_widget(
+            Paragraph::new(rows).block(Block::bordered().title("board")),
+            board,
+        );
+
+        let colors = Keyboard::from_history(history);
+        let keys: Vec<Line> = LAYOUT
+            .iter()
+            .map(|row| {
+                Line::from(
+                    row.chars()
+                        .map(|letter| {
+                            let style = match colors.state(letter) {
+                                KeyState::Unused => Style::new(),
+                                KeyState::Absent => tile(Correctness::Wrong),
+                                KeyState::Present => tile(Correctness::Misplaced),
+                                KeyState::Correct => tile(Correctness::Correct),
+                            };
+                            Span::styled(format!(" {} ", letter.to_ascii_uppercase()), style)
+                        })
+                        .collect::<Vec<_>>(),
+                )
+            })
+            .collect();
+        frame.render_widget(
+            Paragraph::new(keys).block(Block::bordered().title("keyboard")),
+            keyboard,
+        );
+
+        let first = history.is_empty();
+        let solved = self.assistant.is_solved();
+        let suggestion = self.assistant.suggestion();
+        let mut lines = vec![Line::from(vec![
+            Span::raw("play "),
+            Span::styled(
+                suggestion.guess.to_string(),
+                Style::new().add_modifier(Modifier::BOLD),
+            ),
+        ])];
+        if let Some(estimate) = &suggestion.estimate {
+            lines.push(Line::from(estimate.to_string()));
+        }
+        if let Some(explanation) = &suggestion.explanation {
+            lines.push(Line::from(format!(
+                "expected to reveal {:.2} bits",
+                explanation.entropy
+            )));
+            lines.extend(
+                explanation
+                    .alternatives
+                    .iter()
+                    .map(|(word, entropy)| Line::from(format!("  or {} ({:.2})", word, entropy))),
+            );
+        }
+        frame.render_widget(
+            Paragraph::new(lines).block(Block::bordered().title("suggestions")),
+            suggestions,
+        );
+
+        let (title, words) = match &suggestion.remaining {
+            Some(remaining) if !first && !solved => {
+                let plural = if remaining.len() == 1 { "" } else { "s" };
+                (
+                    format!("{} candidate{}", remaining.len(), plural),
+                    remaining.join(" "),
+                )
+            }
+            _ => ("candidates".to_string(), String::new()),
+        };
+        frame.render_widget(
+            Paragraph::new(words)
+                .wrap(Wrap { trim: true })
+                .block(Block::bordered().title(title)),
+            candidates,
+        );
+
+        let help = "type `word colors` or only the colors, Tab for the suggestion, Esc to quit";
+        frame.render_widget(
+            Paragraph::new(self.message.as_deref().unwrap_or(help)),
+            status,
+        );
+    }
+}
+
+/// Returns the style of a tile of the given color.
+fn tile(c: Correctness) -> Style {
+    let background = match c {
+        Correctness::Correct => Color::Green,
+        Correctness::Misplaced => Color::Yellow,
+        Correctness::Wrong => Color::DarkGray,
+    };
+    Style::new()
+        .fg(Color::White)
+        .bg(background)
+        .add_modifier(Modifier::BOLD)
+}
+
+fn board_row(guess: &Guess) -> Line<'static> {
+    Line::from(
+        guess
+            .word
+            .chars()
+            .zip(&guess.mask)
+            .map(|(letter, &c)| Span::styled(format!(" {} ", letter.to_uppercase()), tile(c)))
+            .collect::<Vec<_>>(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use ratatui::backend::TestBackend;
+    use ratatui::crossterm::event::KeyCode;
+    use ratatui::Terminal;
+
+    use super::Tui;
+    use crate::algorithms::Minimax;
+    use crate::WORDS;
+
+    fn type_line(tui: &mut Tui<Minimax>, line: &str) {
+        for c in line.chars() {
+            assert!(!tui.handle(KeyCode::Char(c)));
+        }
+        assert!(!tui.handle(KeyCode::Enter));
+    }
+
+    #[test]
+    fn plays_the_turns_typed() {
+        let mut tui = Tui::new(Minimax::with_dictionary(WORDS.iter().copied()));
+        let mut terminal = Terminal::new(TestBackend::new(80, 24)).unwrap();
+
+        type_line(&mut tui, "blush xx");
+        assert!(tui.message.as_ref().unwrap().contains("is not five colors"));
+        type_line(&mut tui, "blush xxyxg");
+        terminal.draw(|frame| tui.draw(frame)).unwrap();
+        let screen: String = terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(|cell| cell.symbol())
+            .collect();
+        assert!(screen.contains("play humph"));
+        assert!(screen.contains("1 candidate"));
+
+        type_line(&mut tui, "ggggg");
+        assert!(tui.message.as_ref().unwrap().starts_with("solved in 2!"));
+        assert!(tui.handle(KeyCode::Char('q')));
+    }
+}
@@ -0,0 +1,155 @@
+//! Checking the words of a dictionary file before playing with them, so that mistakes in a custom
+//! word list are reported with their line rather than breaking games halfway through.
+
+use std::collections::HashMap;
+use std::fmt;
+
+/// Number of diagnostics listed by the [`Display`](fmt::Display) output of [`InvalidDictionary`].
+const LISTED: usize = 10;
+
+/// Something wrong with a line of a dictionary file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Problem {
+    /// The line is not a word followed by a space and its frequency count.
+    Malformed,
+    /// The word already appeared on an earlier line, numbered from 1.
+    Duplicate { first: usize },
+    /// The word does not have the expected number of letters.
+    Length { expected: usize },
+    /// The word has other characters than lowercase letters.
+    Letters,
+    /// The word has a frequency count of zero, so it would never be picked as the answer.
+    ZeroCount,
+}
+
+/// A [`Problem`] found on a line of a dictionary file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    /// The number of the line, from 1.
+    pub line: usize,
+    /// The word on the line, or the whole line if it is malformed.
+    pub word: String,
+    pub problem: Problem,
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: ", self.line)?;
+        match &self.problem {
+            Problem::Malformed => write!(f, "expected a word and its frequency count"),
+            Problem::Duplicate { first } => {
+                write!(f, "`{}` is a duplicate of line {}", self.word, first)
+            }
+            Problem::Length { expected } => {
+                write!(f, "`{}` does not have {} letters", self.word, expected)
+            }
+            Problem::Letters => write!(f, "`{}` is not made of lowercase letters", self.word),
+            Problem::ZeroCount => write!(f, "`{}` has a frequency count of zero", self.word),
+        }
+    }
+}
+
+/// The error returned when loading a dictionary with problems, see
+/// [`Error::Dictionary`](crate::Error::Dictionary).
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("invalid dictionary:\n  {}", listing(.diagnostics))]
+pub struct InvalidDictionary {
+    /// Every problem found, in the order of the lines.
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+/// Lists the first problems one per line, and how many more there are.
+fn listing(diagnostics: &[Diagnostic]) -> String {
+    let mut listed: Vec<String> = diagnostics
+        .iter()
+        .take(LISTED)
+        .map(|diagnostic| diagnostic.to_string())
+        .collect();
+    if diagnostics.len() > LISTED {
+        listed.push(format!("and {} more", diagnostics.len() - LISTED));
+    }
+    listed.join("\n  ")
+}
+
+/// Returns every problem with the lines of the contents of a dictionary file, whose words should
+/// have `len` letters. Blank lines are ignored.
+pub fn validate(contents: &str, len: usize) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+    let mut lines: HashMap<&str, usize> = HashMap::new();
+    for (i, line) in contents.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let diagnostic = |word: &str, problem| Diagnostic {
+            line: i + 1,
+            word: word.to_string(),
+            problem,
+        };
+
+        let (word, count) = match crate::parse_dictionary_line(line) {
+            Some(parsed) => parsed,
+            None => {
+                diagnostics.push(diagnostic(line, Problem::Malformed));
+                continue;
+            }
+        };
+        if let Some(&first) = lines.get(word) {
+            diagnostics.push(diagnostic(word, Problem::Duplicate { first }));
+            continue;
+        }
+        lines.insert(word, i + 1);
+
+        if !word.chars().all(char::is_lowercase) {
+            diagnostics.push(diagnostic(word, Problem::Letters));
+        } else if word.chars().count() != len {
+            diagnostics.push(diagnostic(word, Problem::Length { expected: len }));
+        }
+        if count == 0 {
+            diagnostics.push(diagnostic(word, Problem::ZeroCount));
+        }
+    }
+    diagnostics
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{validate, Diagnostic, InvalidDictionary, Problem};
+
+    #[test]
+    fn reports_every_problem() {
+        let contents = "right 10\nwrong\n\nright 3\nrights 2\nr1ght 5\nfight 0\n";
+        let problems: Vec<(usize, Problem)> = validate(contents, 5)
+            .into_iter()
+            .map(|diagnostic| (diagnostic.line, diagnostic.problem))
+            .collect();
+        assert_eq!(
+            problems,
+            vec![
+                (2, Problem::Malformed),
+                (4, Problem::Duplicate { first: 1 }),
+                (5, Problem::Length { expected: 5 }),
+                (6, Problem::Letters),
+                (7, Problem::ZeroCount),
+            ]
+        );
+    }
+
+    #[test]
+    fn builtin_dictionary_is_valid() {
+        assert_eq!(validate(crate::DICTIONARY, 5), vec![]);
+    }
+
+    #[test]
+    fn lists_the_first_problems() {
+        let diagnostics = (1..=12)
+            .map(|line| Diagnostic {
+                line,
+                word: "fight".to_string(),
+                problem: Problem::ZeroCount,
+            })
+            .collect();
+        let message = InvalidDictionary { diagnostics }.to_string();
+        assert!(message.starts_with("invalid dictionary:\n  line 1: `fight` has a frequency"));
+        assert!(message.ends_with("\n  and 2 more"));
+    }
+}
@@ -0,0 +1,159 @@
+//! JavaScript bindings, so that the solver can power a web page.
+//!
+//! Build them with `wasm-pack build --target web -- --no-default-features --features wasm`. The
+//! `wasm` feature only builds on `solver`, which leaves out reading files, the clock and the
+//! command line crates.
+//!
+//! Guessers hand out words borrowed from their dictionary for the rest of the program, so the
+//! dictionaries given to [`Solver::with_dictionary`] are kept alive until the page is closed. Each
+//! distinct dictionary is only kept once, however many solvers are created over it.
+
+use std::borrow::Cow;
+use std::collections::HashSet;
+use std::sync::Mutex;
+
+use wasm_bindgen::prelude::*;
+
+use crate::algorithms::Naive;
+use crate::{parse_dictionary, validate_word, Correctness, Guess, Guesser, History};
+
+/// Returns the colors revealed by playing `guess` when the answer is `answer`, in the format of
+/// [`Correctness::parse_mask`], e.g. `"GYXXX"`.
+#[wasm_bindgen]
+pub fn compute(answer: &str, guess: &str) -> Result<String, JsError> {
+    if answer.chars().count() != 5 || guess.chars().count() != 5 {
+        return Err(JsError::new("expected two five-letter words"));
+    }
+
+    let mask: [Correctness; 5] = Correctness::compute(answer, guess);
+    Ok(mask.iter().map(|c| c.to_char()).collect())
+}
+
+/// Returns the candidates that could still be the answer after `guess` revealed `mask`.
+#[wasm_bindgen]
+pub fn filter(candidates: Vec<String>, guess: &str, mask: &str) -> Result<Vec<String>, JsError> {
+    let guess = parse_guess(guess, mask)?;
+    Ok(candidates
+        .into_iter()
+        .filter(|word| word.chars().count() == 5 && guess.matches(word))
+        .collect())
+}
+
+/// Suggests guesses for a puzzle being played in the page, like the `solve` command does.
+#[wasm_bindgen]
+pub struct Solver {
+    guesser: Naive,
+    history: History,
+}
+
+#[wasm_bindgen]
+impl Solver {
+    /// Creates a solver over the built-in dictionary.
+    ///
+    /// The first suggestion takes a while over the whole dictionary, so consider hardcoding it.
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Solver {
+        Self::with_guesser(Naive::new())
+    }
+
+    /// Creates a solver over a dictionary in the format of [`crate::load_dictionary`], e.g. one
+    /// fetched by the page.
+    #[wasm_bindgen(js_name = withDictionary)]
+    pub fn with_dictionary(contents: String) -> Result<Solver, JsError> {
+        let dictionary = parse_dictionary(intern(contents))?;
+        Ok(Self::with_guesser(Naive::with_dictionary(dictionary)))
+    }
+
+    fn with_guesser(guesser: Naive) -> Solver {
+        Solver {
+            guesser,
+            history: History::new(),
+        }
+    }
+
+    /// Returns the word to play next.
+    ///
+    /// Fails if no word of the dictionary agrees with the colors recorded so far, e.g. because
+    /// some were mistyped.
+    pub fn suggest(&mut self) -> Result<String, JsError> {
+        let guess = self.guesser.guess(&self.history);
+        if self
+            .guesser
+            .remaining()
+            .is_some_and(|remaining| remaining.is_empty())
+        {
+            return Err(JsError::new("no word agrees with all the colors so far"));
+        }
+        Ok(guess.into_owned())
+    }
+
+    /// Records the colors revealed by playing `word`, which does not have to be the suggestion.
+    pub fn record(&mut self, word: &str, mask: &str) -> Result<(), JsError> {
+        let guess = parse_guess(word, mask)?;
+        self.history.push(guess);
+        Ok(())
+    }
+}
+
+impl Default for Solver {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Returns the same contents as `contents`, kept alive for the rest of the program, and only once
+/// however many times they are interned.
+fn intern(contents: String) -> &'static str {
+    static INTERNED: Mutex<Option<HashSet<&'static str>>> = Mutex::new(None);
+
+    let mut interned = INTERNED.lock().unwrap_or_else(|e| e.into_inner());
+    let interned = interned.get_or_insert_with(HashSet::new);
+    if let Some(&contents) = interned.get(contents.as_str()) {
+        return contents;
+    }
+    let contents: &'static str = Box::leak(contents.into_boxed_str());
+    interned.insert(contents);
+    contents
+}
+
+fn parse_guess(word: &str, mask: &str) -> Result<Guess, JsError> {
+    validate_word::<5>(word).map_err(|e| JsError::new(&e.to_string()))?;
+
+    let mask = Correctness::parse_mask(mask)
+        .ok_or_else(|| JsError::new(&format!("`{}` is not five colors out of G, Y and X", mask)))?;
+    Ok(Guess::new(Cow::Owned(word.to_string()), mask))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{compute, filter, intern};
+
+    // Only the successful paths can be tested natively, since building a `JsError` calls into
+    // JavaScript.
+
+    #[test]
+    fn computes_masks() {
+        assert_eq!(compute("right", "wrong").unwrap(), "XYXXY");
+    }
+
+    #[test]
+    fn filters_candidates() {
+        let candidates = vec![
+            "fight".to_string(),
+            "light".to_string(),
+            "wrong".to_string(),
+        ];
+        assert_eq!(
+            filter(candidates, "right", "XGGGG").unwrap(),
+            vec!["fight".to_string(), "light".to_string()]
+        );
+    }
+
+    #[test]
+    fn interns_dictionaries_once() {
+        let first = intern("right 10\nwrong 20\n".to_string());
+        let second = intern("right 10\nwrong 20\n".to_string());
+        assert!(std::ptr::eq(first, second));
+        assert!(!std::ptr::eq(first, intern("fight 5\n".to_string())));
+    }
+}
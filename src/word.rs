@@ -0,0 +1,151 @@
+//! Words of a fixed number of lowercase ASCII letters, which compare and hash as plain bytes.
+
+use alloc::string::{String, ToString};
+use core::convert::{TryFrom, TryInto};
+use core::fmt;
+use core::str::FromStr;
+
+use serde::de::Error;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::Correctness;
+
+/// A word of `N` lowercase ASCII letters, like every word of the built-in dictionary.
+///
+/// Words of any other length or with other letters are not representable, so code handed a
+/// `Word` can go through its bytes without checking them, e.g. with
+/// [`Correctness::compute_bytes`].
+///
+/// Only the pattern cache and `Dictionary::letters` go through `Word`s: `Guess`, `Guesser` and
+/// `Wordle` keep working on strings, since dictionaries with other letters, e.g. `ñ`, are
+/// supported and could not be spelled as `N` bytes.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Word<const N: usize = 5>([u8; N]);
+
+impl<const N: usize> Word<N> {
+    /// Returns the word spelled by `word`, if it is made of `N` lowercase ASCII letters.
+    pub fn new(word: &str) -> Option<Self> {
+        let bytes: [u8; N] = word.as_bytes().try_into().ok()?;
+        Self::from_bytes(bytes)
+    }
+
+    /// Returns the word spelled by `bytes`, if they are all lowercase ASCII letters.
+    pub fn from_bytes(bytes: [u8; N]) -> Option<Self> {
+        bytes
+            .iter()
+            .all(u8::is_ascii_lowercase)
+            .then_some(Self(bytes))
+    }
+
+    pub fn as_bytes(&self) -> &[u8; N] {
+        &self.0
+    }
+
+    pub fn as_str(&self) -> &str {
+        core::str::from_utf8(&self.0).expect("words are ASCII")
+    }
+
+    /// Returns the mask the game reveals when `guess` is played against this word as the answer,
+    /// like [`Correctness::compute`] without going through strings.
+    pub fn mask(&self, guess: &Self) -> [Correctness; N] {
+        Correctness::compute_bytes(&self.0, &guess.0)
+    }
+}
+
+/// The error returned when a string is not a [`Word`].
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("`{word}` is not a word of {len} lowercase letters")]
+pub struct InvalidWord {
+    pub word: String,
+    /// The number of letters words were expected to have.
+    pub len: usize,
+}
+
+impl<const N: usize> TryFrom<&str> for Word<N> {
+    type Error = InvalidWord;
+
+    fn try_from(word: &str) -> Result<Self, Self::Error> {
+        Self::new(word).ok_or_else(|| InvalidWord {
+            word: word.to_string(),
+            len: N,
+        })
+    }
+}
+
+impl<const N: usize> FromStr for Word<N> {
+    type Err = InvalidWord;
+
+    fn from_str(word: &str) -> Result<Self, Self::Err> {
+        Self::try_from(word)
+    }
+}
+
+impl<const N: usize> fmt::Display for Word<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl<const N: usize> fmt::Debug for Word<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.as_str(), f)
+    }
+}
+
+impl<const N: usize> Serialize for Word<N> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de, const N: usize> Deserialize<'de> for Word<N> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let word = String::deserialize(deserializer)?;
+        Self::try_from(word.as_str()).map_err(D::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Word;
+    use crate::Correctness;
+
+    #[test]
+    fn valid() {
+        let word: Word = "crane".parse().unwrap();
+        assert_eq!(word.as_str(), "crane");
+        assert_eq!(word.as_bytes(), b"crane");
+        assert_eq!(word.to_string(), "crane");
+        assert_eq!(format!("{:?}", word), "\"crane\"");
+        assert_eq!(
+            Word::<3>::new("cat").map(|w| w.to_string()),
+            Some("cat".into())
+        );
+    }
+
+    #[test]
+    fn invalid() {
+        for word in ["cran", "cranes", "Crane", "cr4ne", "señor"] {
+            assert_eq!(Word::<5>::new(word), None, "{}", word);
+        }
+        assert_eq!(
+            "cranes".parse::<Word>().unwrap_err().to_string(),
+            "`cranes` is not a word of 5 lowercase letters"
+        );
+    }
+
+    #[test]
+    fn mask() {
+        let answer: Word = Word::new("abbey").unwrap();
+        let guess = Word::new("kebab").unwrap();
+        assert_eq!(answer.mask(&guess), Correctness::compute("abbey", "kebab"));
+    }
+
+    #[test]
+    fn serde() {
+        let word = Word::<5>::new("crane").unwrap();
+        assert_eq!(serde_json::to_string(&word).unwrap(), "\"crane\"");
+        assert_eq!(serde_json::from_str::<Word>("\"crane\"").unwrap(), word);
+        assert!(serde_json::from_str::<Word>("\"crate!\"").is_err());
+    }
+}